@@ -0,0 +1,49 @@
+//! Times the three ways to interpolate `n` values: solving the Vandermonde system in `O(n³)`,
+//! [`Polynomial::lagrange_interpolation`], which switches to a product tree above 64 points, and the
+//! inverse NTT in `O(n log n)`.
+//!
+//! Usage: `cargo run --release -p algebra --example interpolation -- [log2 of the largest size]`
+use algebra::domain::EvaluationDomain;
+use algebra::finite_field::FiniteField;
+use algebra::polynomial::Polynomial;
+use std::sync::Arc;
+use std::time::Instant;
+
+fn main() {
+    let max_log_size: u32 = std::env::args()
+        .nth(1)
+        .map(|arg| arg.parse().expect("log2 of the size must be an integer"))
+        .unwrap_or(8);
+
+    // 3 * 2^30 + 1, with power-of-two subgroups up to 2^30
+    let finite_field = Arc::new(FiniteField::new(3221225473, 5));
+    println!(
+        "{:>6} {:>16} {:>16} {:>16}",
+        "n", "vandermonde (ms)", "lagrange (ms)", "ntt (ms)"
+    );
+    for log_size in 2..=max_log_size {
+        let n = 1 << log_size;
+        let domain = EvaluationDomain::new(&finite_field, n).expect("the subgroup exists");
+        let values: Vec<_> = (0..n).map(|_| finite_field.random_element()).collect();
+        let points: Vec<_> = domain.elements().zip(values.iter().cloned()).collect();
+
+        let start = Instant::now();
+        let vandermonde = Polynomial::interpolate_vandermonde(&points, Arc::clone(&finite_field))
+            .expect("the points are distinct");
+        let vandermonde_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+        let start = Instant::now();
+        let lagrange = Polynomial::lagrange_interpolation(&points, Arc::clone(&finite_field));
+        let lagrange_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+        let start = Instant::now();
+        let ntt = Polynomial::interpolate(&domain, &values).expect("one value per point");
+        let ntt_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+        assert!(vandermonde == lagrange && lagrange == ntt);
+        println!(
+            "{:>6} {:>16.3} {:>16.3} {:>16.3}",
+            n, vandermonde_ms, lagrange_ms, ntt_ms
+        );
+    }
+}
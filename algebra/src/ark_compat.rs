@@ -0,0 +1,135 @@
+//! Conversions to and from [arkworks](https://github.com/arkworks-rs) types, enabled with the `ark-compat` feature.
+//!
+//! Conversions only succeed when the arkworks field has the same modulus as our `FiniteField`.
+use crate::error::AlgebraError;
+use crate::finite_field::{FieldElement, FieldSize, FiniteField};
+use crate::polynomial::Polynomial;
+use ark_ff::{BigInteger, PrimeField};
+use ark_poly::univariate::DensePolynomial;
+use ark_poly::DenseUVPolynomial;
+use std::sync::Arc;
+
+impl FiniteField {
+    pub fn matches_ark_modulus<F: PrimeField>(&self) -> bool {
+        let mut modulus = F::MODULUS.to_bytes_le();
+        while modulus.last() == Some(&0) {
+            modulus.pop();
+        }
+        let mut prime = self.prime.to_le_bytes().to_vec();
+        while prime.last() == Some(&0) {
+            prime.pop();
+        }
+        modulus == prime
+    }
+}
+
+impl FieldElement {
+    pub fn to_ark<F: PrimeField>(&self) -> Result<F, AlgebraError> {
+        if !self.finite_field().matches_ark_modulus::<F>() {
+            return Err(AlgebraError::ModulusMismatch(self.finite_field().prime));
+        }
+        Ok(F::from(self.value() as u128))
+    }
+
+    pub fn from_ark<F: PrimeField>(
+        value: &F,
+        finite_field: &Arc<FiniteField>,
+    ) -> Result<Self, AlgebraError> {
+        if !finite_field.matches_ark_modulus::<F>() {
+            return Err(AlgebraError::ModulusMismatch(finite_field.prime));
+        }
+        // the modulus fits in a FieldSize, so does every canonical element
+        let mut bytes = [0u8; 16];
+        for (byte, value_byte) in bytes.iter_mut().zip(value.into_bigint().to_bytes_le()) {
+            *byte = value_byte;
+        }
+        Ok(finite_field.element(FieldSize::from_le_bytes(bytes)))
+    }
+}
+
+impl Polynomial {
+    pub fn to_ark<F: PrimeField>(&self) -> Result<DensePolynomial<F>, AlgebraError> {
+        let coefficients = self
+            .coefficients
+            .iter()
+            .map(|coeff| coeff.to_ark())
+            .collect::<Result<Vec<F>, _>>()?;
+        Ok(DensePolynomial::from_coefficients_vec(coefficients))
+    }
+
+    pub fn from_ark<F: PrimeField>(
+        polynomial: &DensePolynomial<F>,
+        finite_field: Arc<FiniteField>,
+    ) -> Result<Self, AlgebraError> {
+        let coefficients = polynomial
+            .coeffs()
+            .iter()
+            .map(|coeff| FieldElement::from_ark(coeff, &finite_field))
+            .collect::<Result<Vec<FieldElement>, _>>()?;
+        Ok(Polynomial::new(coefficients, finite_field))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::AlgebraError;
+    use crate::finite_field::{FieldElement, FiniteField};
+    use crate::polynomial::Polynomial;
+    use ark_ff::fields::{Fp64, MontBackend, MontConfig};
+    use ark_poly::univariate::DensePolynomial;
+    use ark_poly::Polynomial as _;
+    use std::sync::Arc;
+
+    #[derive(MontConfig)]
+    #[modulus = "97"]
+    #[generator = "5"]
+    struct F97Config;
+    type F97 = Fp64<MontBackend<F97Config, 1>>;
+
+    #[test]
+    fn test_field_element_round_trip() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        for i in 0..97 {
+            let element = finite_field.element(i);
+            let ark_element: F97 = element.to_ark().unwrap();
+            assert_eq!(ark_element, F97::from(i as u64));
+            assert_eq!(
+                FieldElement::from_ark(&ark_element, &finite_field).unwrap(),
+                element
+            );
+        }
+
+        let other_field = Arc::new(FiniteField::new(13, 2));
+        assert_eq!(
+            other_field.one().to_ark::<F97>(),
+            Err(AlgebraError::ModulusMismatch(13))
+        );
+    }
+
+    #[test]
+    fn test_polynomial_cross_check() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let polynomial1 = Polynomial::from_slice(&[2, 7, 7], Arc::clone(&finite_field));
+        let polynomial2 = Polynomial::from_slice(&[3, 5], Arc::clone(&finite_field));
+
+        let ark_polynomial1: DensePolynomial<F97> = polynomial1.to_ark().unwrap();
+        let ark_polynomial2: DensePolynomial<F97> = polynomial2.to_ark().unwrap();
+
+        let product = &polynomial1 * &polynomial2;
+        let ark_product = &ark_polynomial1 * &ark_polynomial2;
+        assert_eq!(
+            Polynomial::from_ark(&ark_product, Arc::clone(&finite_field)).unwrap(),
+            product
+        );
+
+        let point = finite_field.element(42);
+        assert_eq!(
+            FieldElement::from_ark(
+                &ark_product.evaluate(&point.to_ark().unwrap()),
+                &finite_field
+            )
+            .unwrap(),
+            product.evaluate(point)
+        );
+    }
+}
@@ -0,0 +1,138 @@
+//! Evaluating the interpolant of values over a fixed set of points without its coefficients.
+//!
+//! With the weights `w_i = 1 / Π_{j != i} (x_i - x_j)`, the barycentric formula
+//! `f(z) = Σ w_i y_i / (z - x_i) / Σ w_i / (z - x_i)` costs `O(n)` per query, with a single inversion.
+use crate::domain::EvaluationDomain;
+use crate::error::AlgebraError;
+use crate::finite_field::{FieldElement, FieldSize};
+
+#[derive(Debug, Clone)]
+pub struct BarycentricEvaluator {
+    points: Vec<FieldElement>,
+    weights: Vec<FieldElement>,
+}
+
+impl BarycentricEvaluator {
+    /// `O(n²)` weights for arbitrary distinct points
+    pub fn new(points: Vec<FieldElement>) -> Result<Self, AlgebraError> {
+        let denominators: Vec<_> = points
+            .iter()
+            .enumerate()
+            .map(|(i, x_i)| {
+                let mut denominator = x_i.finite_field().one();
+                for (j, x_j) in points.iter().enumerate() {
+                    if i != j {
+                        let difference = x_i - x_j;
+                        if difference == x_i.finite_field().zero() {
+                            return Err(AlgebraError::DuplicatePoint(x_i.value()));
+                        }
+                        denominator = &denominator * &difference;
+                    }
+                }
+                Ok(denominator)
+            })
+            .collect::<Result<_, _>>()?;
+        let weights = FieldElement::batch_inverse(&denominators);
+        Ok(Self { points, weights })
+    }
+
+    /// `O(n)` weights for a coset `gH` of size `n`: the zerofier is `x^n - g^n`, its derivative at
+    /// `x_i` is `n x_i^(n - 1) = n g^n / x_i`, so `w_i = x_i / (n g^n)`
+    pub fn from_domain(domain: &EvaluationDomain) -> Self {
+        let n = domain.size();
+        let scale = (&domain.finite_field().element(n as FieldSize)
+            * &domain.offset().pow(n as u128))
+            .inverse();
+        let points: Vec<_> = domain.elements().collect();
+        let weights = points.iter().map(|x| x * &scale).collect();
+        Self { points, weights }
+    }
+
+    pub fn points(&self) -> &[FieldElement] {
+        &self.points
+    }
+
+    pub fn weights(&self) -> &[FieldElement] {
+        &self.weights
+    }
+
+    /// The interpolant of `values` at `z`, the value itself when `z` is one of the points
+    pub fn evaluate(
+        &self,
+        values: &[FieldElement],
+        z: &FieldElement,
+    ) -> Result<FieldElement, AlgebraError> {
+        if values.len() != self.points.len() {
+            return Err(AlgebraError::EvaluationCountMismatch {
+                expected: self.points.len(),
+                found: values.len(),
+            });
+        }
+        let finite_field = z.finite_field();
+        let differences: Vec<_> = self.points.iter().map(|x| z - x).collect();
+        if let Some(i) = differences.iter().position(|d| *d == finite_field.zero()) {
+            return Ok(values[i].clone());
+        }
+        let inverses = FieldElement::batch_inverse(&differences);
+        let mut numerator = finite_field.zero();
+        let mut denominator = finite_field.zero();
+        for ((weight, inverse), value) in self.weights.iter().zip(&inverses).zip(values) {
+            let term = weight * inverse;
+            numerator += &(&term * value);
+            denominator += &term;
+        }
+        Ok(&numerator / &denominator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::barycentric::BarycentricEvaluator;
+    use crate::domain::EvaluationDomain;
+    use crate::error::AlgebraError;
+    use crate::finite_field::FiniteField;
+    use crate::poly;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_evaluate() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let f = poly!(finite_field, [3, 1, 4, 1, 5]);
+        let points: Vec<_> = finite_field.elements([2, 9, 17, 40, 63]).collect();
+        let values: Vec<_> = points.iter().map(|x| f.evaluate(x.clone())).collect();
+        let evaluator = BarycentricEvaluator::new(points.clone()).unwrap();
+
+        for z in finite_field.elements([0, 1, 50, 96]) {
+            assert_eq!(evaluator.evaluate(&values, &z), Ok(f.evaluate(z)));
+        }
+        assert_eq!(
+            evaluator.evaluate(&values, &points[3]),
+            Ok(values[3].clone())
+        );
+        assert_eq!(
+            evaluator.evaluate(&values[..4], &points[0]),
+            Err(AlgebraError::EvaluationCountMismatch {
+                expected: 5,
+                found: 4
+            })
+        );
+        assert_eq!(
+            BarycentricEvaluator::new(finite_field.elements([1, 2, 1]).collect()).err(),
+            Some(AlgebraError::DuplicatePoint(1))
+        );
+    }
+
+    #[test]
+    fn test_from_domain() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let domain = EvaluationDomain::coset(&finite_field, 8, finite_field.element(5)).unwrap();
+        let evaluator = BarycentricEvaluator::from_domain(&domain);
+        let generic = BarycentricEvaluator::new(domain.elements().collect()).unwrap();
+        assert_eq!(evaluator.weights(), generic.weights());
+
+        let f = poly!(finite_field, [8, 6, 7, 5, 3, 0, 9]);
+        let values = f.evaluate_on(&domain);
+        let z = finite_field.element(2);
+        assert_eq!(evaluator.evaluate(&values, &z), Ok(f.evaluate(z)));
+    }
+}
@@ -0,0 +1,631 @@
+//! Prime fields with a modulus of up to 256 bits, like the Stark-252 prime.
+//!
+//! Values are [`U256`]s of four little-endian `u64` limbs. Products are reduced with Montgomery
+//! multiplication: `mont(a, b) = a * b / 2^256 (mod p)` only needs multiplications and shifts by whole
+//! limbs, and a second multiplication by `2^512 mod p` removes the `2^256` factor, so elements are kept
+//! in their canonical form.
+use crate::error::AlgebraError;
+use crate::field::Field;
+use rand::Rng;
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::sync::Arc;
+
+/// 2^251 + 17 * 2^192 + 1
+pub const STARK_252_PRIME: U256 = U256([1, 0, 0, 0x0800_0000_0000_0011]);
+/// generator of the multiplicative group of the Stark-252 field
+pub const STARK_252_GENERATOR: U256 = U256([3, 0, 0, 0]);
+
+/// the first 20 primes, as in [`is_prime`](crate::finite_field::is_prime)
+const WITNESSES: [u64; 20] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71,
+];
+
+/// unsigned 256 bit integer, the limbs are little-endian
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct U256(pub [u64; 4]);
+
+impl U256 {
+    pub const ZERO: Self = Self([0; 4]);
+    pub const ONE: Self = Self([1, 0, 0, 0]);
+
+    pub fn limbs(&self) -> [u64; 4] {
+        self.0
+    }
+
+    pub fn is_zero(&self) -> bool {
+        *self == Self::ZERO
+    }
+
+    pub fn bits(&self) -> u32 {
+        match self.0.iter().rposition(|limb| *limb != 0) {
+            Some(i) => 64 * i as u32 + 64 - self.0[i].leading_zeros(),
+            None => 0,
+        }
+    }
+
+    pub fn bit(&self, i: u32) -> bool {
+        (self.0[i as usize / 64] >> (i % 64)) & 1 == 1
+    }
+
+    pub fn to_le_bytes(&self) -> [u8; 32] {
+        let mut bytes = [0; 32];
+        for (chunk, limb) in bytes.chunks_exact_mut(8).zip(self.0) {
+            chunk.copy_from_slice(&limb.to_le_bytes());
+        }
+        bytes
+    }
+
+    pub fn from_le_bytes(bytes: [u8; 32]) -> Self {
+        let mut limbs = [0; 4];
+        for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks_exact(8)) {
+            *limb = u64::from_le_bytes(chunk.try_into().unwrap());
+        }
+        Self(limbs)
+    }
+
+    fn overflowing_add(&self, rhs: &Self) -> (Self, bool) {
+        let mut result = [0; 4];
+        let mut carry = false;
+        for (i, limb) in result.iter_mut().enumerate() {
+            let (sum, c1) = self.0[i].overflowing_add(rhs.0[i]);
+            let (sum, c2) = sum.overflowing_add(carry as u64);
+            *limb = sum;
+            carry = c1 || c2;
+        }
+        (Self(result), carry)
+    }
+
+    fn overflowing_sub(&self, rhs: &Self) -> (Self, bool) {
+        let mut result = [0; 4];
+        let mut borrow = false;
+        for (i, limb) in result.iter_mut().enumerate() {
+            let (difference, b1) = self.0[i].overflowing_sub(rhs.0[i]);
+            let (difference, b2) = difference.overflowing_sub(borrow as u64);
+            *limb = difference;
+            borrow = b1 || b2;
+        }
+        (Self(result), borrow)
+    }
+
+    fn shr(&self, n: u32) -> Self {
+        let mut result = [0; 4];
+        let (limbs, bits) = (n as usize / 64, n % 64);
+        for (i, limb) in result.iter_mut().enumerate().take(4 - limbs.min(4)) {
+            let low = self.0[i + limbs] >> bits;
+            let high = match self.0.get(i + limbs + 1) {
+                Some(next) if bits > 0 => next << (64 - bits),
+                _ => 0,
+            };
+            *limb = low | high;
+        }
+        Self(result)
+    }
+
+    /// quotient and remainder of the division by a single limb
+    fn div_rem_u64(&self, divisor: u64) -> (Self, u64) {
+        let mut quotient = [0; 4];
+        let mut remainder = 0u128;
+        for i in (0..4).rev() {
+            let current = remainder << 64 | self.0[i] as u128;
+            quotient[i] = (current / divisor as u128) as u64;
+            remainder = current % divisor as u128;
+        }
+        (Self(quotient), remainder as u64)
+    }
+}
+
+impl Ord for U256 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
+impl PartialOrd for U256 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<u64> for U256 {
+    fn from(value: u64) -> Self {
+        Self([value, 0, 0, 0])
+    }
+}
+
+impl From<u128> for U256 {
+    fn from(value: u128) -> Self {
+        Self([value as u64, (value >> 64) as u64, 0, 0])
+    }
+}
+
+impl Display for U256 {
+    /// decimal, in chunks of 19 digits
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        const CHUNK: u64 = 10_000_000_000_000_000_000;
+        let mut chunks = vec![];
+        let mut value = *self;
+        loop {
+            let (quotient, remainder) = value.div_rem_u64(CHUNK);
+            chunks.push(remainder);
+            if quotient.is_zero() {
+                break;
+            }
+            value = quotient;
+        }
+        write!(f, "{}", chunks.pop().unwrap())?;
+        for chunk in chunks.iter().rev() {
+            write!(f, "{chunk:019}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BigPrimeField {
+    pub prime: U256,
+    pub generator: U256,
+    /// `-p^-1 mod 2^64`
+    inv: u64,
+    /// `2^512 mod p`
+    r2: U256,
+}
+
+impl BigPrimeField {
+    pub fn new(prime: U256, generator: U256) -> Self {
+        Self::try_new(prime, generator).expect("Invalid field parameters")
+    }
+
+    /// Fails if the modulus is not an odd prime. The generator is only checked to be non-zero, since
+    /// checking its order would need the factorization of `p - 1`.
+    pub fn try_new(prime: U256, generator: U256) -> Result<Self, AlgebraError> {
+        if prime.0[0] & 1 == 0 || prime <= U256::ONE {
+            return Err(AlgebraError::CompositeBigModulus(prime));
+        }
+        let finite_field = Self::new_unchecked(prime, generator);
+        if !finite_field.is_prime() {
+            return Err(AlgebraError::CompositeBigModulus(prime));
+        }
+        if finite_field.reduce(generator).is_zero() {
+            return Err(AlgebraError::InvalidGenerator(0));
+        }
+        Ok(finite_field)
+    }
+
+    /// Skips the primality test. The modulus must still be odd for Montgomery multiplication.
+    pub fn new_unchecked(prime: U256, generator: U256) -> Self {
+        assert_eq!(prime.0[0] & 1, 1, "The modulus must be odd");
+        // Newton's iteration doubles the number of correct low bits of p^-1 each step
+        let mut inv = 1u64;
+        for _ in 0..6 {
+            inv = inv.wrapping_mul(2u64.wrapping_sub(prime.0[0].wrapping_mul(inv)));
+        }
+        let mut finite_field = Self {
+            prime,
+            generator,
+            inv: inv.wrapping_neg(),
+            r2: U256::ZERO,
+        };
+        // 2^512 mod p by doubling, the Montgomery form isn't available before r2 is known
+        let mut r2 = U256::ONE;
+        for _ in 0..512 {
+            r2 = finite_field.add(&r2, &r2);
+        }
+        finite_field.r2 = r2;
+        finite_field
+    }
+
+    /// the Stark-252 field
+    pub fn stark_252() -> Self {
+        Self::new_unchecked(STARK_252_PRIME, STARK_252_GENERATOR)
+    }
+
+    pub fn element(self: &Arc<Self>, value: U256) -> BigFieldElement {
+        BigFieldElement {
+            value: self.reduce(value),
+            finite_field: Arc::clone(self),
+        }
+    }
+
+    pub fn zero(self: &Arc<Self>) -> BigFieldElement {
+        self.element(U256::ZERO)
+    }
+
+    pub fn one(self: &Arc<Self>) -> BigFieldElement {
+        self.element(U256::ONE)
+    }
+
+    pub fn generator(self: &Arc<Self>) -> BigFieldElement {
+        self.element(self.generator)
+    }
+
+    pub fn random_element(self: &Arc<Self>) -> BigFieldElement {
+        self.sample(&mut rand::thread_rng())
+    }
+
+    /// uniform element drawn from `rng` by rejection
+    pub fn sample<R: Rng + ?Sized>(self: &Arc<Self>, rng: &mut R) -> BigFieldElement {
+        let bits = self.prime.bits();
+        loop {
+            let mut value = U256([
+                rng.next_u64(),
+                rng.next_u64(),
+                rng.next_u64(),
+                rng.next_u64(),
+            ]);
+            for (i, limb) in value.0.iter_mut().enumerate() {
+                let low = (64 * i as u32).min(bits);
+                let kept = (bits - low).min(64);
+                *limb &= if kept == 64 {
+                    u64::MAX
+                } else {
+                    (1 << kept) - 1
+                };
+            }
+            if value < self.prime {
+                return BigFieldElement {
+                    value,
+                    finite_field: Arc::clone(self),
+                };
+            }
+        }
+    }
+
+    pub fn random_elements<R: Rng + ?Sized>(
+        self: &Arc<Self>,
+        n: usize,
+        rng: &mut R,
+    ) -> Vec<BigFieldElement> {
+        (0..n).map(|_| self.sample(rng)).collect()
+    }
+
+    /// `x mod p` for any 256 bit `x`, as `mont(mont(x, 2^512), 1) = x * 2^256 / 2^256`
+    fn reduce(&self, x: U256) -> U256 {
+        self.montgomery(&self.montgomery(&x, &self.r2), &U256::ONE)
+    }
+
+    fn add(&self, a: &U256, b: &U256) -> U256 {
+        let (sum, carry) = a.overflowing_add(b);
+        if carry || sum >= self.prime {
+            sum.overflowing_sub(&self.prime).0
+        } else {
+            sum
+        }
+    }
+
+    fn sub(&self, a: &U256, b: &U256) -> U256 {
+        let (difference, borrow) = a.overflowing_sub(b);
+        if borrow {
+            difference.overflowing_add(&self.prime).0
+        } else {
+            difference
+        }
+    }
+
+    fn mul(&self, a: &U256, b: &U256) -> U256 {
+        self.montgomery(&self.montgomery(a, b), &self.r2)
+    }
+
+    /// `a * b / 2^256 mod p` for `a * b < p * 2^256`, coarsely integrated operand scanning
+    #[allow(clippy::needless_range_loop)]
+    fn montgomery(&self, a: &U256, b: &U256) -> U256 {
+        let p = &self.prime.0;
+        let mut t = [0u64; 6];
+        for i in 0..4 {
+            let mut carry = 0u128;
+            for j in 0..4 {
+                let product = t[j] as u128 + a.0[j] as u128 * b.0[i] as u128 + carry;
+                t[j] = product as u64;
+                carry = product >> 64;
+            }
+            let sum = t[4] as u128 + carry;
+            t[4] = sum as u64;
+            t[5] = (sum >> 64) as u64;
+
+            // m makes t + m * p divisible by 2^64
+            let m = t[0].wrapping_mul(self.inv);
+            let mut carry = (t[0] as u128 + m as u128 * p[0] as u128) >> 64;
+            for j in 1..4 {
+                let product = t[j] as u128 + m as u128 * p[j] as u128 + carry;
+                t[j - 1] = product as u64;
+                carry = product >> 64;
+            }
+            let sum = t[4] as u128 + carry;
+            t[3] = sum as u64;
+            t[4] = t[5] + (sum >> 64) as u64;
+        }
+        let result = U256([t[0], t[1], t[2], t[3]]);
+        if t[4] != 0 || result >= self.prime {
+            result.overflowing_sub(&self.prime).0
+        } else {
+            result
+        }
+    }
+
+    fn pow(&self, base: &U256, exponent: &U256) -> U256 {
+        let mut result = U256::ONE;
+        for i in (0..exponent.bits()).rev() {
+            result = self.mul(&result, &result);
+            if exponent.bit(i) {
+                result = self.mul(&result, base);
+            }
+        }
+        result
+    }
+
+    /// Miller-Rabin with the first 20 primes as witnesses
+    fn is_prime(&self) -> bool {
+        let n = self.prime;
+        for witness in WITNESSES {
+            if n.div_rem_u64(witness).1 == 0 {
+                return n == U256::from(witness);
+            }
+        }
+
+        let minus_one = n.overflowing_sub(&U256::ONE).0;
+        let s = (0..minus_one.bits())
+            .take_while(|i| !minus_one.bit(*i))
+            .count() as u32;
+        let d = minus_one.shr(s);
+        'witness: for witness in WITNESSES {
+            let mut x = self.pow(&U256::from(witness), &d);
+            if x == U256::ONE || x == minus_one {
+                continue;
+            }
+            for _ in 1..s {
+                x = self.mul(&x, &x);
+                if x == minus_one {
+                    continue 'witness;
+                }
+            }
+            return false;
+        }
+        true
+    }
+}
+
+/// canonical element of a [`BigPrimeField`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BigFieldElement {
+    value: U256,
+    finite_field: Arc<BigPrimeField>,
+}
+
+impl BigFieldElement {
+    pub fn value(&self) -> U256 {
+        self.value
+    }
+
+    pub fn finite_field(&self) -> &Arc<BigPrimeField> {
+        &self.finite_field
+    }
+
+    pub fn pow(&self, exponent: &U256) -> Self {
+        self.with_value(self.finite_field.pow(&self.value, exponent))
+    }
+
+    pub fn inverse(&self) -> Self {
+        self.try_inverse()
+            .expect("Cannot compute the inverse of zero")
+    }
+
+    /// `x^(p - 2)`
+    pub fn try_inverse(&self) -> Result<Self, AlgebraError> {
+        if self.value.is_zero() {
+            return Err(AlgebraError::DivisionByZero);
+        }
+        let exponent = self.finite_field.prime.overflowing_sub(&U256::from(2u64)).0;
+        Ok(self.pow(&exponent))
+    }
+
+    fn with_value(&self, value: U256) -> Self {
+        Self {
+            value,
+            finite_field: Arc::clone(&self.finite_field),
+        }
+    }
+}
+
+impl Display for BigFieldElement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+impl Add for &BigFieldElement {
+    type Output = BigFieldElement;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        assert!(
+            Arc::ptr_eq(&self.finite_field, &rhs.finite_field),
+            "Cannot add elements from different finite fields"
+        );
+        self.with_value(self.finite_field.add(&self.value, &rhs.value))
+    }
+}
+
+impl Add for BigFieldElement {
+    type Output = BigFieldElement;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        &self + &rhs
+    }
+}
+
+impl Sub for &BigFieldElement {
+    type Output = BigFieldElement;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        assert!(
+            Arc::ptr_eq(&self.finite_field, &rhs.finite_field),
+            "Cannot sub elements from different finite fields"
+        );
+        self.with_value(self.finite_field.sub(&self.value, &rhs.value))
+    }
+}
+
+impl Sub for BigFieldElement {
+    type Output = BigFieldElement;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        &self - &rhs
+    }
+}
+
+impl Mul for &BigFieldElement {
+    type Output = BigFieldElement;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        assert!(
+            Arc::ptr_eq(&self.finite_field, &rhs.finite_field),
+            "Cannot mul elements from different finite fields"
+        );
+        self.with_value(self.finite_field.mul(&self.value, &rhs.value))
+    }
+}
+
+impl Mul for BigFieldElement {
+    type Output = BigFieldElement;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        &self * &rhs
+    }
+}
+
+impl Div for &BigFieldElement {
+    type Output = BigFieldElement;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        assert!(!rhs.value.is_zero(), "Division by zero is not allowed");
+        self * &rhs.inverse()
+    }
+}
+
+impl Div for BigFieldElement {
+    type Output = BigFieldElement;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        &self / &rhs
+    }
+}
+
+impl Neg for BigFieldElement {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self.with_value(self.finite_field.sub(&U256::ZERO, &self.value))
+    }
+}
+
+impl Field for BigFieldElement {
+    fn zero(&self) -> Self {
+        self.finite_field.zero()
+    }
+
+    fn one(&self) -> Self {
+        self.finite_field.one()
+    }
+
+    fn try_inverse(&self) -> Result<Self, AlgebraError> {
+        BigFieldElement::try_inverse(self)
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self {
+        self.finite_field.sample(rng)
+    }
+
+    fn in_field_of(&self, other: &Self) -> Self {
+        other.with_value(self.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::big_field::{BigPrimeField, STARK_252_PRIME, U256};
+    use crate::error::AlgebraError;
+    use crate::finite_field::FiniteField;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_u256() {
+        assert_eq!(
+            STARK_252_PRIME.to_string(),
+            "3618502788666131213697322783095070105623107215331596699973092056135872020481"
+        );
+        assert_eq!(U256::ZERO.to_string(), "0");
+        assert_eq!(STARK_252_PRIME.bits(), 252);
+        assert_eq!(
+            U256::from_le_bytes(STARK_252_PRIME.to_le_bytes()),
+            STARK_252_PRIME
+        );
+        assert!(U256::from(u128::MAX) < U256([0, 0, 1, 0]));
+    }
+
+    #[test]
+    fn test_matches_finite_field() {
+        // a 107 bit prime, so the products can be checked against the i128 field
+        let prime = (1 << 107) - 1;
+        let finite_field = Arc::new(FiniteField::new(prime, 3).with_barrett_reduction());
+        let big_field = Arc::new(BigPrimeField::new(
+            U256::from(prime as u128),
+            U256::from(3u64),
+        ));
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..100 {
+            let (x, y) = (finite_field.sample(&mut rng), finite_field.sample(&mut rng));
+            let (bx, by) = (
+                big_field.element(U256::from(x.value() as u128)),
+                big_field.element(U256::from(y.value() as u128)),
+            );
+            for (big, small) in [
+                (&bx + &by, &x + &y),
+                (&bx - &by, &x - &y),
+                (&bx * &by, &x * &y),
+            ] {
+                assert_eq!(big.value(), U256::from(small.value() as u128));
+            }
+        }
+    }
+
+    #[test]
+    fn test_stark_252() {
+        let finite_field = Arc::new(BigPrimeField::new(STARK_252_PRIME, U256::from(3u64)));
+        assert_eq!(*finite_field, BigPrimeField::stark_252());
+
+        let minus_one = -finite_field.one();
+        assert_eq!(minus_one.value(), U256([0, 0, 0, 0x0800_0000_0000_0011]));
+        assert_eq!(&minus_one * &minus_one, finite_field.one());
+        // values above the prime are reduced
+        assert!(finite_field.element(U256([u64::MAX; 4])).value() < STARK_252_PRIME);
+
+        let mut rng = StdRng::seed_from_u64(2);
+        let x = finite_field.sample(&mut rng);
+        let y = finite_field.sample(&mut rng);
+        assert_eq!(&x * &x.inverse(), finite_field.one());
+        assert_eq!(&(&x * &y) / &y, x);
+        assert_eq!(&(&x + &y) - &y, x);
+        assert_eq!(x.pow(&STARK_252_PRIME), x);
+        assert_eq!(
+            finite_field.zero().try_inverse(),
+            Err(AlgebraError::DivisionByZero)
+        );
+
+        // the generator is a non-residue, g^((p - 1) / 2) = -1
+        let half = U256([0, 0, 1 << 63, 0x0400_0000_0000_0008]);
+        assert_eq!(finite_field.generator().pow(&half), minus_one);
+    }
+
+    #[test]
+    fn test_composite_modulus() {
+        let composite = U256([1, 0, 0, 0x0800_0000_0000_0013]);
+        assert_eq!(
+            BigPrimeField::try_new(composite, U256::from(3u64)),
+            Err(AlgebraError::CompositeBigModulus(composite))
+        );
+        assert!(BigPrimeField::try_new(U256::from(96u64), U256::ONE).is_err());
+        assert!(BigPrimeField::try_new(U256::from(97u64), U256::from(5u64)).is_ok());
+    }
+}
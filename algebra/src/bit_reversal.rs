@@ -0,0 +1,101 @@
+//! Bit-reversed ordering of power-of-two sized vectors.
+//!
+//! Index `i` of a vector of size `2^k` goes to the index whose `k` bits are those of `i` reversed.
+//! The iterative NTT reads its input in this order, and in bit-reversed order the two points `x`
+//! and `-x` folded together by FRI are neighbours, so they sit under the same Merkle node. The
+//! permutation is its own inverse: the same call moves a vector into and out of bit-reversed order.
+use crate::domain::EvaluationDomain;
+use crate::finite_field::FieldElement;
+
+/// `index` with its `log_size` low bits reversed, `index` must be below `2^log_size`
+pub fn reverse_index(index: usize, log_size: u32) -> usize {
+    assert!(
+        log_size == usize::BITS || index >> log_size == 0,
+        "The index must fit in log_size bits"
+    );
+    if log_size == 0 {
+        return 0;
+    }
+    index.reverse_bits() >> (usize::BITS - log_size)
+}
+
+/// Swaps every element with the one at its reversed index, in place.
+pub fn bit_reverse_permute<T>(values: &mut [T]) {
+    let n = values.len();
+    if n <= 2 {
+        return;
+    }
+    assert!(n.is_power_of_two(), "The length must be a power of two");
+    let log_size = n.trailing_zeros();
+    for i in 0..n {
+        let j = reverse_index(i, log_size);
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+}
+
+/// a bit-reversed copy, see [`bit_reverse_permute`]
+pub fn bit_reversed<T: Clone>(values: &[T]) -> Vec<T> {
+    let mut values = values.to_vec();
+    bit_reverse_permute(&mut values);
+    values
+}
+
+impl EvaluationDomain {
+    /// g ω^rev(index), the point at `index` of the bit-reversed domain
+    pub fn element_bit_reversed(&self, index: usize) -> FieldElement {
+        self.element(reverse_index(index, self.log_size()))
+    }
+
+    /// g ω^rev(0), g ω^rev(1), ..., the order of bit-reversed evaluations
+    pub fn elements_bit_reversed(&self) -> impl Iterator<Item = FieldElement> + '_ {
+        (0..self.size()).map(|i| self.element_bit_reversed(i))
+    }
+
+    fn log_size(&self) -> u32 {
+        assert!(
+            self.size().is_power_of_two(),
+            "Bit-reversed order needs a power-of-two domain"
+        );
+        self.size().trailing_zeros()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bit_reversal::{bit_reverse_permute, bit_reversed, reverse_index};
+    use crate::domain::EvaluationDomain;
+    use crate::finite_field::FiniteField;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_bit_reverse() {
+        assert_eq!(reverse_index(1, 3), 4);
+        assert_eq!(reverse_index(6, 3), 3);
+        assert_eq!(reverse_index(0, 0), 0);
+        assert_eq!(
+            bit_reversed(&[0, 1, 2, 3, 4, 5, 6, 7]),
+            vec![0, 4, 2, 6, 1, 5, 3, 7]
+        );
+
+        let values: Vec<_> = (0..64).collect();
+        let mut permuted = values.clone();
+        bit_reverse_permute(&mut permuted);
+        bit_reverse_permute(&mut permuted);
+        assert_eq!(permuted, values);
+    }
+
+    #[test]
+    fn test_domain_order() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let domain = EvaluationDomain::coset(&finite_field, 8, finite_field.element(5)).unwrap();
+        let natural: Vec<_> = domain.elements().collect();
+        let reversed: Vec<_> = domain.elements_bit_reversed().collect();
+        assert_eq!(reversed, bit_reversed(&natural));
+        // x and -x are neighbours
+        for pair in reversed.chunks(2) {
+            assert_eq!(pair[1], -&pair[0]);
+        }
+    }
+}
@@ -0,0 +1,284 @@
+//! Experimental circle group domains over Mersenne31, as used by Circle STARKs.
+//!
+//! The points `(x, y)` with `x^2 + y^2 = 1` form a group of order `p + 1 = 2^31`, so M31 gets
+//! power-of-two domains even though `p - 1` is not divisible by a large power of 2.
+//! Polynomials are kept in the circle FFT basis: the coefficient at index `j` multiplies
+//! `y^j0 * x^j1 * π(x)^j2 * π(π(x))^j3 ...` where `j0, j1, ...` are the bits of `j` and `π(x) = 2x^2 - 1`.
+use crate::finite_field::{FieldElement, FieldSize, FiniteField};
+use std::sync::Arc;
+
+/// 2^31 - 1
+pub const M31_PRIME: FieldSize = (1 << 31) - 1;
+pub const M31_GENERATOR: FieldSize = 7;
+/// log2 of the circle group order
+pub const M31_CIRCLE_LOG_ORDER: u32 = 31;
+
+pub fn mersenne31() -> FiniteField {
+    FiniteField::new(M31_PRIME, M31_GENERATOR)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CirclePoint {
+    pub x: FieldElement,
+    pub y: FieldElement,
+}
+
+impl CirclePoint {
+    pub fn identity(finite_field: &Arc<FiniteField>) -> Self {
+        Self {
+            x: finite_field.one(),
+            y: finite_field.zero(),
+        }
+    }
+
+    /// generator of the whole circle group, of order 2^31
+    pub fn generator(finite_field: &Arc<FiniteField>) -> Self {
+        assert_eq!(finite_field.prime, M31_PRIME, "Only M31 is supported");
+        Self {
+            x: finite_field.element(2),
+            y: finite_field.element(1268011823),
+        }
+    }
+
+    /// generator of the subgroup of order 2^log_order
+    pub fn subgroup_generator(finite_field: &Arc<FiniteField>, log_order: u32) -> Self {
+        assert!(log_order <= M31_CIRCLE_LOG_ORDER);
+        Self::generator(finite_field).repeated_double(M31_CIRCLE_LOG_ORDER - log_order)
+    }
+
+    pub fn add(&self, rhs: &Self) -> Self {
+        Self {
+            x: &(&self.x * &rhs.x) - &(&self.y * &rhs.y),
+            y: &(&self.x * &rhs.y) + &(&self.y * &rhs.x),
+        }
+    }
+
+    pub fn double(&self) -> Self {
+        self.add(self)
+    }
+
+    pub fn repeated_double(&self, n: u32) -> Self {
+        let mut point = self.clone();
+        for _ in 0..n {
+            point = point.double();
+        }
+        point
+    }
+
+    /// the inverse of the point, (x, -y)
+    pub fn conjugate(&self) -> Self {
+        Self {
+            x: self.x.clone(),
+            y: (-self.y.clone()).abs(),
+        }
+    }
+
+    pub fn is_on_circle(&self) -> bool {
+        &(&self.x * &self.x) + &(&self.y * &self.y) == self.x.finite_field().one()
+    }
+}
+
+/// `π(x) = 2x^2 - 1`, the x coordinate of the doubled point
+fn pi(x: &FieldElement) -> FieldElement {
+    let square = x * x;
+    &(&square + &square) - &x.finite_field().one()
+}
+
+/// Domain of size 2^log_size made of a half coset `initial + <step>` and its conjugate.
+///
+/// Points are ordered as the half coset followed by the conjugates in the same order, which is
+/// the order taken and returned by `interpolate` and `evaluate`.
+#[derive(Debug, Clone)]
+pub struct CircleDomain {
+    half_coset: Vec<CirclePoint>,
+}
+
+impl CircleDomain {
+    /// canonic domain, `initial` generates the subgroup of order 2 * size so no point has x or y = 0
+    pub fn new(finite_field: &Arc<FiniteField>, log_size: u32) -> Self {
+        assert!((1..M31_CIRCLE_LOG_ORDER).contains(&log_size));
+        let initial = CirclePoint::subgroup_generator(finite_field, log_size + 1);
+        let step = CirclePoint::subgroup_generator(finite_field, log_size - 1);
+
+        let mut half_coset = Vec::with_capacity(1 << (log_size - 1));
+        let mut point = initial;
+        for _ in 0..1 << (log_size - 1) {
+            half_coset.push(point.clone());
+            point = point.add(&step);
+        }
+        Self { half_coset }
+    }
+
+    pub fn size(&self) -> usize {
+        2 * self.half_coset.len()
+    }
+
+    pub fn points(&self) -> Vec<CirclePoint> {
+        self.half_coset
+            .iter()
+            .cloned()
+            .chain(self.half_coset.iter().map(CirclePoint::conjugate))
+            .collect()
+    }
+
+    /// inverse circle FFT, values over `points()` to coefficients
+    pub fn interpolate(&self, values: &[FieldElement]) -> Vec<FieldElement> {
+        assert_eq!(values.len(), self.size());
+        let half = self.half_coset.len();
+        let finite_field = self.half_coset[0].x.finite_field();
+        let two_inv = finite_field.element(2).inverse();
+
+        // f(x, y) = f0(x) + y * f1(x)
+        let mut even = Vec::with_capacity(half);
+        let mut odd = Vec::with_capacity(half);
+        for (i, point) in self.half_coset.iter().enumerate() {
+            let (a, b) = (&values[i], &values[i + half]);
+            even.push(&(a + b) * &two_inv);
+            odd.push(&(a - b) * &(&two_inv * &point.y.inverse()));
+        }
+
+        let xs: Vec<FieldElement> = self.half_coset.iter().map(|p| p.x.clone()).collect();
+        interleave(interpolate_x(&xs, &even), interpolate_x(&xs, &odd))
+    }
+
+    /// circle FFT, coefficients to values over `points()`
+    pub fn evaluate(&self, coefficients: &[FieldElement]) -> Vec<FieldElement> {
+        assert_eq!(coefficients.len(), self.size());
+        let half = self.half_coset.len();
+        let xs: Vec<FieldElement> = self.half_coset.iter().map(|p| p.x.clone()).collect();
+        let (even, odd) = deinterleave(coefficients);
+        let even = evaluate_x(&xs, &even);
+        let odd = evaluate_x(&xs, &odd);
+
+        let mut values = vec![self.half_coset[0].x.finite_field().zero(); 2 * half];
+        for (i, point) in self.half_coset.iter().enumerate() {
+            let y_odd = &point.y * &odd[i];
+            values[i] = &even[i] + &y_odd;
+            values[i + half] = &even[i] - &y_odd;
+        }
+        values
+    }
+}
+
+/// evaluates coefficients in the circle FFT basis at any point of the circle
+pub fn evaluate_at(coefficients: &[FieldElement], point: &CirclePoint) -> FieldElement {
+    let finite_field = point.x.finite_field();
+    // y, x, π(x), π(π(x)), ...
+    let mut factors = vec![point.y.clone()];
+    let mut x = point.x.clone();
+    while 1 << factors.len() < coefficients.len() {
+        factors.push(x.clone());
+        x = pi(&x);
+    }
+
+    let mut result = finite_field.zero();
+    for (index, coefficient) in coefficients.iter().enumerate() {
+        let mut term = coefficient.clone();
+        for (bit, factor) in factors.iter().enumerate() {
+            if index >> bit & 1 == 1 {
+                term = &term * factor;
+            }
+        }
+        result += term;
+    }
+    result
+}
+
+/// `xs` must satisfy `xs[i + m / 2] = -xs[i]`, which holds for the x coordinates of a half coset
+fn interpolate_x(xs: &[FieldElement], values: &[FieldElement]) -> Vec<FieldElement> {
+    if values.len() == 1 {
+        return values.to_vec();
+    }
+    let half = values.len() / 2;
+    let two_inv = xs[0].finite_field().element(2).inverse();
+
+    // g(x) = g0(π(x)) + x * g1(π(x))
+    let mut even = Vec::with_capacity(half);
+    let mut odd = Vec::with_capacity(half);
+    for (i, x) in xs[..half].iter().enumerate() {
+        let (a, b) = (&values[i], &values[i + half]);
+        even.push(&(a + b) * &two_inv);
+        odd.push(&(a - b) * &(&two_inv * &x.inverse()));
+    }
+
+    let next_xs: Vec<FieldElement> = xs[..half].iter().map(pi).collect();
+    interleave(
+        interpolate_x(&next_xs, &even),
+        interpolate_x(&next_xs, &odd),
+    )
+}
+
+fn evaluate_x(xs: &[FieldElement], coefficients: &[FieldElement]) -> Vec<FieldElement> {
+    if coefficients.len() == 1 {
+        return coefficients.to_vec();
+    }
+    let half = coefficients.len() / 2;
+    let next_xs: Vec<FieldElement> = xs[..half].iter().map(pi).collect();
+    let (even, odd) = deinterleave(coefficients);
+    let even = evaluate_x(&next_xs, &even);
+    let odd = evaluate_x(&next_xs, &odd);
+
+    let mut values = vec![xs[0].finite_field().zero(); 2 * half];
+    for (i, x) in xs[..half].iter().enumerate() {
+        let x_odd = x * &odd[i];
+        values[i] = &even[i] + &x_odd;
+        values[i + half] = &even[i] - &x_odd;
+    }
+    values
+}
+
+fn interleave(even: Vec<FieldElement>, odd: Vec<FieldElement>) -> Vec<FieldElement> {
+    even.into_iter()
+        .zip(odd)
+        .flat_map(|(e, o)| [e, o])
+        .collect()
+}
+
+fn deinterleave(coefficients: &[FieldElement]) -> (Vec<FieldElement>, Vec<FieldElement>) {
+    let even = coefficients.iter().step_by(2).cloned().collect();
+    let odd = coefficients.iter().skip(1).step_by(2).cloned().collect();
+    (even, odd)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::circle::{evaluate_at, mersenne31, CircleDomain, CirclePoint, M31_CIRCLE_LOG_ORDER};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_circle_group() {
+        let finite_field = Arc::new(mersenne31());
+        let generator = CirclePoint::generator(&finite_field);
+        let identity = CirclePoint::identity(&finite_field);
+
+        assert!(generator.is_on_circle());
+        assert_ne!(
+            generator.repeated_double(M31_CIRCLE_LOG_ORDER - 1),
+            identity
+        );
+        assert_eq!(generator.repeated_double(M31_CIRCLE_LOG_ORDER), identity);
+        assert_eq!(generator.add(&generator.conjugate()), identity);
+
+        let g4 = CirclePoint::subgroup_generator(&finite_field, 2);
+        assert_eq!(g4.double().double(), identity);
+        assert_ne!(g4.double(), identity);
+    }
+
+    #[test]
+    fn test_circle_fft() {
+        let finite_field = Arc::new(mersenne31());
+        for log_size in 1..=5 {
+            let domain = CircleDomain::new(&finite_field, log_size);
+            let coefficients: Vec<_> = finite_field
+                .elements((0..1 << log_size).map(|i| 7 * i * i + 3))
+                .collect();
+
+            let values = domain.evaluate(&coefficients);
+            for (point, value) in domain.points().iter().zip(&values) {
+                assert!(point.is_on_circle());
+                assert_eq!(evaluate_at(&coefficients, point), *value);
+            }
+            assert_eq!(domain.interpolate(&values), coefficients);
+        }
+    }
+}
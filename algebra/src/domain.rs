@@ -0,0 +1,189 @@
+//! Cosets `gH` of a multiplicative subgroup `H`, the evaluation domains of the trace, the composition
+//! polynomial and the FRI layers.
+//!
+//! A coset with an offset outside `H` is disjoint from `H`, so a low-degree extension over it never
+//! evaluates at the trace points, where the constraint quotients are undefined.
+use crate::error::AlgebraError;
+use crate::finite_field::{FieldElement, FiniteField};
+use crate::polynomial::Polynomial;
+use crate::sparse::SparsePolynomial;
+use crate::subgroup::Subgroup;
+use std::sync::Arc;
+
+/// `{g, g ω, g ω^2, ..., g ω^(n - 1)}` for the offset `g` and a primitive `n`-th root of unity `ω`
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvaluationDomain {
+    subgroup: Subgroup,
+    offset: FieldElement,
+}
+
+impl EvaluationDomain {
+    /// the subgroup of order `size` itself, with offset 1
+    pub fn new(finite_field: &Arc<FiniteField>, size: usize) -> Result<Self, AlgebraError> {
+        Self::coset(finite_field, size, finite_field.one())
+    }
+
+    /// `size` must divide `p - 1` and the offset must be non-zero
+    pub fn coset(
+        finite_field: &Arc<FiniteField>,
+        size: usize,
+        offset: FieldElement,
+    ) -> Result<Self, AlgebraError> {
+        Self::from_subgroup(Subgroup::new(finite_field, size)?, offset)
+    }
+
+    pub fn from_subgroup(subgroup: Subgroup, offset: FieldElement) -> Result<Self, AlgebraError> {
+        if offset == offset.finite_field().zero() {
+            return Err(AlgebraError::ZeroOffset);
+        }
+        Ok(Self {
+            subgroup,
+            offset: offset.abs(),
+        })
+    }
+
+    pub fn size(&self) -> usize {
+        self.subgroup.order()
+    }
+
+    /// g
+    pub fn offset(&self) -> &FieldElement {
+        &self.offset
+    }
+
+    /// H
+    pub fn subgroup(&self) -> &Subgroup {
+        &self.subgroup
+    }
+
+    /// ω
+    pub fn generator(&self) -> &FieldElement {
+        self.subgroup.generator()
+    }
+
+    pub fn finite_field(&self) -> &Arc<FiniteField> {
+        self.subgroup.finite_field()
+    }
+
+    /// the same subgroup shifted by another offset
+    pub fn with_offset(&self, offset: FieldElement) -> Result<Self, AlgebraError> {
+        Self::from_subgroup(self.subgroup.clone(), offset)
+    }
+
+    /// g ω^index, the index is taken modulo the size
+    pub fn element(&self, index: usize) -> FieldElement {
+        &self.offset * &self.subgroup.element(index)
+    }
+
+    /// g, g ω, ..., g ω^(n - 1)
+    pub fn elements(&self) -> impl Iterator<Item = FieldElement> + '_ {
+        self.subgroup.elements().map(|x| &self.offset * &x)
+    }
+
+    /// `(x / g)^n = 1`
+    pub fn contains(&self, x: &FieldElement) -> bool {
+        self.subgroup.contains(&(x / &self.offset))
+    }
+
+    /// the domain of the squares, of half the size with offset g^2, for an even size
+    pub fn square(&self) -> Option<Self> {
+        Some(Self {
+            subgroup: self.subgroup.square()?,
+            offset: &self.offset * &self.offset,
+        })
+    }
+
+    /// The coset of `blowup` times the size, shifted by the generator of the multiplicative group.
+    /// The generator lies outside every proper subgroup, so the extended domain is disjoint from a
+    /// trace domain that is a subgroup.
+    pub fn lde_domain(&self, blowup: usize) -> Result<Self, AlgebraError> {
+        let finite_field = self.finite_field();
+        let size = self.size().checked_mul(blowup).unwrap_or(0);
+        Self::coset(
+            finite_field,
+            size,
+            finite_field.element(finite_field.generator),
+        )
+    }
+
+    /// `x^n - g^n`, which vanishes exactly on the domain
+    pub fn zerofier(&self) -> Polynomial {
+        Polynomial::zerofier_coset(self.size(), &self.offset)
+    }
+
+    /// the zerofier with its two terms only
+    pub fn sparse_zerofier(&self) -> SparsePolynomial {
+        let finite_field = self.finite_field();
+        SparsePolynomial::new(
+            [
+                (self.size(), finite_field.one()),
+                (0, -self.offset.pow(self.size() as u128)),
+            ],
+            Arc::clone(finite_field),
+        )
+    }
+
+    /// the zerofier at `x` with a single exponentiation
+    pub fn evaluate_zerofier(&self, x: &FieldElement) -> FieldElement {
+        let n = self.size() as u128;
+        &x.pow(n) - &self.offset.pow(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::EvaluationDomain;
+    use crate::error::AlgebraError;
+    use crate::finite_field::FiniteField;
+    use crate::poly;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_coset() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let subgroup = EvaluationDomain::new(&finite_field, 8).unwrap();
+        let coset = subgroup.with_offset(finite_field.element(5)).unwrap();
+        assert_eq!(coset.size(), 8);
+
+        let elements: Vec<_> = coset.elements().collect();
+        for (i, x) in elements.iter().enumerate() {
+            assert_eq!(*x, coset.element(i));
+            assert_eq!(*x, &finite_field.element(5) * &subgroup.element(i));
+            assert!(coset.contains(x));
+            // 5 generates the whole group, so it's outside the subgroup and the coset is disjoint
+            assert!(!subgroup.contains(x));
+            assert_eq!(coset.evaluate_zerofier(x), finite_field.zero());
+            assert_eq!(coset.zerofier().evaluate(x.clone()), finite_field.zero());
+        }
+        let outside = finite_field.element(3);
+        assert_eq!(coset.sparse_zerofier().to_dense(), coset.zerofier());
+        assert_eq!(
+            coset.zerofier().evaluate(outside.clone()),
+            coset.evaluate_zerofier(&outside)
+        );
+
+        let squares = coset.square().unwrap();
+        assert_eq!(squares.size(), 4);
+        assert!(elements.iter().all(|x| squares.contains(&(x * x))));
+
+        assert_eq!(
+            subgroup.with_offset(finite_field.zero()),
+            Err(AlgebraError::ZeroOffset)
+        );
+        assert_eq!(
+            EvaluationDomain::new(&finite_field, 5),
+            Err(AlgebraError::InvalidDomainSize(5))
+        );
+    }
+
+    #[test]
+    fn test_evaluate_on() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let coset = EvaluationDomain::coset(&finite_field, 4, finite_field.element(5)).unwrap();
+        let f = poly!(finite_field, [1, 2, 3]);
+        let evaluations = f.evaluate_on(&coset);
+        for (x, y) in coset.elements().zip(evaluations) {
+            assert_eq!(f.evaluate(x), y);
+        }
+    }
+}
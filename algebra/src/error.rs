@@ -0,0 +1,74 @@
+use crate::big_field::U256;
+use crate::finite_field::FieldSize;
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum AlgebraError {
+    #[error("elements belong to different finite fields")]
+    DifferentFields,
+    #[error("division by zero is not allowed")]
+    DivisionByZero,
+    #[error("invalid generator {0}")]
+    InvalidGenerator(FieldSize),
+    #[error("the modulus {0} is not prime")]
+    CompositeModulus(FieldSize),
+    #[error("the modulus {0} is too large, primes must be below 2^126")]
+    ModulusTooLarge(FieldSize),
+    #[error("the modulus {0} is not prime")]
+    CompositeBigModulus(U256),
+    #[error("the modulus {0} does not match the modulus of the target field")]
+    ModulusMismatch(FieldSize),
+    #[error("{0} is not a canonical field element")]
+    NotCanonical(FieldSize),
+    #[error("the field has no subgroup of size {0}")]
+    InvalidDomainSize(usize),
+    #[error("the coset offset must be non-zero")]
+    ZeroOffset,
+    #[error("the point {0} appears more than once")]
+    DuplicatePoint(FieldSize),
+    #[error("{0} is a quadratic residue")]
+    QuadraticResidue(FieldSize),
+    #[error("expected {expected} bytes, found {found}")]
+    InvalidByteLength { expected: usize, found: usize },
+    #[error("expected {expected} evaluations, found {found}")]
+    EvaluationCountMismatch { expected: usize, found: usize },
+    #[error("the table size {0} is not a power of two")]
+    NotPowerOfTwo(usize),
+    #[error("expected {expected} variables, found {found}")]
+    VariableCountMismatch { expected: usize, found: usize },
+    #[error("the modulus is not a monic irreducible polynomial of degree at least 2")]
+    InvalidModulus,
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum PolynomialError {
+    #[error("division by the zero polynomial")]
+    DivisionByZero,
+    #[error("the polynomials are defined over different finite fields")]
+    DifferentFields,
+    #[error("the division leaves a remainder of degree {0}")]
+    NonZeroRemainder(usize),
+    #[error("cannot parse {0:?} as a polynomial in x")]
+    InvalidTerm(String),
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum MatrixError {
+    #[error("row {row} has {found} entries, expected {expected}")]
+    RaggedRows {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    #[error("cannot multiply a {left:?} matrix by a {right:?} one")]
+    DimensionMismatch {
+        left: (usize, usize),
+        right: (usize, usize),
+    },
+    #[error("the matrix is {rows}x{cols}, not square")]
+    NotSquare { rows: usize, cols: usize },
+    #[error("the matrix is singular")]
+    Singular,
+    #[error("the linear system has no solution")]
+    Inconsistent,
+}
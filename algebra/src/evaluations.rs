@@ -0,0 +1,255 @@
+//! Polynomials in evaluation form over an [`EvaluationDomain`].
+//!
+//! Sums, products and quotients of polynomials are pointwise on the values, as long as the domain
+//! is larger than the degree of the result. The coefficients are only interpolated, once, when asked for.
+use crate::domain::EvaluationDomain;
+use crate::error::AlgebraError;
+use crate::finite_field::FieldElement;
+use crate::polynomial::Polynomial;
+use crate::slice::{add_slices, mul_slices, scale_slice, sub_slices};
+use std::ops::{Add, Div, Mul, Sub};
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone)]
+pub struct PolynomialEvaluations {
+    domain: EvaluationDomain,
+    values: Vec<FieldElement>,
+    coefficients: OnceLock<Polynomial>,
+}
+
+impl PolynomialEvaluations {
+    /// one value per point of the domain, in the order of [`EvaluationDomain::elements`]
+    pub fn new(domain: EvaluationDomain, values: Vec<FieldElement>) -> Result<Self, AlgebraError> {
+        if values.len() != domain.size() {
+            return Err(AlgebraError::EvaluationCountMismatch {
+                expected: domain.size(),
+                found: values.len(),
+            });
+        }
+        Ok(Self {
+            domain,
+            values,
+            coefficients: OnceLock::new(),
+        })
+    }
+
+    pub fn from_polynomial(polynomial: &Polynomial, domain: EvaluationDomain) -> Self {
+        Self {
+            values: polynomial.evaluate_on(&domain),
+            domain,
+            coefficients: OnceLock::new(),
+        }
+    }
+
+    pub fn domain(&self) -> &EvaluationDomain {
+        &self.domain
+    }
+
+    pub fn values(&self) -> &[FieldElement] {
+        &self.values
+    }
+
+    pub fn into_values(self) -> Vec<FieldElement> {
+        self.values
+    }
+
+    /// the interpolant of degree below the domain size, computed on the first call
+    pub fn to_polynomial(&self) -> &Polynomial {
+        self.coefficients.get_or_init(|| {
+            Polynomial::interpolate(&self.domain, &self.values)
+                .expect("there is one value per point")
+        })
+    }
+
+    /// every value multiplied by `c`
+    pub fn scale(&self, c: &FieldElement) -> Self {
+        let mut values = self.values.clone();
+        scale_slice(&mut values, c);
+        self.with_values(values)
+    }
+
+    /// Pointwise quotient with a single batch inversion, fails if `rhs` vanishes anywhere on the domain
+    pub fn checked_div(&self, rhs: &Self) -> Result<Self, AlgebraError> {
+        self.assert_same_domain(rhs);
+        let inverses = FieldElement::try_batch_inverse(&rhs.values)?;
+        let mut values = self.values.clone();
+        mul_slices(&mut values, &inverses);
+        Ok(self.with_values(values))
+    }
+
+    /// The constraint quotient `self / Z` for the zerofier `Z = x^n - g^n` of `vanishing`, like the
+    /// trace domain, without going through coefficients. `Z` is evaluated at every point, the values
+    /// are inverted in a batch and multiplied in. Fails when the two domains intersect, where `Z`
+    /// vanishes.
+    ///
+    /// On the domain `h ω^i` of size `N`, `Z(h ω^i) = h^n (ω^n)^i - g^n` only takes `N / n` distinct
+    /// values when `n` divides `N`, so only those are computed and inverted.
+    pub fn divide_by_zerofier(&self, vanishing: &EvaluationDomain) -> Result<Self, AlgebraError> {
+        let n = vanishing.size();
+        let size = self.domain.size();
+        let period = if size.is_multiple_of(n) {
+            size / n
+        } else {
+            size
+        };
+        let g_n = vanishing.offset().pow(n as u128);
+        let step = self.domain.generator().pow(n as u128);
+        let mut power = self.domain.offset().pow(n as u128);
+        let mut zerofier = Vec::with_capacity(period);
+        for _ in 0..period {
+            zerofier.push(&power - &g_n);
+            power = &power * &step;
+        }
+        let inverses = FieldElement::try_batch_inverse(&zerofier)?;
+        let values = self
+            .values
+            .iter()
+            .zip(inverses.iter().cycle())
+            .map(|(value, inverse)| value * inverse)
+            .collect();
+        Ok(self.with_values(values))
+    }
+
+    /// `self / z` pointwise for any zerofier `z`, like one of boundary points, with a single batch
+    /// inversion of its evaluations
+    pub fn divide_by(&self, zerofier: &Polynomial) -> Result<Self, AlgebraError> {
+        self.checked_div(&Self::from_polynomial(zerofier, self.domain.clone()))
+    }
+
+    fn with_values(&self, values: Vec<FieldElement>) -> Self {
+        Self {
+            domain: self.domain.clone(),
+            values,
+            coefficients: OnceLock::new(),
+        }
+    }
+
+    fn assert_same_domain(&self, rhs: &Self) {
+        assert_eq!(
+            self.domain, rhs.domain,
+            "The evaluations must be over the same domain"
+        );
+    }
+}
+
+impl PartialEq for PolynomialEvaluations {
+    fn eq(&self, other: &Self) -> bool {
+        self.domain == other.domain && self.values == other.values
+    }
+}
+
+impl Add for &PolynomialEvaluations {
+    type Output = PolynomialEvaluations;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.assert_same_domain(rhs);
+        let mut values = self.values.clone();
+        add_slices(&mut values, &rhs.values);
+        self.with_values(values)
+    }
+}
+
+impl Sub for &PolynomialEvaluations {
+    type Output = PolynomialEvaluations;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.assert_same_domain(rhs);
+        let mut values = self.values.clone();
+        sub_slices(&mut values, &rhs.values);
+        self.with_values(values)
+    }
+}
+
+impl Mul for &PolynomialEvaluations {
+    type Output = PolynomialEvaluations;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.assert_same_domain(rhs);
+        let mut values = self.values.clone();
+        mul_slices(&mut values, &rhs.values);
+        self.with_values(values)
+    }
+}
+
+impl Div for &PolynomialEvaluations {
+    type Output = PolynomialEvaluations;
+
+    /// panics if `rhs` has a zero value, see [`PolynomialEvaluations::checked_div`]
+    fn div(self, rhs: Self) -> Self::Output {
+        self.checked_div(rhs).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::EvaluationDomain;
+    use crate::error::AlgebraError;
+    use crate::evaluations::PolynomialEvaluations;
+    use crate::finite_field::FiniteField;
+    use crate::poly;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_pointwise_arithmetic() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let domain = EvaluationDomain::coset(&finite_field, 8, finite_field.element(5)).unwrap();
+        let f = poly!(finite_field, [1, 2, 3]);
+        let g = poly!(finite_field, [4, 0, 0, 1]);
+        let f_values = PolynomialEvaluations::from_polynomial(&f, domain.clone());
+        let g_values = PolynomialEvaluations::from_polynomial(&g, domain.clone());
+
+        assert_eq!((&f_values + &g_values).to_polynomial(), &(&f + &g));
+        assert_eq!((&f_values - &g_values).to_polynomial(), &(&f - &g));
+        assert_eq!((&f_values * &g_values).to_polynomial(), &(&f * &g));
+        assert_eq!(
+            f_values.scale(&finite_field.element(3)).to_polynomial(),
+            &f.clone().scalar_mul(finite_field.element(3))
+        );
+
+        // (f g) / g = f, the coset avoids the roots of g
+        let quotient = (&f_values * &g_values).checked_div(&g_values).unwrap();
+        assert_eq!(quotient, f_values);
+        assert_eq!(quotient.to_polynomial(), &f);
+    }
+
+    #[test]
+    fn test_errors() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let domain = EvaluationDomain::new(&finite_field, 4).unwrap();
+        assert_eq!(
+            PolynomialEvaluations::new(domain.clone(), vec![finite_field.one(); 3]),
+            Err(AlgebraError::EvaluationCountMismatch {
+                expected: 4,
+                found: 3
+            })
+        );
+
+        // x - 1 vanishes at the first point of the subgroup
+        let f = PolynomialEvaluations::from_polynomial(&poly!(finite_field, [1]), domain.clone());
+        let g = PolynomialEvaluations::from_polynomial(&poly!(finite_field, [-1, 1]), domain);
+        assert!(f.checked_div(&g).is_err());
+    }
+
+    #[test]
+    fn test_divide_by_zerofier() {
+        // 3 * 2^30 + 1
+        let finite_field = Arc::new(FiniteField::new(3221225473, 5));
+        let trace_domain = EvaluationDomain::new(&finite_field, 8).unwrap();
+        let lde_domain = trace_domain.lde_domain(4).unwrap();
+        let zerofier = trace_domain.zerofier();
+        let quotient = poly!(finite_field, [3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5]);
+        let constraint =
+            PolynomialEvaluations::from_polynomial(&(&quotient * &zerofier), lde_domain);
+
+        let divided = constraint.divide_by_zerofier(&trace_domain).unwrap();
+        assert_eq!(divided.to_polynomial(), &quotient);
+        assert_eq!(constraint.divide_by(&zerofier), Ok(divided));
+
+        // the trace domain itself is where the zerofier vanishes
+        let on_trace = PolynomialEvaluations::from_polynomial(&quotient, trace_domain.clone());
+        assert_eq!(
+            on_trace.divide_by_zerofier(&trace_domain),
+            Err(AlgebraError::DivisionByZero)
+        );
+    }
+}
@@ -0,0 +1,352 @@
+//! Quadratic extension `Fp² = Fp[u] / (u^2 - β)` for a quadratic non-residue `β`.
+//!
+//! Challenges drawn from `Fp²` make the soundness error of a random evaluation `deg / p^2` instead of
+//! `deg / p`, which matters for small base fields.
+use crate::error::AlgebraError;
+use crate::field::Field;
+use crate::finite_field::{FieldElement, FieldSize, FiniteField};
+use crate::polynomial::Polynomial;
+use rand::Rng;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Mul, Neg, Sub};
+use std::sync::Arc;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuadraticExtension {
+    base: Arc<FiniteField>,
+    /// β, with `u^2 = β`
+    non_residue: FieldElement,
+}
+
+impl QuadraticExtension {
+    pub fn new(base: &Arc<FiniteField>, non_residue: FieldSize) -> Self {
+        Self::try_new(base, non_residue).expect("Invalid non-residue")
+    }
+
+    /// checks with Euler's criterion that `β^((p - 1) / 2) = -1`, so `u^2 - β` is irreducible
+    pub fn try_new(base: &Arc<FiniteField>, non_residue: FieldSize) -> Result<Self, AlgebraError> {
+        let non_residue = base.element(non_residue).abs();
+        let minus_one = (-base.one()).abs();
+        if non_residue.pow(((base.prime - 1) / 2) as u128) != minus_one {
+            return Err(AlgebraError::QuadraticResidue(non_residue.value()));
+        }
+        Ok(Self {
+            base: Arc::clone(base),
+            non_residue,
+        })
+    }
+
+    pub fn base(&self) -> &Arc<FiniteField> {
+        &self.base
+    }
+
+    pub fn non_residue(&self) -> &FieldElement {
+        &self.non_residue
+    }
+
+    /// `c0 + c1 * u`
+    pub fn element(self: &Arc<Self>, c0: FieldSize, c1: FieldSize) -> ExtensionFieldElement {
+        ExtensionFieldElement {
+            c0: self.base.element(c0).abs(),
+            c1: self.base.element(c1).abs(),
+            extension: Arc::clone(self),
+        }
+    }
+
+    /// embeds a base field element as `x + 0 * u`
+    pub fn from_base(self: &Arc<Self>, x: &FieldElement) -> ExtensionFieldElement {
+        assert!(
+            Arc::ptr_eq(x.finite_field(), &self.base),
+            "The element is not in the base field"
+        );
+        ExtensionFieldElement {
+            c0: x.abs(),
+            c1: self.base.zero(),
+            extension: Arc::clone(self),
+        }
+    }
+
+    pub fn zero(self: &Arc<Self>) -> ExtensionFieldElement {
+        self.element(0, 0)
+    }
+
+    pub fn one(self: &Arc<Self>) -> ExtensionFieldElement {
+        self.element(1, 0)
+    }
+
+    pub fn random_element(self: &Arc<Self>) -> ExtensionFieldElement {
+        self.sample(&mut rand::thread_rng())
+    }
+
+    /// uniform element drawn from `rng`, both coordinates from the base field
+    pub fn sample<R: Rng + ?Sized>(self: &Arc<Self>, rng: &mut R) -> ExtensionFieldElement {
+        self.element(self.base.sample(rng).value(), self.base.sample(rng).value())
+    }
+}
+
+/// `c0 + c1 * u` with canonical coefficients
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtensionFieldElement {
+    c0: FieldElement,
+    c1: FieldElement,
+    extension: Arc<QuadraticExtension>,
+}
+
+impl ExtensionFieldElement {
+    pub fn coefficients(&self) -> (&FieldElement, &FieldElement) {
+        (&self.c0, &self.c1)
+    }
+
+    pub fn extension(&self) -> &Arc<QuadraticExtension> {
+        &self.extension
+    }
+
+    /// the element is in the base field when `c1 = 0`
+    pub fn to_base(&self) -> Option<FieldElement> {
+        (self.c1.value() == 0).then(|| self.c0.clone())
+    }
+
+    pub fn mul_base(&self, rhs: &FieldElement) -> Self {
+        Self {
+            c0: &self.c0 * rhs,
+            c1: &self.c1 * rhs,
+            extension: Arc::clone(&self.extension),
+        }
+    }
+
+    /// `c0 - c1 * u`
+    pub fn conjugate(&self) -> Self {
+        Self {
+            c0: self.c0.clone(),
+            c1: (-self.c1.clone()).abs(),
+            extension: Arc::clone(&self.extension),
+        }
+    }
+
+    /// `c0^2 - β * c1^2`, the product with the conjugate
+    pub fn norm(&self) -> FieldElement {
+        &(&self.c0 * &self.c0) - &(&self.extension.non_residue * &(&self.c1 * &self.c1))
+    }
+
+    pub fn inverse(&self) -> Self {
+        self.try_inverse()
+            .expect("Cannot compute the inverse of zero")
+    }
+
+    /// the conjugate divided by the norm, which is non-zero because β is a non-residue
+    pub fn try_inverse(&self) -> Result<Self, AlgebraError> {
+        let norm_inv = self.norm().try_inverse()?;
+        Ok(self.conjugate().mul_base(&norm_inv))
+    }
+
+    pub fn pow(&self, mut exponent: u128) -> Self {
+        let mut result = self.extension.one();
+        let mut square = self.clone();
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = &result * &square;
+            }
+            square = &square * &square;
+            exponent >>= 1;
+        }
+        result
+    }
+}
+
+impl Display for ExtensionFieldElement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} + {}u", self.c0, self.c1)
+    }
+}
+
+impl From<(FieldElement, &Arc<QuadraticExtension>)> for ExtensionFieldElement {
+    fn from((x, extension): (FieldElement, &Arc<QuadraticExtension>)) -> Self {
+        extension.from_base(&x)
+    }
+}
+
+impl Add for &ExtensionFieldElement {
+    type Output = ExtensionFieldElement;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        assert!(
+            Arc::ptr_eq(&self.extension, &rhs.extension),
+            "Cannot add elements from different extension fields"
+        );
+        ExtensionFieldElement {
+            c0: &self.c0 + &rhs.c0,
+            c1: &self.c1 + &rhs.c1,
+            extension: Arc::clone(&self.extension),
+        }
+    }
+}
+
+impl Add for ExtensionFieldElement {
+    type Output = ExtensionFieldElement;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        &self + &rhs
+    }
+}
+
+impl Sub for &ExtensionFieldElement {
+    type Output = ExtensionFieldElement;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        assert!(
+            Arc::ptr_eq(&self.extension, &rhs.extension),
+            "Cannot sub elements from different extension fields"
+        );
+        ExtensionFieldElement {
+            c0: &self.c0 - &rhs.c0,
+            c1: &self.c1 - &rhs.c1,
+            extension: Arc::clone(&self.extension),
+        }
+    }
+}
+
+impl Sub for ExtensionFieldElement {
+    type Output = ExtensionFieldElement;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        &self - &rhs
+    }
+}
+
+impl Mul for &ExtensionFieldElement {
+    type Output = ExtensionFieldElement;
+
+    /// `(a0 + a1 u)(b0 + b1 u) = a0 b0 + β a1 b1 + (a0 b1 + a1 b0) u`
+    fn mul(self, rhs: Self) -> Self::Output {
+        assert!(
+            Arc::ptr_eq(&self.extension, &rhs.extension),
+            "Cannot mul elements from different extension fields"
+        );
+        let a1_b1 = &self.c1 * &rhs.c1;
+        ExtensionFieldElement {
+            c0: &(&self.c0 * &rhs.c0) + &(&self.extension.non_residue * &a1_b1),
+            c1: &(&self.c0 * &rhs.c1) + &(&self.c1 * &rhs.c0),
+            extension: Arc::clone(&self.extension),
+        }
+    }
+}
+
+impl Mul for ExtensionFieldElement {
+    type Output = ExtensionFieldElement;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        &self * &rhs
+    }
+}
+
+impl Neg for ExtensionFieldElement {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            c0: (-self.c0).abs(),
+            c1: (-self.c1).abs(),
+            extension: self.extension,
+        }
+    }
+}
+
+impl Field for ExtensionFieldElement {
+    fn zero(&self) -> Self {
+        self.extension.zero()
+    }
+
+    fn one(&self) -> Self {
+        self.extension.one()
+    }
+
+    fn try_inverse(&self) -> Result<Self, AlgebraError> {
+        ExtensionFieldElement::try_inverse(self)
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self {
+        self.extension.sample(rng)
+    }
+
+    fn in_field_of(&self, other: &Self) -> Self {
+        Self {
+            c0: self.c0.in_field_of(&other.c0),
+            c1: self.c1.in_field_of(&other.c1),
+            extension: Arc::clone(&other.extension),
+        }
+    }
+}
+
+impl Polynomial {
+    /// Horner evaluation of a base field polynomial at an extension field point
+    pub fn evaluate_extension(&self, x: &ExtensionFieldElement) -> ExtensionFieldElement {
+        let extension = x.extension();
+        let mut result = extension.zero();
+        for coefficient in self.coefficients.iter().rev() {
+            result = &(&result * x) + &extension.from_base(coefficient);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::AlgebraError;
+    use crate::extension::QuadraticExtension;
+    use crate::finite_field::FiniteField;
+    use crate::poly;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_quadratic_extension() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let extension = Arc::new(QuadraticExtension::new(&finite_field, 5));
+        let u = extension.element(0, 1);
+        assert_eq!(&u * &u, extension.element(5, 0));
+
+        let x = extension.element(13, 42);
+        let y = extension.element(77, 3);
+        assert_eq!(&x * &x.inverse(), extension.one());
+        assert_eq!(&(&x * &y) * &y.inverse(), x);
+        assert_eq!(&(&x + &y) - &y, x);
+        assert_eq!(&x + &-x.clone(), extension.zero());
+        // the Frobenius map x -> x^p is the conjugation
+        assert_eq!(x.pow(97), x.conjugate());
+        assert_eq!(x.pow(97 * 97 - 1), extension.one());
+
+        let base = finite_field.element(11);
+        let lifted = extension.from_base(&base);
+        assert_eq!(
+            (&lifted * &lifted).to_base(),
+            Some(finite_field.element(121 % 97))
+        );
+        assert_eq!(x.to_base(), None);
+        assert_eq!(
+            extension.zero().try_inverse(),
+            Err(AlgebraError::DivisionByZero)
+        );
+
+        assert_eq!(
+            QuadraticExtension::try_new(&finite_field, 4),
+            Err(AlgebraError::QuadraticResidue(4))
+        );
+    }
+
+    #[test]
+    fn test_evaluate_extension() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let extension = Arc::new(QuadraticExtension::new(&finite_field, 5));
+        let f = poly!(finite_field, [1, 2, 3]);
+
+        let x = finite_field.element(20);
+        assert_eq!(
+            f.evaluate_extension(&extension.from_base(&x)).to_base(),
+            Some(f.evaluate(x))
+        );
+        // 1 + 2u + 3u^2 = 16 + 2u
+        assert_eq!(
+            f.evaluate_extension(&extension.element(0, 1)),
+            extension.element(16, 2)
+        );
+    }
+}
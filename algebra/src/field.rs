@@ -0,0 +1,155 @@
+use crate::error::AlgebraError;
+use crate::finite_field::FieldElement;
+use crate::ntt::{mul_coefficients_fft, FFT_MUL_THRESHOLD};
+use crate::polynomial::schoolbook;
+use rand::Rng;
+use std::fmt::{Debug, Display};
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// Arithmetic shared by every field element type, so that code can be written once for all fields.
+///
+/// Constants are obtained from an existing element (`x.zero()`, `x.one()`) because some element types,
+/// like [`FieldElement`], carry a handle to the field they belong to.
+pub trait Field:
+    Sized
+    + Clone
+    + PartialEq
+    + Debug
+    + Display
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+{
+    fn zero(&self) -> Self;
+
+    fn one(&self) -> Self;
+
+    fn is_zero(&self) -> bool {
+        *self == self.zero()
+    }
+
+    fn try_inverse(&self) -> Result<Self, AlgebraError>;
+
+    /// uniformly sampled element of the same field
+    fn random(&self) -> Self {
+        self.sample(&mut rand::thread_rng())
+    }
+
+    /// uniform element of the same field drawn from `rng`, for reproducible or caller-seeded draws
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self;
+
+    fn square(&self) -> Self {
+        self.clone() * self.clone()
+    }
+
+    /// The same value with the field handle of `other`, so that it can be added to `other` even when it
+    /// was made by another handle to an equal field. Element types without a handle return it as is.
+    fn in_field_of(&self, _other: &Self) -> Self {
+        self.clone()
+    }
+
+    /// The coefficients of the product of two non-empty polynomials, schoolbook by default.
+    /// Element types with large power-of-two subgroups can override it with NTT multiplication.
+    fn mul_polynomials(lhs: &[Self], rhs: &[Self]) -> Vec<Self> {
+        schoolbook(lhs, rhs)
+    }
+}
+
+impl Field for FieldElement {
+    fn zero(&self) -> Self {
+        self.finite_field().zero()
+    }
+
+    fn one(&self) -> Self {
+        self.finite_field().one()
+    }
+
+    fn try_inverse(&self) -> Result<Self, AlgebraError> {
+        FieldElement::try_inverse(self)
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self {
+        self.finite_field().sample(rng)
+    }
+
+    fn in_field_of(&self, other: &Self) -> Self {
+        other.finite_field().element(self.value())
+    }
+
+    /// NTT multiplication once both operands have more than [`FFT_MUL_THRESHOLD`] coefficients and
+    /// the field has a large enough power-of-two subgroup
+    fn mul_polynomials(lhs: &[Self], rhs: &[Self]) -> Vec<Self> {
+        if lhs.len() > FFT_MUL_THRESHOLD && rhs.len() > FFT_MUL_THRESHOLD {
+            if let Ok(product) = mul_coefficients_fft(lhs, rhs) {
+                return product;
+            }
+        }
+        schoolbook(lhs, rhs)
+    }
+}
+
+/// Inverts every element with a single inversion and `3(n - 1)` multiplications (Montgomery's trick).
+///
+/// The prefix products `a_0 * ... * a_i` are inverted as a whole, then unwound from the end.
+/// Fails with [`AlgebraError::DivisionByZero`] if any element is zero.
+pub fn batch_inverse<F: Field>(elements: &[F]) -> Result<Vec<F>, AlgebraError> {
+    let Some(first) = elements.first() else {
+        return Ok(vec![]);
+    };
+    let mut prefix = Vec::with_capacity(elements.len());
+    let mut product = first.one();
+    for element in elements {
+        product = product * element.clone();
+        prefix.push(product.clone());
+    }
+
+    let mut inverse = product.try_inverse()?;
+    let mut inverses = vec![first.zero(); elements.len()];
+    for i in (1..elements.len()).rev() {
+        inverses[i] = inverse.clone() * prefix[i - 1].clone();
+        inverse = inverse * elements[i].clone();
+    }
+    inverses[0] = inverse;
+    Ok(inverses)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::AlgebraError;
+    use crate::field::{batch_inverse, Field};
+    use crate::finite_field::{FieldElement, FiniteField};
+    use std::sync::Arc;
+
+    /// x^3 + x written only against the trait
+    fn cube_plus<F: Field>(x: &F) -> F {
+        x.square() * x.clone() + x.clone()
+    }
+
+    #[test]
+    fn test_field_element() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let x = finite_field.element(10);
+        assert_eq!(cube_plus(&x), finite_field.element(1010 % 97));
+        assert!(Field::zero(&x).is_zero());
+        assert_eq!(Field::try_inverse(&x).unwrap() * x.clone(), Field::one(&x));
+        assert_eq!(
+            Field::try_inverse(&Field::zero(&x)),
+            Err(AlgebraError::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn test_batch_inverse() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let elements: Vec<_> = finite_field.elements([3, 1, 96, 50, 7]).collect();
+        let inverses = batch_inverse(&elements).unwrap();
+        for (element, inverse) in elements.iter().zip(&inverses) {
+            assert_eq!(*inverse, element.inverse());
+        }
+        assert_eq!(batch_inverse::<FieldElement>(&[]), Ok(vec![]));
+
+        let with_zero: Vec<_> = finite_field.elements([3, 0, 7]).collect();
+        assert_eq!(batch_inverse(&with_zero), Err(AlgebraError::DivisionByZero));
+    }
+}
@@ -1,23 +1,47 @@
-use rand::random;
+use crate::error::AlgebraError;
+use rand::Rng;
+use std::cmp::Ordering;
 use std::fmt::{Display, Formatter};
-use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
-use std::rc::Rc;
+use std::hash::{Hash, Hasher};
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::sync::Arc;
 
 pub type FieldSize = i128;
 
 #[derive(Debug, Clone)]
 pub struct FieldElement {
     pub(crate) element: FieldSize,
-    finite_field: Rc<FiniteField>,
+    finite_field: Arc<FiniteField>,
 }
 
+/// Elements compare by their prime and canonical value, so an unreduced element equals its
+/// canonical form and elements of different fields are ordered by their primes.
 impl PartialEq for FieldElement {
     fn eq(&self, other: &Self) -> bool {
-        if self.finite_field.prime != other.finite_field.prime {
-            false
-        } else {
-            self.element % self.finite_field.prime == other.element % self.finite_field.prime
-        }
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for FieldElement {}
+
+impl PartialOrd for FieldElement {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FieldElement {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.finite_field.prime, self.canonical())
+            .cmp(&(other.finite_field.prime, other.canonical()))
+    }
+}
+
+impl Hash for FieldElement {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.finite_field.prime.hash(state);
+        self.canonical().hash(state);
     }
 }
 
@@ -32,12 +56,12 @@ impl Add for FieldElement {
 
     fn add(self, rhs: Self) -> Self::Output {
         assert!(
-            Rc::ptr_eq(&self.finite_field, &rhs.finite_field),
+            Arc::ptr_eq(&self.finite_field, &rhs.finite_field),
             "Cannot add elements from different finite fields"
         );
         Self {
             element: self.element + rhs.element,
-            finite_field: Rc::clone(&self.finite_field),
+            finite_field: Arc::clone(&self.finite_field),
         }
         .abs()
     }
@@ -48,11 +72,11 @@ impl Add for &FieldElement {
 
     fn add(self, rhs: Self) -> Self::Output {
         assert!(
-            Rc::ptr_eq(&self.finite_field, &rhs.finite_field),
+            Arc::ptr_eq(&self.finite_field, &rhs.finite_field),
             "Cannot add elements from different finite fields"
         );
         FieldElement {
-            element: &self.element + &rhs.element,
+            element: self.element + rhs.element,
             finite_field: self.finite_field.clone(),
         }
         .abs()
@@ -63,7 +87,7 @@ impl AddAssign for FieldElement {
     fn add_assign(&mut self, rhs: Self) {
         assert_eq!(self.finite_field, rhs.finite_field);
         *self = Self {
-            element: &self.element + &rhs.element,
+            element: self.element + rhs.element,
             finite_field: self.finite_field.clone(),
         }
         .abs();
@@ -74,12 +98,12 @@ impl Sub for FieldElement {
     type Output = FieldElement;
     fn sub(self, rhs: Self) -> Self::Output {
         assert!(
-            Rc::ptr_eq(&self.finite_field, &rhs.finite_field),
+            Arc::ptr_eq(&self.finite_field, &rhs.finite_field),
             "Cannot sub elements from different finite fields"
         );
         Self {
             element: self.element - rhs.element,
-            finite_field: Rc::clone(&self.finite_field),
+            finite_field: Arc::clone(&self.finite_field),
         }
         .abs()
     }
@@ -89,12 +113,12 @@ impl Sub for &FieldElement {
     type Output = FieldElement;
     fn sub(self, rhs: Self) -> Self::Output {
         assert!(
-            Rc::ptr_eq(&self.finite_field, &rhs.finite_field),
+            Arc::ptr_eq(&self.finite_field, &rhs.finite_field),
             "Cannot sub elements from different finite fields"
         );
         FieldElement {
-            element: &self.element - &rhs.element,
-            finite_field: Rc::clone(&self.finite_field),
+            element: self.element - rhs.element,
+            finite_field: Arc::clone(&self.finite_field),
         }
         .abs()
     }
@@ -116,7 +140,9 @@ impl Mul for FieldElement {
     fn mul(self, rhs: Self) -> Self::Output {
         assert_eq!(self.finite_field, rhs.finite_field);
         Self {
-            element: self.abs().element * rhs.abs().element,
+            element: self
+                .finite_field
+                .multiply(self.abs().element, rhs.abs().element),
             finite_field: self.finite_field.clone(),
         }
         .abs()
@@ -129,7 +155,9 @@ impl Mul for &FieldElement {
     fn mul(self, rhs: Self) -> Self::Output {
         assert_eq!(self.finite_field, rhs.finite_field);
         FieldElement {
-            element: self.abs().element * rhs.abs().element,
+            element: self
+                .finite_field
+                .multiply(self.abs().element, rhs.abs().element),
             finite_field: self.finite_field.clone(),
         }
         .abs()
@@ -176,75 +204,528 @@ impl Neg for FieldElement {
     }
 }
 
+impl Neg for &FieldElement {
+    type Output = FieldElement;
+
+    fn neg(self) -> Self::Output {
+        -self.clone()
+    }
+}
+
+/// the mixed owned and borrowed operands and the assigning forms, all through the `&a op &b` impls
+macro_rules! forward_binary_op {
+    ($($op:ident, $method:ident, $op_assign:ident, $method_assign:ident);*) => {
+        $(
+            impl $op<&FieldElement> for FieldElement {
+                type Output = FieldElement;
+
+                fn $method(self, rhs: &FieldElement) -> Self::Output {
+                    (&self).$method(rhs)
+                }
+            }
+
+            impl $op<FieldElement> for &FieldElement {
+                type Output = FieldElement;
+
+                fn $method(self, rhs: FieldElement) -> Self::Output {
+                    self.$method(&rhs)
+                }
+            }
+
+            impl $op_assign<&FieldElement> for FieldElement {
+                fn $method_assign(&mut self, rhs: &FieldElement) {
+                    *self = (&*self).$method(rhs);
+                }
+            }
+
+            /// the scalar is reduced modulo the prime first
+            impl $op<FieldSize> for FieldElement {
+                type Output = FieldElement;
+
+                fn $method(self, rhs: FieldSize) -> Self::Output {
+                    (&self).$method(rhs)
+                }
+            }
+
+            impl $op<FieldSize> for &FieldElement {
+                type Output = FieldElement;
+
+                fn $method(self, rhs: FieldSize) -> Self::Output {
+                    self.$method(&self.finite_field.element(rhs).abs())
+                }
+            }
+        )*
+    };
+}
+
+forward_binary_op!(
+    Add, add, AddAssign, add_assign;
+    Sub, sub, SubAssign, sub_assign;
+    Mul, mul, MulAssign, mul_assign;
+    Div, div, DivAssign, div_assign
+);
+
+impl MulAssign for FieldElement {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self *= &rhs;
+    }
+}
+
+impl DivAssign for FieldElement {
+    fn div_assign(&mut self, rhs: Self) {
+        *self /= &rhs;
+    }
+}
+
+impl Mul<FieldElement> for FieldSize {
+    type Output = FieldElement;
+
+    fn mul(self, rhs: FieldElement) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl Mul<&FieldElement> for FieldSize {
+    type Output = FieldElement;
+
+    fn mul(self, rhs: &FieldElement) -> Self::Output {
+        rhs * self
+    }
+}
+
+/// panics on an empty iterator, which has no field to take the zero from
+impl Sum for FieldElement {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(|acc, x| acc + x)
+            .expect("Cannot sum an empty iterator of field elements")
+    }
+}
+
+impl<'a> Sum<&'a FieldElement> for FieldElement {
+    fn sum<I: Iterator<Item = &'a FieldElement>>(iter: I) -> Self {
+        iter.cloned().sum()
+    }
+}
+
+/// panics on an empty iterator, which has no field to take the one from
+impl Product for FieldElement {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(|acc, x| acc * x)
+            .expect("Cannot multiply an empty iterator of field elements")
+    }
+}
+
+impl<'a> Product<&'a FieldElement> for FieldElement {
+    fn product<I: Iterator<Item = &'a FieldElement>>(iter: I) -> Self {
+        iter.cloned().product()
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($integer:ty),*) => {
+        $(
+            impl From<($integer, &Arc<FiniteField>)> for FieldElement {
+                /// reduces the value modulo the field prime
+                fn from((value, finite_field): ($integer, &Arc<FiniteField>)) -> Self {
+                    finite_field.element(value as FieldSize).abs()
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(u8, u16, u32, u64, i64);
+
+impl TryFrom<(FieldSize, &Arc<FiniteField>)> for FieldElement {
+    type Error = AlgebraError;
+
+    /// only accepts canonical values, 0 <= value < p
+    fn try_from(
+        (value, finite_field): (FieldSize, &Arc<FiniteField>),
+    ) -> Result<Self, Self::Error> {
+        if value < 0 || value >= finite_field.prime {
+            return Err(AlgebraError::NotCanonical(value));
+        }
+        Ok(finite_field.element(value))
+    }
+}
+
 impl FieldElement {
     pub fn inverse(&self) -> Self {
+        self.try_inverse()
+            .expect("Cannot compute the inverse of zero")
+    }
+
+    pub fn try_inverse(&self) -> Result<Self, AlgebraError> {
+        if self.abs().element == 0 {
+            return Err(AlgebraError::DivisionByZero);
+        }
         let xgcd = FiniteField::extended_euclidean(self.element, self.finite_field.prime);
         let inv = if xgcd.1.is_negative() {
             self.finite_field.prime + xgcd.1
         } else {
             xgcd.1.abs()
         };
-        Self {
+        Ok(Self {
             element: inv % self.finite_field.prime,
             finite_field: self.finite_field.clone(),
         }
-        .abs()
+        .abs())
+    }
+
+    /// inverts all the elements with a single inversion, panics if any of them is zero
+    pub fn batch_inverse(elements: &[FieldElement]) -> Vec<FieldElement> {
+        Self::try_batch_inverse(elements).expect("Cannot compute the inverse of zero")
+    }
+
+    pub fn try_batch_inverse(elements: &[FieldElement]) -> Result<Vec<FieldElement>, AlgebraError> {
+        crate::field::batch_inverse(elements)
     }
 
     pub fn value(&self) -> FieldSize {
         self.abs().element
     }
 
-    pub fn pow(&self, y: &FieldElement) -> FieldElement {
-        let mut result = self.clone();
-        for _i in 0..y.element - 1 {
-            result = &result * self;
+    pub fn finite_field(&self) -> &Arc<FiniteField> {
+        &self.finite_field
+    }
+
+    /// square and multiply over the bits of the exponent, `x^0 = 1`
+    pub fn pow(&self, exponent: u128) -> FieldElement {
+        let mut result = self.finite_field.one();
+        let mut square = self.clone();
+        let mut exponent = exponent;
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = &result * &square;
+            }
+            square = &square * &square;
+            exponent >>= 1;
         }
         result
     }
 
-    pub fn abs(&self) -> FieldElement {
-        let value = self.element.rem_euclid(self.finite_field.prime);
-        if self.element.is_negative() {
-            return FieldElement {
-                element: value + &self.finite_field.prime,
-                finite_field: self.finite_field.clone(),
-            };
+    /// the exponent is the canonical value of `e`
+    pub fn pow_field(&self, e: &FieldElement) -> FieldElement {
+        self.pow(e.value() as u128)
+    }
+
+    /// `byte_len` little-endian bytes
+    pub fn to_bytes_le(&self) -> Vec<u8> {
+        let width = self.finite_field.byte_len();
+        self.value().to_le_bytes()[..width].to_vec()
+    }
+
+    /// `byte_len` big-endian bytes
+    pub fn to_bytes_be(&self) -> Vec<u8> {
+        let mut bytes = self.to_bytes_le();
+        bytes.reverse();
+        bytes
+    }
+
+    /// Reads exactly `byte_len` little-endian bytes and only accepts canonical values.
+    pub fn from_bytes(bytes: &[u8], finite_field: &Arc<FiniteField>) -> Result<Self, AlgebraError> {
+        let width = finite_field.byte_len();
+        if bytes.len() != width {
+            return Err(AlgebraError::InvalidByteLength {
+                expected: width,
+                found: bytes.len(),
+            });
+        }
+        let mut buffer = [0u8; 16];
+        buffer[..width].copy_from_slice(bytes);
+        FieldElement::try_from((FieldSize::from_le_bytes(buffer), finite_field))
+    }
+
+    pub fn from_bytes_be(
+        bytes: &[u8],
+        finite_field: &Arc<FiniteField>,
+    ) -> Result<Self, AlgebraError> {
+        let mut bytes = bytes.to_vec();
+        bytes.reverse();
+        Self::from_bytes(&bytes, finite_field)
+    }
+
+    /// Legendre symbol `x^((p - 1) / 2)`: 1 for non-zero squares, -1 for non-squares and 0 for zero
+    pub fn legendre(&self) -> i8 {
+        if self.value() == 0 {
+            return 0;
+        }
+        let prime = self.finite_field.prime;
+        if self.pow(((prime - 1) / 2) as u128).value() == 1 {
+            1
+        } else {
+            -1
+        }
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.legendre() >= 0
+    }
+
+    /// Square root by Tonelli-Shanks, the smaller of the two roots, or `None` for a non-square.
+    ///
+    /// With `p - 1 = q * 2^s` for odd `q`, the candidate `r = x^((q + 1) / 2)` satisfies
+    /// `r^2 = x * t` with `t = x^q` in the 2-Sylow subgroup, and every step fixes one more bit of the
+    /// order of `t` using powers of a non-residue until `t = 1`.
+    pub fn sqrt(&self) -> Option<FieldElement> {
+        let finite_field = &self.finite_field;
+        let prime = finite_field.prime;
+        if self.value() == 0 || prime == 2 {
+            return Some(self.abs());
+        }
+        if self.legendre() != 1 {
+            return None;
+        }
+
+        let s = finite_field.two_adicity();
+        let q = ((prime - 1) >> s) as u128;
+        let one = finite_field.one();
+        let mut z = finite_field.element(2);
+        while z.legendre() != -1 {
+            z = &z + &one;
         }
 
+        let mut m = s;
+        let mut c = z.pow(q);
+        let mut t = self.pow(q);
+        let mut r = self.pow(q.div_ceil(2));
+        while t != one {
+            // least i with t^(2^i) = 1, i < m
+            let mut i = 0;
+            let mut square = t.clone();
+            while square != one {
+                square = &square * &square;
+                i += 1;
+            }
+            let b = c.pow(1 << (m - i - 1));
+            m = i;
+            c = &b * &b;
+            t = &t * &c;
+            r = &r * &b;
+        }
+
+        let other = (-r.clone()).abs();
+        Some(if other.value() < r.value() { other } else { r })
+    }
+
+    /// value in `0..p` without building a new element
+    pub(crate) fn canonical(&self) -> FieldSize {
+        self.element.rem_euclid(self.finite_field.prime)
+    }
+
+    /// the canonical representative, in `0..p`
+    pub fn abs(&self) -> FieldElement {
         FieldElement {
-            element: value,
+            element: self.canonical(),
             finite_field: self.finite_field.clone(),
         }
     }
 }
 
+/// Barrett reduction needs `3p < 2^128` for the intermediate remainder
+pub const MAX_BARRETT_PRIME: FieldSize = 1 << 126;
+
+/// Below it the product of two elements fits in a [`FieldSize`], from it on fields always multiply with
+/// Barrett reduction
+pub const MAX_PLAIN_PRIME: FieldSize = 1 << 63;
+
 #[derive(PartialEq, Debug, Clone)]
 pub struct FiniteField {
     pub prime: FieldSize,
     pub generator: FieldSize,
+    barrett: Option<Barrett>,
+}
+
+/// Precomputed `mu = floor(2^2k / p)` where `k` is the bit length of the prime.
+#[derive(PartialEq, Debug, Clone, Copy)]
+struct Barrett {
+    mu: u128,
+    k: u32,
+}
+
+impl Barrett {
+    fn new(prime: u128) -> Self {
+        let k = u128::BITS - prime.leading_zeros();
+        // long division of 2^2k by p, one bit at a time
+        let mut mu: u128 = 0;
+        let mut remainder: u128 = 0;
+        for bit in (0..=2 * k).rev() {
+            remainder = (remainder << 1) | (bit == 2 * k) as u128;
+            mu <<= 1;
+            if remainder >= prime {
+                remainder -= prime;
+                mu |= 1;
+            }
+        }
+        Self { mu, k }
+    }
+
+    /// `a * b mod p` for canonical `a` and `b`
+    fn multiply(&self, a: u128, b: u128, prime: u128) -> u128 {
+        let (x_hi, x_lo) = mul_wide(a, b);
+        let q1 = shr_wide(x_hi, x_lo, self.k - 1);
+        let (q2_hi, q2_lo) = mul_wide(q1, self.mu);
+        let q3 = shr_wide(q2_hi, q2_lo, self.k + 1);
+        // the estimate is off by at most 2, so the remainder is below 3p and fits in 128 bits
+        let mut remainder = x_lo.wrapping_sub(q3.wrapping_mul(prime));
+        while remainder >= prime {
+            remainder -= prime;
+        }
+        remainder
+    }
+}
+
+/// full 256 bit product as (high, low) halves
+fn mul_wide(a: u128, b: u128) -> (u128, u128) {
+    const MASK: u128 = u64::MAX as u128;
+    let (a0, a1) = (a & MASK, a >> 64);
+    let (b0, b1) = (b & MASK, b >> 64);
+    let p00 = a0 * b0;
+    let p01 = a0 * b1;
+    let p10 = a1 * b0;
+    let p11 = a1 * b1;
+
+    let middle = (p00 >> 64) + (p01 & MASK) + (p10 & MASK);
+    let low = (p00 & MASK) | (middle << 64);
+    let high = p11 + (p01 >> 64) + (p10 >> 64) + (middle >> 64);
+    (high, low)
+}
+
+/// low 128 bits of `(high, low) >> shift`
+fn shr_wide(high: u128, low: u128, shift: u32) -> u128 {
+    match shift {
+        0 => low,
+        1..=127 => (high << (128 - shift)) | (low >> shift),
+        _ => high >> (shift - 128),
+    }
 }
 
 impl FiniteField {
     pub fn new(prime: FieldSize, g: FieldSize) -> Self {
-        assert_ne!(g, 0, "Invalid generator");
-        Self {
+        Self::try_new(prime, g).expect("Invalid field parameters")
+    }
+
+    /// Fails if the modulus is not prime, see [`is_prime`], not below [`MAX_BARRETT_PRIME`], or if `g`
+    /// doesn't generate the multiplicative group, see [`FiniteField::is_generator`].
+    pub fn try_new(prime: FieldSize, g: FieldSize) -> Result<Self, AlgebraError> {
+        if prime >= MAX_BARRETT_PRIME {
+            return Err(AlgebraError::ModulusTooLarge(prime));
+        }
+        if !is_prime(prime) {
+            return Err(AlgebraError::CompositeModulus(prime));
+        }
+        let finite_field = Self::new_unchecked(prime, g);
+        if !finite_field.is_generator(g) {
+            return Err(AlgebraError::InvalidGenerator(g));
+        }
+        Ok(finite_field)
+    }
+
+    /// field of the given prime with its smallest generator
+    pub fn from_prime(prime: FieldSize) -> Result<Self, AlgebraError> {
+        if prime >= MAX_BARRETT_PRIME {
+            return Err(AlgebraError::ModulusTooLarge(prime));
+        }
+        if !is_prime(prime) {
+            return Err(AlgebraError::CompositeModulus(prime));
+        }
+        let finite_field = Self::new_unchecked(prime, 1);
+        let generator = finite_field.find_generator();
+        Ok(Self {
+            generator,
+            ..finite_field
+        })
+    }
+
+    /// Skips the primality and generator checks, for parameters known to be valid. A composite
+    /// modulus gives a ring where some inverses don't exist.
+    ///
+    /// Moduli from [`MAX_PLAIN_PRIME`] on multiply with Barrett reduction, their products would
+    /// overflow a [`FieldSize`]. Panics from [`MAX_BARRETT_PRIME`] on, where neither works.
+    pub fn new_unchecked(prime: FieldSize, g: FieldSize) -> Self {
+        assert!(prime < MAX_BARRETT_PRIME, "Fields need a prime below 2^126");
+        let field = Self {
             prime,
             generator: g,
+            barrett: None,
+        };
+        if prime >= MAX_PLAIN_PRIME {
+            field.with_barrett_reduction()
+        } else {
+            field
+        }
+    }
+
+    /// `g` generates the multiplicative group iff `g^((p - 1) / q) != 1` for every prime `q` dividing `p - 1`
+    pub fn is_generator(&self, g: FieldSize) -> bool {
+        let prime = self.prime as u128;
+        let g = g.rem_euclid(self.prime) as u128;
+        if g == 0 {
+            return false;
+        }
+        let order = prime - 1;
+        prime_factors(order)
+            .into_iter()
+            .all(|factor| pow_mod(g, order / factor, prime) != 1)
+    }
+
+    /// smallest primitive root, found by factoring `p - 1`
+    pub fn find_generator(&self) -> FieldSize {
+        (1..self.prime)
+            .find(|g| self.is_generator(*g))
+            .expect("The multiplicative group of a prime field is cyclic")
+    }
+
+    /// Multiplies with Barrett reduction instead of a division by the prime. Primes too large for the
+    /// product of two elements to fit in a [`FieldSize`] always do, see [`MAX_PLAIN_PRIME`].
+    pub fn with_barrett_reduction(self) -> Self {
+        assert!(
+            self.prime > 1 && self.prime < MAX_BARRETT_PRIME,
+            "Barrett reduction needs a prime below 2^126"
+        );
+        Self {
+            barrett: Some(Barrett::new(self.prime as u128)),
+            ..self
+        }
+    }
+
+    pub fn uses_barrett_reduction(&self) -> bool {
+        self.barrett.is_some()
+    }
+
+    /// `a * b mod p` for canonical `a` and `b`
+    pub(crate) fn multiply(&self, a: FieldSize, b: FieldSize) -> FieldSize {
+        match &self.barrett {
+            Some(barrett) => {
+                barrett.multiply(a as u128, b as u128, self.prime as u128) as FieldSize
+            }
+            None => a * b,
         }
     }
 
-    pub fn element(self: &Rc<Self>, value: FieldSize) -> FieldElement {
+    pub fn element(self: &Arc<Self>, value: FieldSize) -> FieldElement {
         FieldElement {
             element: value,
-            finite_field: Rc::clone(self),
+            finite_field: Arc::clone(self),
         }
     }
 
-    pub fn zero(self: &Rc<Self>) -> FieldElement {
+    /// `finite_field.elements(0..4)` yields the elements 0, 1, 2, 3
+    pub fn elements<I: IntoIterator<Item = FieldSize>>(
+        self: &Arc<Self>,
+        values: I,
+    ) -> impl Iterator<Item = FieldElement> {
+        let finite_field = Arc::clone(self);
+        values
+            .into_iter()
+            .map(move |value| finite_field.element(value))
+    }
+
+    pub fn zero(self: &Arc<Self>) -> FieldElement {
         self.element(0)
     }
-    pub fn one(self: &Rc<Self>) -> FieldElement {
+    pub fn one(self: &Arc<Self>) -> FieldElement {
         self.element(1)
     }
 
@@ -259,18 +740,69 @@ impl FiniteField {
         (gcd, x, y) // ax + by = gcd(a, b)
     }
 
-    pub fn random_element(self: &Rc<Self>) -> FieldElement {
-        let random = random();
-        self.element(random)
+    pub fn random_element(self: &Arc<Self>) -> FieldElement {
+        self.sample(&mut rand::thread_rng())
+    }
+
+    /// Uniform element drawn from `rng`. Draws of the bit length of the prime are rejected until
+    /// one is below it, so less than half of the draws are thrown away.
+    pub fn sample<R: Rng + ?Sized>(self: &Arc<Self>, rng: &mut R) -> FieldElement {
+        let bits = FieldSize::BITS - self.prime.leading_zeros();
+        let mask = u128::MAX >> (u128::BITS - bits);
+        loop {
+            let value = ((rng.next_u64() as u128) << 64 | rng.next_u64() as u128) & mask;
+            if value < self.prime as u128 {
+                return self.element(value as FieldSize);
+            }
+        }
     }
 
-    pub fn nth_root_of_unity(self: &Rc<Self>, n: FieldElement) -> Option<FieldElement> {
-        assert!(Rc::ptr_eq(&n.finite_field, self));
+    /// `n` uniform elements drawn from `rng`, the same `rng` state gives the same elements
+    pub fn random_elements<R: Rng + ?Sized>(
+        self: &Arc<Self>,
+        n: usize,
+        rng: &mut R,
+    ) -> Vec<FieldElement> {
+        (0..n).map(|_| self.sample(rng)).collect()
+    }
+
+    /// number of bytes of an encoded element, enough for the prime
+    pub fn byte_len(&self) -> usize {
+        let bits = FieldSize::BITS - self.prime.leading_zeros();
+        bits.div_ceil(8) as usize
+    }
+
+    /// largest `k` such that `2^k` divides `p - 1`, the largest power-of-two subgroup has order `2^k`
+    pub fn two_adicity(&self) -> u32 {
+        ((self.prime - 1) as u128).trailing_zeros()
+    }
+
+    /// `generator^((p - 1) / n)`, checked to have order exactly `n`.
+    ///
+    /// Fails with [`AlgebraError::InvalidDomainSize`] if `n` doesn't divide `p - 1`, and with
+    /// [`AlgebraError::InvalidGenerator`] if the field generator doesn't generate the multiplicative group.
+    pub fn primitive_nth_root(self: &Arc<Self>, n: usize) -> Result<FieldElement, AlgebraError> {
+        let order = (self.prime - 1) as u128;
+        if n == 0 || !order.is_multiple_of(n as u128) {
+            return Err(AlgebraError::InvalidDomainSize(n));
+        }
+        let root = self.element(self.generator).pow(order / n as u128);
+        let one = self.one();
+        for factor in prime_factors(n as u128) {
+            if root.pow(n as u128 / factor) == one {
+                return Err(AlgebraError::InvalidGenerator(self.generator));
+            }
+        }
+        Ok(root)
+    }
+
+    pub fn nth_root_of_unity(self: &Arc<Self>, n: FieldElement) -> Option<FieldElement> {
+        assert!(Arc::ptr_eq(&n.finite_field, self));
         let mut felt = self.element(2);
         let one = self.one();
 
         while felt.element < self.prime - 1 {
-            if felt.pow(&n) == one {
+            if felt.pow_field(&n) == one {
                 return Some(felt);
             }
             felt = &felt + &one;
@@ -280,14 +812,142 @@ impl FiniteField {
     }
 }
 
+/// Miller-Rabin bases, deterministic for all moduli below 3.3 * 10^24 (the first 13 suffice there)
+/// and with a negligible error probability above
+const WITNESSES: [u128; 20] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71,
+];
+
+/// Miller-Rabin primality test.
+///
+/// Writes `n - 1 = d * 2^s` with odd `d`. For a prime `n` every witness `a` has `a^d = 1` or
+/// `a^(d * 2^r) = -1` for some `r < s`, a composite fails this for most `a`.
+pub fn is_prime(n: FieldSize) -> bool {
+    if n < 2 {
+        return false;
+    }
+    let n = n as u128;
+    for witness in WITNESSES {
+        if n.is_multiple_of(witness) {
+            return n == witness;
+        }
+    }
+
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    'witness: for witness in WITNESSES {
+        let mut x = pow_mod(witness, d, n);
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        for _ in 1..s {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                continue 'witness;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+fn mul_mod(a: u128, b: u128, modulus: u128) -> u128 {
+    if (a | b) >> 64 == 0 {
+        return a * b % modulus;
+    }
+    // remainder of the 256 bit product, one bit at a time, the modulus is below 2^127
+    let (high, low) = mul_wide(a, b);
+    let mut remainder = 0;
+    for bit in (0..256).rev() {
+        let word = if bit >= 128 { high } else { low };
+        remainder = (remainder << 1) | (word >> (bit % 128) & 1);
+        if remainder >= modulus {
+            remainder -= modulus;
+        }
+    }
+    remainder
+}
+
+fn pow_mod(base: u128, mut exponent: u128, modulus: u128) -> u128 {
+    let mut result = 1;
+    let mut square = base % modulus;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, square, modulus);
+        }
+        square = mul_mod(square, square, modulus);
+        exponent >>= 1;
+    }
+    result
+}
+
+/// distinct prime factors in increasing order, by trial division up to 1000 then Pollard's rho
+pub(crate) fn prime_factors(mut n: u128) -> Vec<u128> {
+    let mut factors = vec![];
+    let mut factor = 2;
+    while factor < 1000 && factor * factor <= n {
+        if n.is_multiple_of(factor) {
+            factors.push(factor);
+            while n.is_multiple_of(factor) {
+                n /= factor;
+            }
+        }
+        factor += 1;
+    }
+    if n > 1 {
+        split(n, &mut factors);
+    }
+    factors.sort_unstable();
+    factors.dedup();
+    factors
+}
+
+fn split(n: u128, factors: &mut Vec<u128>) {
+    if is_prime(n as FieldSize) {
+        factors.push(n);
+        return;
+    }
+    let divisor = pollard_rho(n);
+    split(divisor, factors);
+    split(n / divisor, factors);
+}
+
+/// a non-trivial divisor of an odd composite `n`, from a cycle of `x -> x^2 + c mod n`
+fn pollard_rho(n: u128) -> u128 {
+    for c in 1.. {
+        let step = |x: u128| (mul_mod(x, x, n) + c) % n;
+        let (mut x, mut y, mut divisor) = (2, 2, 1);
+        while divisor == 1 {
+            x = step(x);
+            y = step(step(y));
+            divisor = gcd(x.abs_diff(y), n);
+        }
+        if divisor != n {
+            return divisor;
+        }
+    }
+    unreachable!("some c gives a divisor")
+}
+
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
 #[cfg(test)]
 mod tests {
-    use super::FiniteField;
-    use std::rc::Rc;
+    use super::{is_prime, prime_factors, FieldElement, FieldSize, FiniteField, MAX_PLAIN_PRIME};
+    use crate::error::AlgebraError;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::collections::{BTreeSet, HashSet};
+    use std::sync::Arc;
 
     #[test]
     fn test_finite_field() {
-        let finite_field = Rc::new(FiniteField::new(97, 1));
+        let finite_field = Arc::new(FiniteField::new(97, 5));
         let field_element1 = finite_field.element(6);
         let field_element2 = finite_field.element(3);
 
@@ -299,7 +959,7 @@ mod tests {
     #[test]
     fn test_xeuclidean() {
         let prime = 97;
-        let finite_field = Rc::new(FiniteField::new(prime, 1));
+        let finite_field = Arc::new(FiniteField::from_prime(prime).unwrap());
 
         for i in 1..prime {
             let result = FiniteField::extended_euclidean(i, prime);
@@ -311,10 +971,423 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_pow() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let x = finite_field.element(10);
+        assert_eq!(x.pow(0), finite_field.one());
+        assert_eq!(x.pow(1), x);
+        assert_eq!(x.pow(3), finite_field.element(1000 % 97));
+        // Fermat's little theorem, the exponent is far too large for repeated multiplication
+        assert_eq!(x.pow(96 * 1_000_000_000_000 + 1), x);
+        assert_eq!(x.pow_field(&finite_field.element(3)), x.pow(3));
+        assert_eq!(finite_field.zero().pow(0), finite_field.one());
+    }
+
+    #[test]
+    fn test_sqrt() {
+        // 97 - 1 = 3 * 2^5, 13 - 1 = 3 * 2^2, 23 = 3 (mod 4)
+        for prime in [97, 13, 23] {
+            let finite_field = Arc::new(FiniteField::from_prime(prime).unwrap());
+            let mut squares = 0;
+            for value in 1..prime {
+                let x = finite_field.element(value);
+                match x.sqrt() {
+                    Some(root) => {
+                        assert_eq!(&root * &root, x);
+                        assert!(root.value() <= prime / 2);
+                        assert_eq!(x.legendre(), 1);
+                        squares += 1;
+                    }
+                    None => assert_eq!(x.legendre(), -1),
+                }
+            }
+            assert_eq!(squares, (prime - 1) / 2);
+            assert_eq!(finite_field.zero().legendre(), 0);
+            assert_eq!(finite_field.zero().sqrt(), Some(finite_field.zero()));
+        }
+
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        assert!(!finite_field.element(5).is_square());
+        assert_eq!(
+            finite_field.element(64).sqrt(),
+            Some(finite_field.element(8))
+        );
+    }
+
+    #[test]
+    fn test_bytes() {
+        let finite_field = Arc::new(FiniteField::new(65537, 3));
+        assert_eq!(finite_field.byte_len(), 3);
+        let x = finite_field.element(0x01_0000);
+        assert_eq!(x.to_bytes_le(), vec![0x00, 0x00, 0x01]);
+        assert_eq!(x.to_bytes_be(), vec![0x01, 0x00, 0x00]);
+        assert_eq!(
+            finite_field.element(0x1234).to_bytes_be(),
+            vec![0x00, 0x12, 0x34]
+        );
+        assert_eq!(
+            FieldElement::from_bytes(&x.to_bytes_le(), &finite_field),
+            Ok(x.clone())
+        );
+        assert_eq!(
+            FieldElement::from_bytes_be(&x.to_bytes_be(), &finite_field),
+            Ok(x)
+        );
+
+        assert_eq!(
+            FieldElement::from_bytes(&[0x01, 0x00, 0x01], &finite_field),
+            Err(AlgebraError::NotCanonical(65537))
+        );
+        assert_eq!(
+            FieldElement::from_bytes(&[0x01, 0x00], &finite_field),
+            Err(AlgebraError::InvalidByteLength {
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(Arc::new(FiniteField::new(97, 5)).byte_len(), 1);
+    }
+
+    #[test]
+    fn test_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<FieldElement>();
+        assert_send_sync::<crate::polynomial::Polynomial>();
+
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let elements: Vec<_> = finite_field.elements(1..97).collect();
+        let products: Vec<FieldElement> = std::thread::scope(|scope| {
+            let handles: Vec<_> = elements
+                .chunks(24)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .fold(chunk[0].finite_field().one(), |acc, x| &acc * x)
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+        // Wilson's theorem, (p - 1)! = -1
+        let product = products.iter().fold(finite_field.one(), |acc, x| &acc * x);
+        assert_eq!(product, finite_field.element(96));
+    }
+
+    #[test]
+    fn test_primality() {
+        let primes = [2, 3, 97, 65537, 3221225473, (1 << 61) - 1, (1 << 107) - 1];
+        for prime in primes {
+            assert!(is_prime(prime), "{prime} is prime");
+        }
+        // 561 is a Carmichael number, 3215031751 is a strong pseudoprime to bases 2, 3, 5 and 7
+        let composites = [
+            -7,
+            0,
+            1,
+            91,
+            561,
+            3215031751,
+            ((1 << 31) - 1) * ((1 << 61) - 1),
+        ];
+        for composite in composites {
+            assert!(!is_prime(composite), "{composite} is composite");
+        }
+
+        assert_eq!(
+            FiniteField::try_new(91, 3),
+            Err(AlgebraError::CompositeModulus(91))
+        );
+        let ring = FiniteField::new_unchecked(91, 3);
+        assert_eq!(ring.prime, 91);
+    }
+
+    #[test]
+    fn test_generators() {
+        let finite_field = FiniteField::new(97, 5);
+        assert!(finite_field.is_generator(5));
+        assert!(!finite_field.is_generator(1));
+        assert!(!finite_field.is_generator(4));
+        assert!(!finite_field.is_generator(97));
+        assert_eq!(finite_field.find_generator(), 5);
+        assert_eq!(
+            FiniteField::try_new(97, 4),
+            Err(AlgebraError::InvalidGenerator(4))
+        );
+
+        // (p - 1) has large prime factors that trial division alone would not find in time
+        let prime = (1 << 107) - 1;
+        let finite_field = FiniteField::from_prime(prime).unwrap();
+        assert_eq!(finite_field.generator, 3);
+        assert_eq!(
+            FiniteField::from_prime(91),
+            Err(AlgebraError::CompositeModulus(91))
+        );
+        assert_eq!(FiniteField::from_prime(2).unwrap().generator, 1);
+        assert_eq!(
+            prime_factors(2 * 2 * 3 * 1_000_003 * 1_000_033),
+            vec![2, 3, 1_000_003, 1_000_033]
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            FiniteField::try_new(97, 0),
+            Err(AlgebraError::InvalidGenerator(0))
+        );
+
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        assert_eq!(
+            finite_field.zero().try_inverse(),
+            Err(AlgebraError::DivisionByZero)
+        );
+        assert_eq!(
+            finite_field.element(97).try_inverse(),
+            Err(AlgebraError::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn test_operators() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let (x, y) = (finite_field.element(20), finite_field.element(90));
+
+        assert_eq!(x.clone() + &y, finite_field.element(13));
+        assert_eq!(&x + y.clone(), finite_field.element(13));
+        assert_eq!(x.clone() - &y, finite_field.element(27));
+        assert_eq!(&x - y.clone(), finite_field.element(27));
+        assert_eq!(x.clone() * &y, finite_field.element(54));
+        assert_eq!(&x * y.clone(), finite_field.element(54));
+        assert_eq!(x.clone() / &y * &y, x);
+        assert_eq!(&x / y.clone() * y.clone(), x);
+        assert_eq!(-&x, finite_field.element(77));
+
+        let mut z = x.clone();
+        z += &y;
+        z -= &y;
+        z *= &y;
+        z *= y.clone();
+        z /= &y;
+        z /= y.clone();
+        assert_eq!(z, x);
+
+        assert_eq!(&x * 5, finite_field.element(3));
+        assert_eq!(5 * &x, finite_field.element(3));
+        assert_eq!(x.clone() + 80, finite_field.element(3));
+        assert_eq!(&x - 21, finite_field.element(96));
+        assert_eq!(x.clone() * -1, -x.clone());
+        assert_eq!(&x / 20, finite_field.one());
+    }
+
+    #[test]
+    fn test_eq_hash_ord() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let minus_one = finite_field.element(-1);
+        assert_eq!(minus_one, finite_field.element(96));
+        assert_eq!(finite_field.element(98), finite_field.one());
+        assert!(finite_field.element(95) < minus_one);
+        assert!(finite_field.zero() < finite_field.one());
+
+        let set: HashSet<FieldElement> =
+            [-1, 96, 193, 1, 98].map(|x| finite_field.element(x)).into();
+        assert_eq!(set.len(), 2);
+        let sorted: BTreeSet<FieldElement> = finite_field.elements([50, -3, 7]).collect();
+        assert_eq!(
+            sorted.into_iter().collect::<Vec<_>>(),
+            finite_field.elements([7, 50, 94]).collect::<Vec<_>>()
+        );
+
+        let other_field = Arc::new(FiniteField::new(13, 2));
+        assert_ne!(other_field.one(), finite_field.one());
+        assert!(other_field.element(12) < finite_field.one());
+    }
+
+    #[test]
+    fn test_sum_product() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let xs: Vec<FieldElement> = finite_field.elements([50, 60, 70]).collect();
+        assert_eq!(xs.iter().sum::<FieldElement>(), finite_field.element(83));
+        assert_eq!(
+            xs.clone().into_iter().sum::<FieldElement>(),
+            finite_field.element(83)
+        );
+        assert_eq!(
+            xs.iter().product::<FieldElement>(),
+            finite_field.element(50 * 60 * 70)
+        );
+
+        let ys: Vec<FieldElement> = finite_field.elements([1, 2, 3]).collect();
+        let inner_product: FieldElement = xs.iter().zip(&ys).map(|(x, y)| x * y).sum();
+        assert_eq!(inner_product, finite_field.element(50 + 120 + 210));
+    }
+
+    #[test]
+    #[should_panic(expected = "empty iterator")]
+    fn test_empty_sum() {
+        let _: FieldElement = Vec::<FieldElement>::new().into_iter().sum();
+    }
+
+    #[test]
+    fn test_sample() {
+        let finite_field = Arc::new(FiniteField::new(13, 2));
+        let elements = finite_field.random_elements(13_000, &mut StdRng::seed_from_u64(42));
+        assert_eq!(
+            elements,
+            finite_field.random_elements(13_000, &mut StdRng::seed_from_u64(42))
+        );
+
+        let mut counts = [0; 13];
+        for element in &elements {
+            counts[element.value() as usize] += 1;
+        }
+        // each value is expected 1000 times
+        assert!(
+            counts.iter().all(|count| (800..1200).contains(count)),
+            "{counts:?}"
+        );
+
+        let prime = (1 << 107) - 1;
+        let finite_field = Arc::new(FiniteField::from_prime(prime).unwrap());
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..100 {
+            assert!((0..prime).contains(&finite_field.sample(&mut rng).value()));
+        }
+    }
+
+    #[test]
+    fn test_barrett_reduction() {
+        let naive = Arc::new(FiniteField::new(97, 5));
+        let barrett = Arc::new(FiniteField::new(97, 5).with_barrett_reduction());
+        assert!(barrett.uses_barrett_reduction());
+        for a in 0..97 {
+            for b in 0..97 {
+                assert_eq!(
+                    (&barrett.element(a) * &barrett.element(b)).value(),
+                    (&naive.element(a) * &naive.element(b)).value()
+                );
+            }
+        }
+
+        // 2^107 - 1, products of elements overflow an i128
+        let prime = (1 << 107) - 1;
+        let finite_field = Arc::new(FiniteField::new(prime, 3).with_barrett_reduction());
+        let minus_one = finite_field.element(prime - 1);
+        assert_eq!(&minus_one * &minus_one, finite_field.one());
+        assert_eq!(
+            &finite_field.element(1 << 106) * &finite_field.element(2),
+            finite_field.one()
+        );
+        let x = finite_field.element(0x1234_5678_9abc_def0_1234_5678_9abc);
+        assert_eq!(&x * &x.inverse(), finite_field.one());
+    }
+
+    #[test]
+    fn test_large_primes_use_barrett_reduction() {
+        assert!(!FiniteField::new(3221225473, 5).uses_barrett_reduction());
+        assert!(FiniteField::new_unchecked(MAX_PLAIN_PRIME + 1, 3).uses_barrett_reduction());
+
+        // 2^89 - 1, without asking for Barrett reduction
+        let prime = (1 << 89) - 1;
+        let finite_field = Arc::new(FiniteField::from_prime(prime).unwrap());
+        assert!(finite_field.uses_barrett_reduction());
+        let minus_one = finite_field.element(prime - 1);
+        assert_eq!(&minus_one * &minus_one, finite_field.one());
+        assert_eq!(
+            &finite_field.element(1 << 88) * &finite_field.element(2),
+            finite_field.one()
+        );
+        let x = finite_field.element(0x1234_5678_9abc_def0_1234);
+        assert_eq!(&x * &x.inverse(), finite_field.one());
+
+        // 2^127 - 1 is prime, but too large for either multiplication
+        let prime = FieldSize::MAX;
+        assert_eq!(
+            FiniteField::from_prime(prime),
+            Err(AlgebraError::ModulusTooLarge(prime))
+        );
+        assert_eq!(
+            FiniteField::try_new(prime, 43),
+            Err(AlgebraError::ModulusTooLarge(prime))
+        );
+    }
+
+    #[test]
+    fn test_conversions() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+
+        assert_eq!(
+            FieldElement::from((5u8, &finite_field)),
+            finite_field.element(5)
+        );
+        assert_eq!(
+            FieldElement::from((100u16, &finite_field)),
+            finite_field.element(3)
+        );
+        assert_eq!(
+            FieldElement::from((97u32, &finite_field)),
+            finite_field.zero()
+        );
+        assert_eq!(
+            FieldElement::from((u64::MAX, &finite_field)).value(),
+            (u64::MAX % 97) as i128
+        );
+        assert_eq!(
+            FieldElement::from((-1i64, &finite_field)),
+            finite_field.element(96)
+        );
+
+        assert_eq!(
+            FieldElement::try_from((96i128, &finite_field)),
+            Ok(finite_field.element(96))
+        );
+        assert_eq!(
+            FieldElement::try_from((97i128, &finite_field)),
+            Err(AlgebraError::NotCanonical(97))
+        );
+        assert_eq!(
+            FieldElement::try_from((-1i128, &finite_field)),
+            Err(AlgebraError::NotCanonical(-1))
+        );
+
+        let elements: Vec<FieldElement> = finite_field.elements(0..3).collect();
+        assert_eq!(
+            elements,
+            vec![
+                finite_field.zero(),
+                finite_field.one(),
+                finite_field.element(2)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_primitive_nth_root() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        assert_eq!(finite_field.two_adicity(), 5);
+        let root = finite_field.primitive_nth_root(12).unwrap();
+        assert_eq!(root.pow(12), finite_field.one());
+        assert_ne!(root.pow(6), finite_field.one());
+        assert_ne!(root.pow(4), finite_field.one());
+        assert_eq!(finite_field.primitive_nth_root(1), Ok(finite_field.one()));
+        assert_eq!(
+            finite_field.primitive_nth_root(64),
+            Err(AlgebraError::InvalidDomainSize(64))
+        );
+
+        // 4 = 2^2 is a square, so it doesn't generate the group
+        let bad_generator = Arc::new(FiniteField::new_unchecked(97, 4));
+        assert_eq!(
+            bad_generator.primitive_nth_root(32),
+            Err(AlgebraError::InvalidGenerator(4))
+        );
+    }
+
     #[test]
     fn test_nth_root_of_unity() {
         let prime = 97;
-        let finite_field = Rc::new(FiniteField::new(prime, 1));
+        let finite_field = Arc::new(FiniteField::from_prime(prime).unwrap());
         for n in 2..prime {
             if let Some(root_of_unity) = finite_field.nth_root_of_unity(finite_field.element(n)) {
                 println!("First {}-th root of unity : {}", n, root_of_unity);
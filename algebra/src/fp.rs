@@ -0,0 +1,254 @@
+//! Prime field with the modulus fixed at compile time.
+//!
+//! `Fp<P>` is a canonical `u64` and is `Copy`, so arithmetic doesn't clone or reference count a field
+//! handle like [`FieldElement`] does. Mixing elements of different fields is a type error.
+use crate::error::AlgebraError;
+use crate::field::Field;
+use crate::finite_field::{FieldElement, FieldSize, FiniteField};
+use rand::Rng;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::sync::Arc;
+
+/// element of the prime field of order `P`, the primality of `P` isn't checked
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Fp<const P: u64>(u64);
+
+impl<const P: u64> Fp<P> {
+    pub const ZERO: Self = Self(0);
+    pub const ONE: Self = Self(1);
+    pub const MODULUS: u64 = P;
+
+    /// reduces the value modulo `P`
+    pub const fn new(value: u64) -> Self {
+        assert!(P > 1, "The modulus must be at least 2");
+        Self(value % P)
+    }
+
+    pub const fn value(&self) -> u64 {
+        self.0
+    }
+
+    pub fn inverse(&self) -> Self {
+        self.try_inverse()
+            .expect("Cannot compute the inverse of zero")
+    }
+
+    /// `x^(P - 2)`
+    pub fn try_inverse(&self) -> Result<Self, AlgebraError> {
+        if self.0 == 0 {
+            return Err(AlgebraError::DivisionByZero);
+        }
+        Ok(self.pow(P as u128 - 2))
+    }
+
+    pub fn pow(&self, mut exponent: u128) -> Self {
+        let mut result = Self::ONE;
+        let mut square = *self;
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result *= square;
+            }
+            square *= square;
+            exponent >>= 1;
+        }
+        result
+    }
+
+    pub fn random_element() -> Self {
+        Self::sample(&mut rand::thread_rng())
+    }
+
+    /// uniform element drawn from `rng` by rejection, reducing a raw `u64` would favour small values
+    pub fn sample<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let mask = u64::MAX >> P.leading_zeros();
+        loop {
+            let value = rng.next_u64() & mask;
+            if value < P {
+                return Self(value);
+            }
+        }
+    }
+
+    pub fn random_elements<R: Rng + ?Sized>(n: usize, rng: &mut R) -> Vec<Self> {
+        (0..n).map(|_| Self::sample(rng)).collect()
+    }
+
+    /// the same element with a handle to the given field, which must have modulus `P`
+    pub fn to_field_element(
+        &self,
+        finite_field: &Arc<FiniteField>,
+    ) -> Result<FieldElement, AlgebraError> {
+        if finite_field.prime != P as FieldSize {
+            return Err(AlgebraError::ModulusMismatch(finite_field.prime));
+        }
+        Ok(finite_field.element(self.0 as FieldSize))
+    }
+}
+
+impl<const P: u64> TryFrom<&FieldElement> for Fp<P> {
+    type Error = AlgebraError;
+
+    fn try_from(element: &FieldElement) -> Result<Self, Self::Error> {
+        let prime = element.finite_field().prime;
+        if prime != P as FieldSize {
+            return Err(AlgebraError::ModulusMismatch(prime));
+        }
+        Ok(Self(element.value() as u64))
+    }
+}
+
+impl<const P: u64> From<u64> for Fp<P> {
+    fn from(value: u64) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<const P: u64> Display for Fp<P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<const P: u64> Add for Fp<P> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let sum = self.0 as u128 + rhs.0 as u128;
+        if sum >= P as u128 {
+            Self((sum - P as u128) as u64)
+        } else {
+            Self(sum as u64)
+        }
+    }
+}
+
+impl<const P: u64> AddAssign for Fp<P> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const P: u64> Sub for Fp<P> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        if self.0 >= rhs.0 {
+            Self(self.0 - rhs.0)
+        } else {
+            Self(P - (rhs.0 - self.0))
+        }
+    }
+}
+
+impl<const P: u64> SubAssign for Fp<P> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const P: u64> Mul for Fp<P> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self((self.0 as u128 * rhs.0 as u128 % P as u128) as u64)
+    }
+}
+
+impl<const P: u64> MulAssign for Fp<P> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<const P: u64> Div for Fp<P> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        assert_ne!(rhs.0, 0, "Division by zero is not allowed");
+        self * rhs.inverse()
+    }
+}
+
+impl<const P: u64> Neg for Fp<P> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::ZERO - self
+    }
+}
+
+impl<const P: u64> Field for Fp<P> {
+    fn zero(&self) -> Self {
+        Self::ZERO
+    }
+
+    fn one(&self) -> Self {
+        Self::ONE
+    }
+
+    fn try_inverse(&self) -> Result<Self, AlgebraError> {
+        Fp::try_inverse(self)
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self {
+        Self::sample(rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::AlgebraError;
+    use crate::finite_field::FiniteField;
+    use crate::fp::Fp;
+    use std::sync::Arc;
+
+    type F97 = Fp<97>;
+
+    #[test]
+    fn test_fp() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        for a in 0..97 {
+            for b in [0, 1, 2, 50, 96] {
+                let (x, y) = (F97::new(a), F97::new(b));
+                let (fx, fy) = (
+                    finite_field.element(a as i128),
+                    finite_field.element(b as i128),
+                );
+                assert_eq!((x + y).value() as i128, (&fx + &fy).value());
+                assert_eq!((x - y).value() as i128, (&fx - &fy).value());
+                assert_eq!((x * y).value() as i128, (&fx * &fy).value());
+            }
+        }
+        let x = F97::new(100);
+        assert_eq!(x, F97::new(3));
+        assert_eq!(x * x.inverse(), F97::ONE);
+        assert_eq!(-x + x, F97::ZERO);
+        assert_eq!(F97::ZERO.try_inverse(), Err(AlgebraError::DivisionByZero));
+
+        // close to 2^64, the sum of two elements overflows a u64
+        const P: u64 = 0xffff_ffff_ffff_ffc5;
+        let minus_one = Fp::<P>::new(P - 1);
+        assert_eq!(minus_one + minus_one, Fp::<P>::new(P - 2));
+        assert_eq!(minus_one * minus_one, Fp::<P>::ONE);
+    }
+
+    #[test]
+    fn test_field_element_conversions() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let element = finite_field.element(42);
+        let x = F97::try_from(&element).unwrap();
+        assert_eq!(x, F97::new(42));
+        assert_eq!(x.to_field_element(&finite_field), Ok(element));
+
+        let other_field = Arc::new(FiniteField::new(13, 2));
+        assert_eq!(
+            F97::try_from(&other_field.one()),
+            Err(AlgebraError::ModulusMismatch(13))
+        );
+        assert_eq!(
+            x.to_field_element(&other_field),
+            Err(AlgebraError::ModulusMismatch(13))
+        );
+    }
+}
@@ -0,0 +1,273 @@
+//! The Goldilocks field, `p = 2^64 - 2^32 + 1`.
+//!
+//! Elements are canonical `u64`s. Since `2^64 = 2^32 - 1 (mod p)` and `2^96 = -1 (mod p)`, a 128 bit
+//! product reduces with a few 64 bit additions and subtractions instead of a division, and the
+//! products never overflow like they would with the generic [`FieldSize`](crate::finite_field::FieldSize) path.
+use crate::error::AlgebraError;
+use crate::field::Field;
+use rand::Rng;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// 2^64 - 2^32 + 1
+pub const GOLDILOCKS_PRIME: u64 = 0xffff_ffff_0000_0001;
+/// generator of the multiplicative group
+pub const GOLDILOCKS_GENERATOR: u64 = 7;
+/// 2^64 mod p
+const EPSILON: u64 = 0xffff_ffff;
+
+#[derive(Debug, Clone, Copy)]
+pub struct GoldilocksField;
+
+impl GoldilocksField {
+    /// reduces the value modulo the prime
+    pub fn element(value: u64) -> GoldilocksElement {
+        GoldilocksElement(canonicalize(value))
+    }
+
+    pub fn zero() -> GoldilocksElement {
+        GoldilocksElement(0)
+    }
+
+    pub fn one() -> GoldilocksElement {
+        GoldilocksElement(1)
+    }
+
+    pub fn generator() -> GoldilocksElement {
+        GoldilocksElement(GOLDILOCKS_GENERATOR)
+    }
+
+    pub fn random_element() -> GoldilocksElement {
+        Self::sample(&mut rand::thread_rng())
+    }
+
+    /// uniform element drawn from `rng` by rejection
+    pub fn sample<R: Rng + ?Sized>(rng: &mut R) -> GoldilocksElement {
+        loop {
+            let value = rng.next_u64();
+            if value < GOLDILOCKS_PRIME {
+                return GoldilocksElement(value);
+            }
+        }
+    }
+
+    pub fn random_elements<R: Rng + ?Sized>(n: usize, rng: &mut R) -> Vec<GoldilocksElement> {
+        (0..n).map(|_| Self::sample(rng)).collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GoldilocksElement(u64);
+
+impl GoldilocksElement {
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+
+    pub fn inverse(&self) -> Self {
+        self.try_inverse()
+            .expect("Cannot compute the inverse of zero")
+    }
+
+    /// `x^(p - 2)`
+    pub fn try_inverse(&self) -> Result<Self, AlgebraError> {
+        if self.0 == 0 {
+            return Err(AlgebraError::DivisionByZero);
+        }
+        Ok(self.pow(GOLDILOCKS_PRIME - 2))
+    }
+
+    pub fn pow(&self, exponent: u64) -> Self {
+        let mut result = GoldilocksField::one();
+        let mut base = *self;
+        let mut exponent = exponent;
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result *= base;
+            }
+            base *= base;
+            exponent >>= 1;
+        }
+        result
+    }
+}
+
+fn canonicalize(value: u64) -> u64 {
+    if value >= GOLDILOCKS_PRIME {
+        value - GOLDILOCKS_PRIME
+    } else {
+        value
+    }
+}
+
+/// `x = x_lo + 2^64 * x_hi_lo + 2^96 * x_hi_hi = x_lo + EPSILON * x_hi_lo - x_hi_hi (mod p)`
+fn reduce128(x: u128) -> u64 {
+    let x_lo = x as u64;
+    let x_hi = (x >> 64) as u64;
+    let x_hi_hi = x_hi >> 32;
+    let x_hi_lo = x_hi & EPSILON;
+
+    let (mut t0, borrow) = x_lo.overflowing_sub(x_hi_hi);
+    if borrow {
+        // adds p back, t0 >= 2^64 - 2^32 here so this can't underflow
+        t0 -= EPSILON;
+    }
+    let t1 = x_hi_lo * EPSILON;
+    let (result, carry) = t0.overflowing_add(t1);
+    // t1 <= (2^32 - 1)^2 so the correction of the carry can't overflow again
+    canonicalize(result + EPSILON * carry as u64)
+}
+
+impl Display for GoldilocksElement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<u64> for GoldilocksElement {
+    fn from(value: u64) -> Self {
+        GoldilocksField::element(value)
+    }
+}
+
+impl Add for GoldilocksElement {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let (sum, overflow) = self.0.overflowing_add(rhs.0);
+        // both are canonical, so after an overflow sum + EPSILON < 2^64
+        Self(canonicalize(sum + EPSILON * overflow as u64))
+    }
+}
+
+impl AddAssign for GoldilocksElement {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for GoldilocksElement {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let (difference, borrow) = self.0.overflowing_sub(rhs.0);
+        if borrow {
+            Self(difference.wrapping_add(GOLDILOCKS_PRIME))
+        } else {
+            Self(difference)
+        }
+    }
+}
+
+impl SubAssign for GoldilocksElement {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul for GoldilocksElement {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self(reduce128(self.0 as u128 * rhs.0 as u128))
+    }
+}
+
+impl MulAssign for GoldilocksElement {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl Div for GoldilocksElement {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        assert_ne!(rhs.0, 0, "Division by zero is not allowed");
+        self * rhs.inverse()
+    }
+}
+
+impl Neg for GoldilocksElement {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        if self.0 == 0 {
+            self
+        } else {
+            Self(GOLDILOCKS_PRIME - self.0)
+        }
+    }
+}
+
+impl Field for GoldilocksElement {
+    fn zero(&self) -> Self {
+        GoldilocksField::zero()
+    }
+
+    fn one(&self) -> Self {
+        GoldilocksField::one()
+    }
+
+    fn try_inverse(&self) -> Result<Self, AlgebraError> {
+        GoldilocksElement::try_inverse(self)
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self {
+        GoldilocksField::sample(rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::AlgebraError;
+    use crate::goldilocks::{GoldilocksField, GOLDILOCKS_PRIME};
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_goldilocks() {
+        let minus_one = GoldilocksField::element(GOLDILOCKS_PRIME - 1);
+        assert_eq!(-GoldilocksField::one(), minus_one);
+        assert_eq!(minus_one * minus_one, GoldilocksField::one());
+        assert_eq!(
+            minus_one + GoldilocksField::element(2),
+            GoldilocksField::one()
+        );
+        assert_eq!(GoldilocksField::zero() - GoldilocksField::one(), minus_one);
+        assert_eq!(
+            GoldilocksField::element(u64::MAX).value(),
+            u64::MAX - GOLDILOCKS_PRIME
+        );
+
+        // 2^96 = -1
+        let two_to_32 = GoldilocksField::element(1 << 32);
+        assert_eq!(two_to_32.pow(3), minus_one);
+
+        // the generator has order p - 1
+        let generator = GoldilocksField::generator();
+        assert_eq!(generator.pow(GOLDILOCKS_PRIME - 1), GoldilocksField::one());
+        assert_ne!(
+            generator.pow((GOLDILOCKS_PRIME - 1) / 2),
+            GoldilocksField::one()
+        );
+
+        let x = GoldilocksField::element(123456789);
+        assert_eq!(x * x.inverse(), GoldilocksField::one());
+        assert_eq!(x / x, GoldilocksField::one());
+        assert_eq!(
+            GoldilocksField::zero().try_inverse(),
+            Err(AlgebraError::DivisionByZero)
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_arithmetic_matches_u128(a in 0..GOLDILOCKS_PRIME, b in 0..GOLDILOCKS_PRIME) {
+            let p = GOLDILOCKS_PRIME as u128;
+            let (x, y) = (GoldilocksField::element(a), GoldilocksField::element(b));
+            prop_assert_eq!((x * y).value() as u128, a as u128 * b as u128 % p);
+            prop_assert_eq!((x + y).value() as u128, (a as u128 + b as u128) % p);
+            prop_assert_eq!((x - y).value() as u128, (a as u128 + p - b as u128) % p);
+        }
+    }
+}
@@ -1,4 +1,34 @@
-#[allow(dead_code)]
+#[cfg(feature = "ark-compat")]
+pub mod ark_compat;
+pub mod barycentric;
+pub mod big_field;
+pub mod bit_reversal;
+pub mod circle;
+pub mod domain;
+pub mod error;
+pub mod evaluations;
+pub mod extension;
+pub mod field;
 pub mod finite_field;
-#[allow(dead_code)]
+pub mod fp;
+pub mod goldilocks;
+mod macros;
+pub mod matrix;
+pub mod multilinear;
+pub mod ntt;
+pub mod opening;
+pub mod parse;
 pub mod polynomial;
+pub mod product_tree;
+pub mod rational;
+#[cfg(feature = "serde")]
+pub mod serde_compat;
+pub mod slice;
+pub mod small_field;
+pub mod sparse;
+pub mod subgroup;
+pub mod sumcheck;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
+pub mod tower;
+pub mod twiddles;
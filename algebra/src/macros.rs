@@ -0,0 +1,63 @@
+/// `felt!(finite_field, 42)` is `finite_field.element(42)`
+#[macro_export]
+macro_rules! felt {
+    ($finite_field:expr, $value:expr) => {
+        $finite_field.element($value)
+    };
+}
+
+/// `poly!(finite_field, [1, 0, 3])` is the polynomial 1 + 3*x^2 over `finite_field`
+#[macro_export]
+macro_rules! poly {
+    ($finite_field:expr, [$($coefficient:expr),* $(,)?]) => {
+        $crate::polynomial::Polynomial::from_slice(
+            &[$($coefficient),*],
+            ::std::sync::Arc::clone(&$finite_field),
+        )
+    };
+}
+
+/// `points!(finite_field, [(1, 7), (2, 6)])` builds the (x, y) pairs taken by `lagrange_interpolation`
+#[macro_export]
+macro_rules! points {
+    ($finite_field:expr, [$(($x:expr, $y:expr)),* $(,)?]) => {
+        vec![$(($finite_field.element($x), $finite_field.element($y))),*]
+    };
+}
+
+/// `matrix!(finite_field, [[1, 2], [3, 4]])` is the 2x2 matrix with those rows over `finite_field`
+#[macro_export]
+macro_rules! matrix {
+    ($finite_field:expr, [$([$($entry:expr),* $(,)?]),* $(,)?]) => {
+        $crate::matrix::Matrix::from_rows(
+            vec![$(vec![$($finite_field.element($entry)),*]),*],
+            $finite_field.zero(),
+        )
+        .expect("the rows must have the same length")
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::finite_field::FiniteField;
+    use crate::polynomial::Polynomial;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_macros() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+
+        assert_eq!(felt!(finite_field, 42), finite_field.element(42));
+        assert_eq!(
+            poly!(finite_field, [1, 0, 3]),
+            Polynomial::from_slice(&[1, 0, 3], Arc::clone(&finite_field))
+        );
+        assert_eq!(
+            points!(finite_field, [(1, 7), (2, 6)]),
+            vec![
+                (finite_field.element(1), finite_field.element(7)),
+                (finite_field.element(2), finite_field.element(6)),
+            ]
+        );
+    }
+}
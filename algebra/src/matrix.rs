@@ -0,0 +1,550 @@
+//! Dense matrices over any [`Field`], stored row-major.
+//!
+//! MDS matrices of the hash permutations, trace tables and linear systems all live here, so the
+//! arithmetic stays in the field instead of going through a generic numeric array.
+use crate::error::MatrixError;
+use crate::field::Field;
+use crate::finite_field::{FieldElement, FieldSize, FiniteField};
+use crate::polynomial::Polynomial;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Index, IndexMut, Mul, Sub};
+use std::sync::Arc;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matrix<F: Field = FieldElement> {
+    rows: usize,
+    cols: usize,
+    /// row `i` is `entries[i * cols..(i + 1) * cols]`
+    entries: Vec<F>,
+    /// the zero of the entry field, like [`Polynomial`](crate::polynomial::Polynomial) it gives the
+    /// constants of empty matrices
+    zero: F,
+}
+
+impl<F: Field> Matrix<F> {
+    /// every row must have the same length
+    pub fn from_rows(rows: Vec<Vec<F>>, zero: F) -> Result<Self, MatrixError> {
+        let cols = rows.first().map_or(0, Vec::len);
+        let mut entries = Vec::with_capacity(rows.len() * cols);
+        for (i, row) in rows.iter().enumerate() {
+            if row.len() != cols {
+                return Err(MatrixError::RaggedRows {
+                    row: i,
+                    expected: cols,
+                    found: row.len(),
+                });
+            }
+            entries.extend_from_slice(row);
+        }
+        Ok(Self {
+            rows: rows.len(),
+            cols,
+            entries,
+            zero,
+        })
+    }
+
+    pub fn zeros(rows: usize, cols: usize, zero: F) -> Self {
+        Self {
+            rows,
+            cols,
+            entries: vec![zero.clone(); rows * cols],
+            zero,
+        }
+    }
+
+    pub fn identity(n: usize, zero: F) -> Self {
+        let mut identity = Self::zeros(n, n, zero);
+        for i in 0..n {
+            identity[(i, i)] = identity.zero.one();
+        }
+        identity
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// (rows, cols)
+    pub fn dim(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.rows == self.cols
+    }
+
+    pub fn row(&self, i: usize) -> &[F] {
+        &self.entries[i * self.cols..(i + 1) * self.cols]
+    }
+
+    pub fn row_iter(&self) -> impl Iterator<Item = &[F]> {
+        (0..self.rows).map(|i| self.row(i))
+    }
+
+    pub fn column(&self, j: usize) -> Vec<F> {
+        self.row_iter().map(|row| row[j].clone()).collect()
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut entries = Vec::with_capacity(self.entries.len());
+        for j in 0..self.cols {
+            entries.extend(self.row_iter().map(|row| row[j].clone()));
+        }
+        Self {
+            rows: self.cols,
+            cols: self.rows,
+            entries,
+            zero: self.zero.clone(),
+        }
+    }
+
+    /// `M v`, the vector needs one entry per column
+    pub fn mul_vector(&self, vector: &[F]) -> Result<Vec<F>, MatrixError> {
+        if vector.len() != self.cols {
+            return Err(MatrixError::DimensionMismatch {
+                left: self.dim(),
+                right: (vector.len(), 1),
+            });
+        }
+        Ok(self
+            .row_iter()
+            .map(|row| {
+                row.iter()
+                    .zip(vector)
+                    .fold(self.zero.clone(), |acc, (a, b)| acc + a.clone() * b.clone())
+            })
+            .collect())
+    }
+
+    /// `self * rhs`, the columns of `self` must match the rows of `rhs`
+    pub fn checked_mul(&self, rhs: &Self) -> Result<Self, MatrixError> {
+        if self.cols != rhs.rows {
+            return Err(MatrixError::DimensionMismatch {
+                left: self.dim(),
+                right: rhs.dim(),
+            });
+        }
+        let mut product = Self::zeros(self.rows, rhs.cols, self.zero.clone());
+        for i in 0..self.rows {
+            for (k, a) in self.row(i).iter().enumerate() {
+                if a.is_zero() {
+                    continue;
+                }
+                for (j, b) in rhs.row(k).iter().enumerate() {
+                    let entry = &mut product[(i, j)];
+                    *entry = entry.clone() + a.clone() * b.clone();
+                }
+            }
+        }
+        Ok(product)
+    }
+
+    /// Gaussian elimination in `O(n³)`, the determinant of the empty matrix is one
+    pub fn determinant(&self) -> Result<F, MatrixError> {
+        self.check_square()?;
+        let mut reduced = self.clone();
+        let mut determinant = self.zero.one();
+        for col in 0..self.cols {
+            let Some(pivot) = (col..self.rows).find(|&i| !reduced[(i, col)].is_zero()) else {
+                return Ok(self.zero.clone());
+            };
+            if pivot != col {
+                reduced.swap_rows(pivot, col);
+                determinant = -determinant;
+            }
+            let pivot_value = reduced[(col, col)].clone();
+            determinant = determinant * pivot_value.clone();
+            let pivot_inverse = pivot_value.try_inverse().expect("the pivot is non-zero");
+            for i in col + 1..self.rows {
+                let factor = reduced[(i, col)].clone() * pivot_inverse.clone();
+                reduced.sub_scaled_row(i, col, &factor);
+            }
+        }
+        Ok(determinant)
+    }
+
+    /// Gauss-Jordan elimination on `[M | I]`, fails with [`MatrixError::Singular`] when the
+    /// determinant is zero
+    pub fn inverse(&self) -> Result<Self, MatrixError> {
+        self.check_square()?;
+        let mut reduced = self.clone();
+        let mut inverse = Self::identity(self.rows, self.zero.clone());
+        if reduced.reduce(&mut inverse).len() < self.rows {
+            return Err(MatrixError::Singular);
+        }
+        Ok(inverse)
+    }
+
+    /// An `x` with `M x = rhs`, by row reduction of `[M | rhs]`. When the system is underdetermined
+    /// the free variables are set to zero, fails with [`MatrixError::Inconsistent`] without solution.
+    pub fn solve(&self, rhs: &[F]) -> Result<Vec<F>, MatrixError> {
+        if rhs.len() != self.rows {
+            return Err(MatrixError::DimensionMismatch {
+                left: self.dim(),
+                right: (rhs.len(), 1),
+            });
+        }
+        let mut reduced = self.clone();
+        let mut augmented = Self {
+            rows: self.rows,
+            cols: 1,
+            entries: rhs.to_vec(),
+            zero: self.zero.clone(),
+        };
+        let pivots = reduced.reduce(&mut augmented);
+        // the rows below the pivots are zero on the left, so they must be zero on the right
+        if augmented.entries[pivots.len()..]
+            .iter()
+            .any(|x| !x.is_zero())
+        {
+            return Err(MatrixError::Inconsistent);
+        }
+        let mut solution = vec![self.zero.clone(); self.cols];
+        for (row, col) in pivots.into_iter().enumerate() {
+            solution[col] = augmented.entries[row].clone();
+        }
+        Ok(solution)
+    }
+
+    /// the number of linearly independent rows
+    pub fn rank(&self) -> usize {
+        let mut reduced = self.clone();
+        let mut companion = Self::zeros(self.rows, 0, self.zero.clone());
+        reduced.reduce(&mut companion).len()
+    }
+
+    /// Brings `self` to reduced row echelon form, applying the same row operations to `companion`.
+    /// Returns the pivot column of every non-zero row, in order.
+    fn reduce(&mut self, companion: &mut Self) -> Vec<usize> {
+        let mut pivots = vec![];
+        for col in 0..self.cols {
+            let row = pivots.len();
+            let Some(pivot) = (row..self.rows).find(|&i| !self[(i, col)].is_zero()) else {
+                continue;
+            };
+            self.swap_rows(pivot, row);
+            companion.swap_rows(pivot, row);
+
+            let pivot_inverse = self[(row, col)]
+                .try_inverse()
+                .expect("the pivot is non-zero");
+            self.scale_row(row, &pivot_inverse);
+            companion.scale_row(row, &pivot_inverse);
+            for i in (0..self.rows).filter(|&i| i != row) {
+                let factor = self[(i, col)].clone();
+                if !factor.is_zero() {
+                    self.sub_scaled_row(i, row, &factor);
+                    companion.sub_scaled_row(i, row, &factor);
+                }
+            }
+            pivots.push(col);
+            if pivots.len() == self.rows {
+                break;
+            }
+        }
+        pivots
+    }
+
+    fn check_square(&self) -> Result<(), MatrixError> {
+        if !self.is_square() {
+            return Err(MatrixError::NotSquare {
+                rows: self.rows,
+                cols: self.cols,
+            });
+        }
+        Ok(())
+    }
+
+    pub(crate) fn swap_rows(&mut self, a: usize, b: usize) {
+        if a == b {
+            return;
+        }
+        for j in 0..self.cols {
+            self.entries.swap(a * self.cols + j, b * self.cols + j);
+        }
+    }
+
+    pub(crate) fn scale_row(&mut self, i: usize, factor: &F) {
+        for entry in &mut self.entries[i * self.cols..(i + 1) * self.cols] {
+            *entry = entry.clone() * factor.clone();
+        }
+    }
+
+    /// `row[target] -= factor * row[source]`
+    pub(crate) fn sub_scaled_row(&mut self, target: usize, source: usize, factor: &F) {
+        for j in 0..self.cols {
+            let scaled = factor.clone() * self.entries[source * self.cols + j].clone();
+            let entry = &mut self.entries[target * self.cols + j];
+            *entry = entry.clone() - scaled;
+        }
+    }
+
+    fn zip_with(&self, rhs: &Self, f: impl Fn(F, F) -> F) -> Self {
+        assert_eq!(
+            self.dim(),
+            rhs.dim(),
+            "The matrices must have the same dimensions"
+        );
+        Self {
+            rows: self.rows,
+            cols: self.cols,
+            entries: self
+                .entries
+                .iter()
+                .zip(&rhs.entries)
+                .map(|(a, b)| f(a.clone(), b.clone()))
+                .collect(),
+            zero: self.zero.clone(),
+        }
+    }
+}
+
+impl Matrix {
+    /// the entries as integers reduced into `finite_field`, see [`matrix!`](crate::matrix!)
+    pub fn from_values<R: AsRef<[FieldSize]>>(
+        rows: &[R],
+        finite_field: &Arc<FiniteField>,
+    ) -> Result<Self, MatrixError> {
+        Self::from_rows(
+            rows.iter()
+                .map(|row| {
+                    row.as_ref()
+                        .iter()
+                        .map(|v| finite_field.element(*v))
+                        .collect()
+                })
+                .collect(),
+            finite_field.zero(),
+        )
+    }
+}
+
+impl Polynomial {
+    /// Interpolation by solving the Vandermonde system `V c = y` with `V_ij = x_i^j`, in `O(n³)`.
+    /// It's there to compare with [`Polynomial::lagrange_interpolation`] and the NTT of
+    /// [`Polynomial::interpolate`]. Two different values at the same `x` are
+    /// [`MatrixError::Inconsistent`].
+    pub fn interpolate_vandermonde(
+        points: &[(FieldElement, FieldElement)],
+        finite_field: Arc<FiniteField>,
+    ) -> Result<Self, MatrixError> {
+        let rows = points
+            .iter()
+            .map(|(x, _)| {
+                let mut power = finite_field.one();
+                (0..points.len())
+                    .map(|_| {
+                        let entry = power.clone();
+                        power = &power * x;
+                        entry
+                    })
+                    .collect()
+            })
+            .collect();
+        let vandermonde = Matrix::from_rows(rows, finite_field.zero())?;
+        let values: Vec<_> = points.iter().map(|(_, y)| y.clone()).collect();
+        let coefficients = vandermonde.solve(&values)?;
+        Ok(Self::new(coefficients, finite_field))
+    }
+}
+
+impl<F: Field> Index<(usize, usize)> for Matrix<F> {
+    type Output = F;
+
+    fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
+        assert!(i < self.rows && j < self.cols, "Index out of bounds");
+        &self.entries[i * self.cols + j]
+    }
+}
+
+impl<F: Field> IndexMut<(usize, usize)> for Matrix<F> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut Self::Output {
+        assert!(i < self.rows && j < self.cols, "Index out of bounds");
+        &mut self.entries[i * self.cols + j]
+    }
+}
+
+impl<F: Field> Add for &Matrix<F> {
+    type Output = Matrix<F>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, |a, b| a + b)
+    }
+}
+
+impl<F: Field> Sub for &Matrix<F> {
+    type Output = Matrix<F>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, |a, b| a - b)
+    }
+}
+
+impl<F: Field> Mul for &Matrix<F> {
+    type Output = Matrix<F>;
+
+    /// panics when the dimensions don't match, see [`Matrix::checked_mul`]
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.checked_mul(rhs).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl<F: Field> Display for Matrix<F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.row_iter() {
+            let entries: Vec<_> = row.iter().map(ToString::to_string).collect();
+            writeln!(f, "[{}]", entries.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::MatrixError;
+    use crate::finite_field::FiniteField;
+    use crate::matrix;
+    use crate::matrix::Matrix;
+    use crate::points;
+    use crate::polynomial::Polynomial;
+    use crate::small_field::BabyBear;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_arithmetic() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let a = matrix!(finite_field, [[1, 2, 3], [4, 5, 6]]);
+        let b = matrix!(finite_field, [[7, 8], [9, 10], [11, 12]]);
+
+        assert_eq!(a.dim(), (2, 3));
+        assert_eq!(a.transpose().dim(), (3, 2));
+        assert_eq!(a.transpose().transpose(), a);
+        assert_eq!(
+            a.column(1),
+            vec![finite_field.element(2), finite_field.element(5)]
+        );
+
+        assert_eq!(&a * &b, matrix!(finite_field, [[58, 64], [139, 154]]));
+        assert_eq!((&a * &b).transpose(), &b.transpose() * &a.transpose());
+        assert_eq!(&a + &a, &(&(&a + &a) + &a) - &a);
+        assert_eq!(
+            a.mul_vector(&finite_field.elements([1, 0, -1]).collect::<Vec<_>>()),
+            Ok(finite_field.elements([-2, -2]).collect())
+        );
+        assert_eq!(
+            a.checked_mul(&a),
+            Err(MatrixError::DimensionMismatch {
+                left: (2, 3),
+                right: (2, 3)
+            })
+        );
+        assert_eq!(
+            Matrix::from_values(&[vec![1, 2], vec![3]], &finite_field),
+            Err(MatrixError::RaggedRows {
+                row: 1,
+                expected: 2,
+                found: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_determinant_and_inverse() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        // the first pivot is zero, so the rows are swapped
+        let m = matrix!(finite_field, [[0, 2, 1], [1, 1, 0], [3, 0, 4]]);
+        assert_eq!(m.determinant(), Ok(finite_field.element(-11)));
+        let inverse = m.inverse().unwrap();
+        assert_eq!(&m * &inverse, Matrix::identity(3, finite_field.zero()));
+        assert_eq!(&inverse * &m, Matrix::identity(3, finite_field.zero()));
+
+        let singular = matrix!(finite_field, [[1, 2], [2, 4]]);
+        assert_eq!(singular.determinant(), Ok(finite_field.zero()));
+        assert_eq!(singular.inverse(), Err(MatrixError::Singular));
+        assert_eq!(
+            matrix!(finite_field, [[1, 2]]).determinant(),
+            Err(MatrixError::NotSquare { rows: 1, cols: 2 })
+        );
+        assert_eq!(
+            Matrix::identity(0, finite_field.zero()).determinant(),
+            Ok(finite_field.one())
+        );
+
+        let m = Matrix::from_rows(
+            vec![
+                vec![BabyBear::new(2), BabyBear::new(3)],
+                vec![BabyBear::new(5), BabyBear::new(7)],
+            ],
+            BabyBear::ZERO,
+        )
+        .unwrap();
+        assert_eq!(m.determinant(), Ok(-BabyBear::new(1)));
+        assert_eq!(
+            &m * &m.inverse().unwrap(),
+            Matrix::identity(2, BabyBear::ZERO)
+        );
+    }
+
+    #[test]
+    fn test_solve() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let m = matrix!(finite_field, [[0, 2, 1], [1, 1, 0], [3, 0, 4]]);
+        let x: Vec<_> = finite_field.elements([5, -7, 40]).collect();
+        let b = m.mul_vector(&x).unwrap();
+        assert_eq!(m.solve(&b), Ok(x));
+        assert_eq!(m.rank(), 3);
+
+        // x + 2y = 3 twice over, y is free
+        let underdetermined = matrix!(finite_field, [[1, 2], [2, 4]]);
+        assert_eq!(underdetermined.rank(), 1);
+        let b: Vec<_> = finite_field.elements([3, 6]).collect();
+        let solution = underdetermined.solve(&b).unwrap();
+        assert_eq!(solution, finite_field.elements([3, 0]).collect::<Vec<_>>());
+        assert_eq!(underdetermined.mul_vector(&solution), Ok(b));
+        assert_eq!(
+            underdetermined.solve(&finite_field.elements([3, 7]).collect::<Vec<_>>()),
+            Err(MatrixError::Inconsistent)
+        );
+
+        // more equations than unknowns, all consistent
+        let tall = matrix!(finite_field, [[1, 0], [0, 1], [1, 1]]);
+        assert_eq!(
+            tall.solve(&finite_field.elements([4, 5, 9]).collect::<Vec<_>>()),
+            Ok(finite_field.elements([4, 5]).collect())
+        );
+        assert_eq!(
+            tall.solve(&finite_field.elements([4, 5]).collect::<Vec<_>>()),
+            Err(MatrixError::DimensionMismatch {
+                left: (3, 2),
+                right: (2, 1)
+            })
+        );
+    }
+
+    #[test]
+    fn test_interpolate_vandermonde() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let points = points!(finite_field, [(1, 7), (2, 6), (5, 40), (-3, 0)]);
+        assert_eq!(
+            Polynomial::interpolate_vandermonde(&points, Arc::clone(&finite_field)),
+            Ok(Polynomial::lagrange_interpolation(
+                &points,
+                Arc::clone(&finite_field)
+            ))
+        );
+        assert_eq!(
+            Polynomial::interpolate_vandermonde(&[], Arc::clone(&finite_field)),
+            Ok(Polynomial::new(vec![], Arc::clone(&finite_field)))
+        );
+        let conflicting = points!(finite_field, [(1, 7), (1, 6)]);
+        assert_eq!(
+            Polynomial::interpolate_vandermonde(&conflicting, finite_field),
+            Err(MatrixError::Inconsistent)
+        );
+    }
+}
@@ -0,0 +1,225 @@
+//! Multilinear polynomials in `n` variables, stored as their `2^n` evaluations on the boolean hypercube.
+//!
+//! The multilinear extension of a table `T` is the only polynomial of degree at most one in each
+//! variable that agrees with `T` on `{0, 1}^n`:
+//! `f(r) = Σ_{b ∈ {0, 1}^n} T[b] * eq(r, b)` with `eq(r, b) = Π_i (r_i b_i + (1 - r_i)(1 - b_i))`.
+//! The bit `i` of a table index is the value of the variable `x_i`.
+use crate::error::AlgebraError;
+use crate::finite_field::{FieldElement, FiniteField};
+use std::ops::{Add, Sub};
+use std::sync::Arc;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultilinearPolynomial {
+    evaluations: Vec<FieldElement>,
+    num_variables: usize,
+    finite_field: Arc<FiniteField>,
+}
+
+impl MultilinearPolynomial {
+    /// the multilinear extension of a table of `2^n` evaluations
+    pub fn extend(
+        evaluations: Vec<FieldElement>,
+        finite_field: Arc<FiniteField>,
+    ) -> Result<Self, AlgebraError> {
+        if !evaluations.len().is_power_of_two() {
+            return Err(AlgebraError::NotPowerOfTwo(evaluations.len()));
+        }
+        Ok(Self {
+            num_variables: evaluations.len().trailing_zeros() as usize,
+            evaluations,
+            finite_field,
+        })
+    }
+
+    /// `eq(r, x)`, which is 1 at `x = r` and 0 on the rest of the hypercube when `r` is boolean
+    pub fn eq(point: &[FieldElement], finite_field: Arc<FiniteField>) -> Self {
+        let mut evaluations = vec![finite_field.one()];
+        for r in point {
+            let one_minus_r = &finite_field.one() - r;
+            evaluations = evaluations
+                .iter()
+                .map(|e| e * &one_minus_r)
+                .chain(evaluations.iter().map(|e| e * r))
+                .collect();
+        }
+        Self {
+            evaluations,
+            num_variables: point.len(),
+            finite_field,
+        }
+    }
+
+    pub fn num_variables(&self) -> usize {
+        self.num_variables
+    }
+
+    /// the values on the hypercube, indexed by the bits of the variables
+    pub fn evaluations(&self) -> &[FieldElement] {
+        &self.evaluations
+    }
+
+    pub fn finite_field(&self) -> &Arc<FiniteField> {
+        &self.finite_field
+    }
+
+    /// Fixes the variables one at a time, in `O(2^n)` field operations
+    pub fn evaluate(&self, point: &[FieldElement]) -> Result<FieldElement, AlgebraError> {
+        if point.len() != self.num_variables {
+            return Err(AlgebraError::VariableCountMismatch {
+                expected: self.num_variables,
+                found: point.len(),
+            });
+        }
+        let mut polynomial = self.clone();
+        for r in point {
+            polynomial = polynomial.fix_variable(r);
+        }
+        Ok(polynomial.evaluations[0].clone())
+    }
+
+    /// `f(r, x_1, ..., x_(n - 1))` in the remaining variables, the round step of the sumcheck prover.
+    /// Panics if there's no variable left.
+    pub fn fix_variable(&self, r: &FieldElement) -> Self {
+        assert!(self.num_variables > 0, "There is no variable to fix");
+        let evaluations = self
+            .evaluations
+            .chunks(2)
+            .map(|pair| &pair[0] + &(r * &(&pair[1] - &pair[0])))
+            .collect();
+        Self {
+            evaluations,
+            num_variables: self.num_variables - 1,
+            finite_field: Arc::clone(&self.finite_field),
+        }
+    }
+
+    /// `Σ_{b ∈ {0, 1}^n} f(b)`
+    pub fn sum_over_hypercube(&self) -> FieldElement {
+        self.evaluations
+            .iter()
+            .fold(self.finite_field.zero(), |sum, e| &sum + e)
+    }
+}
+
+impl Add for &MultilinearPolynomial {
+    type Output = MultilinearPolynomial;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        assert_eq!(
+            self.num_variables, rhs.num_variables,
+            "The polynomials must have the same number of variables"
+        );
+        MultilinearPolynomial {
+            evaluations: self
+                .evaluations
+                .iter()
+                .zip(&rhs.evaluations)
+                .map(|(a, b)| a + b)
+                .collect(),
+            num_variables: self.num_variables,
+            finite_field: Arc::clone(&self.finite_field),
+        }
+    }
+}
+
+impl Sub for &MultilinearPolynomial {
+    type Output = MultilinearPolynomial;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        assert_eq!(
+            self.num_variables, rhs.num_variables,
+            "The polynomials must have the same number of variables"
+        );
+        MultilinearPolynomial {
+            evaluations: self
+                .evaluations
+                .iter()
+                .zip(&rhs.evaluations)
+                .map(|(a, b)| a - b)
+                .collect(),
+            num_variables: self.num_variables,
+            finite_field: Arc::clone(&self.finite_field),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::AlgebraError;
+    use crate::finite_field::FiniteField;
+    use crate::multilinear::MultilinearPolynomial;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_extend() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let table: Vec<_> = finite_field.elements([3, 1, 4, 1, 5, 9, 2, 6]).collect();
+        let f = MultilinearPolynomial::extend(table.clone(), Arc::clone(&finite_field)).unwrap();
+        assert_eq!(f.num_variables(), 3);
+
+        // agrees with the table on the hypercube
+        for (index, value) in table.iter().enumerate() {
+            let point: Vec<_> = (0..3)
+                .map(|i| finite_field.element(((index >> i) & 1) as i128))
+                .collect();
+            assert_eq!(f.evaluate(&point).unwrap(), *value);
+        }
+
+        // against Σ T[b] eq(r, b) at a point off the hypercube
+        let point: Vec<_> = finite_field.elements([10, 20, 30]).collect();
+        let eq = MultilinearPolynomial::eq(&point, Arc::clone(&finite_field));
+        let expected = table
+            .iter()
+            .zip(eq.evaluations())
+            .fold(finite_field.zero(), |sum, (t, e)| &sum + &(t * e));
+        assert_eq!(f.evaluate(&point).unwrap(), expected);
+        assert_eq!(eq.sum_over_hypercube(), finite_field.one());
+        assert_eq!(f.sum_over_hypercube(), finite_field.element(31));
+
+        assert_eq!(
+            f.evaluate(&point[..2]),
+            Err(AlgebraError::VariableCountMismatch {
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            MultilinearPolynomial::extend(table[..6].to_vec(), finite_field),
+            Err(AlgebraError::NotPowerOfTwo(6))
+        );
+    }
+
+    #[test]
+    fn test_fix_variable() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let f = MultilinearPolynomial::extend(
+            finite_field.elements([7, 0, 11, 42]).collect(),
+            Arc::clone(&finite_field),
+        )
+        .unwrap();
+        let g = MultilinearPolynomial::extend(
+            finite_field.elements([1, 2, 3, 4]).collect(),
+            Arc::clone(&finite_field),
+        )
+        .unwrap();
+        let (r0, r1) = (finite_field.element(13), finite_field.element(55));
+
+        let partial = f.fix_variable(&r0);
+        assert_eq!(partial.num_variables(), 1);
+        assert_eq!(
+            partial.evaluate(std::slice::from_ref(&r1)).unwrap(),
+            f.evaluate(&[r0.clone(), r1.clone()]).unwrap()
+        );
+
+        let point = [r0, r1];
+        assert_eq!(
+            (&f + &g).evaluate(&point).unwrap(),
+            &f.evaluate(&point).unwrap() + &g.evaluate(&point).unwrap()
+        );
+        assert_eq!(
+            (&f - &g).evaluate(&point).unwrap(),
+            &f.evaluate(&point).unwrap() - &g.evaluate(&point).unwrap()
+        );
+    }
+}
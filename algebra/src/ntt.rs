@@ -0,0 +1,325 @@
+//! Radix-2 number-theoretic transform over power-of-two subgroups.
+//!
+//! The forward transform maps the `n` coefficients of a polynomial to its evaluations at
+//! `ω^0, ω^1, ..., ω^(n - 1)` in `O(n log n)`, the inverse transform maps them back.
+use crate::bit_reversal::bit_reverse_permute;
+use crate::domain::EvaluationDomain;
+use crate::error::AlgebraError;
+use crate::finite_field::{FieldElement, FieldSize};
+use crate::polynomial::Polynomial;
+use crate::slice::{mul_slices, scale_slice};
+use crate::subgroup::Subgroup;
+use crate::twiddles::{TwiddleCache, Twiddles};
+use std::cell::RefCell;
+use std::sync::Arc;
+
+/// number of coefficients both factors must exceed before `*` multiplies with NTTs
+pub const FFT_MUL_THRESHOLD: usize = 64;
+
+thread_local! {
+    /// tables of the transforms run by this thread, so repeated products, evaluations and
+    /// interpolations over a domain size compute its roots once
+    static TWIDDLES: RefCell<TwiddleCache> = RefCell::new(TwiddleCache::new());
+}
+
+fn cached_twiddles(subgroup: &Subgroup) -> Arc<Twiddles> {
+    TWIDDLES.with(|cache| cache.borrow_mut().get_for_subgroup(subgroup))
+}
+
+/// the product of two non-empty coefficient lists, see [`Polynomial::mul_fft`]
+pub(crate) fn mul_coefficients_fft(
+    lhs: &[FieldElement],
+    rhs: &[FieldElement],
+) -> Result<Vec<FieldElement>, AlgebraError> {
+    let finite_field = lhs[0].finite_field();
+    let len = lhs.len() + rhs.len() - 1;
+    let size = len.next_power_of_two();
+    let twiddles = TWIDDLES.with(|cache| cache.borrow_mut().get(finite_field, size))?;
+
+    let mut lhs_values = lhs.to_vec();
+    lhs_values.resize(twiddles.size(), finite_field.zero());
+    ntt(&mut lhs_values, &twiddles);
+    let mut rhs_values = rhs.to_vec();
+    rhs_values.resize(twiddles.size(), finite_field.zero());
+    ntt(&mut rhs_values, &twiddles);
+
+    mul_slices(&mut lhs_values, &rhs_values);
+    intt(&mut lhs_values, &twiddles);
+    lhs_values.truncate(len);
+    Ok(lhs_values)
+}
+
+/// coefficients to evaluations at the powers of ω, in natural order
+pub fn ntt(values: &mut [FieldElement], twiddles: &Twiddles) {
+    transform(values, &twiddles.roots);
+}
+
+/// evaluations at the powers of ω to coefficients
+pub fn intt(values: &mut [FieldElement], twiddles: &Twiddles) {
+    transform(values, &twiddles.inverse_roots);
+    if let Some(first) = values.first() {
+        let n_inv = first
+            .finite_field()
+            .element(values.len() as FieldSize)
+            .inverse();
+        scale_slice(values, &n_inv);
+    }
+}
+
+/// iterative Cooley-Tukey: after the bit-reversal permutation, the butterflies of the stage of
+/// length `len` combine two transforms of size `len / 2` with the powers of a `len`-th root of unity
+fn transform(values: &mut [FieldElement], roots: &[FieldElement]) {
+    let n = values.len();
+    assert_eq!(n, roots.len(), "The input length must match the twiddles");
+    bit_reverse_permute(values);
+    let mut len = 2;
+    while len <= n {
+        let step = n / len;
+        for start in (0..n).step_by(len) {
+            for j in 0..len / 2 {
+                let u = values[start + j].clone();
+                let v = &values[start + j + len / 2] * &roots[j * step];
+                values[start + j] = &u + &v;
+                values[start + j + len / 2] = &u - &v;
+            }
+        }
+        len *= 2;
+    }
+}
+
+impl Polynomial {
+    /// Evaluations at every point of the domain. Power-of-two domains use an NTT, any degree is
+    /// accepted since `(g ω^j)^i` only depends on `i mod n` once the offset powers are folded in.
+    pub fn evaluate_on(&self, domain: &EvaluationDomain) -> Vec<FieldElement> {
+        let n = domain.size();
+        if !n.is_power_of_two() {
+            return domain.elements().map(|x| self.evaluate(x)).collect();
+        }
+        let finite_field = domain.finite_field();
+        let mut values = vec![finite_field.zero(); n];
+        let mut shift = finite_field.one();
+        for (i, coefficient) in self.coefficients.iter().enumerate() {
+            values[i % n] += &(coefficient * &shift);
+            shift = &shift * domain.offset();
+        }
+        ntt(&mut values, &cached_twiddles(domain.subgroup()));
+        values
+    }
+
+    /// Low-degree extension: the evaluations on [`EvaluationDomain::lde_domain`], the coset of
+    /// `blowup` times the size of `domain`, with a single NTT of the extended size.
+    pub fn lde(
+        &self,
+        domain: &EvaluationDomain,
+        blowup: usize,
+    ) -> Result<Vec<FieldElement>, AlgebraError> {
+        Ok(self.evaluate_on(&domain.lde_domain(blowup)?))
+    }
+
+    /// The product through NTTs of the next power of two above its length, in `O(n log n)`.
+    /// Fails with [`AlgebraError::InvalidDomainSize`] if the field has no subgroup of that size.
+    pub fn mul_fft(&self, rhs: &Polynomial) -> Result<Polynomial, AlgebraError> {
+        let finite_field = self.finite_field();
+        if self.coefficients.is_empty() || rhs.coefficients.is_empty() {
+            return Ok(Self::new(vec![], Arc::clone(finite_field)));
+        }
+        Ok(Self::new(
+            mul_coefficients_fft(&self.coefficients, &rhs.coefficients)?,
+            Arc::clone(finite_field),
+        ))
+    }
+
+    /// The polynomial of degree below `n` through the evaluations on the domain, with an inverse NTT
+    /// for power-of-two domains and Lagrange interpolation otherwise.
+    pub fn interpolate(
+        domain: &EvaluationDomain,
+        evaluations: &[FieldElement],
+    ) -> Result<Self, AlgebraError> {
+        let n = domain.size();
+        if evaluations.len() != n {
+            return Err(AlgebraError::EvaluationCountMismatch {
+                expected: n,
+                found: evaluations.len(),
+            });
+        }
+        let finite_field = domain.finite_field();
+        if !n.is_power_of_two() {
+            let points: Vec<_> = domain.elements().zip(evaluations.iter().cloned()).collect();
+            return Ok(Self::lagrange_interpolation(
+                &points,
+                Arc::clone(finite_field),
+            ));
+        }
+
+        let mut coefficients = evaluations.to_vec();
+        intt(&mut coefficients, &cached_twiddles(domain.subgroup()));
+        // undo the offset, c_i = c'_i / g^i
+        let offset_inv = domain.offset().inverse();
+        let mut shift = finite_field.one();
+        for coefficient in coefficients.iter_mut() {
+            *coefficient *= &shift;
+            shift = &shift * &offset_inv;
+        }
+        Ok(Self::new(coefficients, Arc::clone(finite_field)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::EvaluationDomain;
+    use crate::error::AlgebraError;
+    use crate::finite_field::FiniteField;
+    use crate::ntt::{cached_twiddles, intt, ntt, TWIDDLES};
+    use crate::poly;
+    use crate::polynomial::Polynomial;
+    use crate::twiddles::Twiddles;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_ntt() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let twiddles = Twiddles::new(&finite_field, 8).unwrap();
+        let f = poly!(finite_field, [4, 8, 15, 16, 23, 42, 1, 2]);
+
+        let mut values = f.coefficients.clone();
+        ntt(&mut values, &twiddles);
+        for (root, value) in twiddles.roots.iter().zip(&values) {
+            assert_eq!(f.evaluate(root.clone()), *value);
+        }
+        intt(&mut values, &twiddles);
+        assert_eq!(values, f.coefficients);
+    }
+
+    #[test]
+    fn test_evaluate_and_interpolate() {
+        // 3 * 2^30 + 1
+        let finite_field = Arc::new(FiniteField::new(3221225473, 5));
+        let mut rng = StdRng::seed_from_u64(6);
+        let domain = EvaluationDomain::coset(&finite_field, 1024, finite_field.element(5)).unwrap();
+
+        let f = Polynomial::new(
+            finite_field.random_elements(1000, &mut rng),
+            Arc::clone(&finite_field),
+        );
+        let evaluations = f.evaluate_on(&domain);
+        for i in [0, 1, 511, 1023] {
+            assert_eq!(evaluations[i], f.evaluate(domain.element(i)));
+        }
+        assert_eq!(Polynomial::interpolate(&domain, &evaluations), Ok(f));
+
+        // above the domain size the evaluations are those of f mod (x^n - g^n)
+        let g = poly!(finite_field, [1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+        let small = EvaluationDomain::coset(&finite_field, 4, finite_field.element(7)).unwrap();
+        for (x, y) in small.elements().zip(g.evaluate_on(&small)) {
+            assert_eq!(g.evaluate(x), y);
+        }
+
+        assert_eq!(
+            Polynomial::interpolate(&small, &evaluations[..3]),
+            Err(AlgebraError::EvaluationCountMismatch {
+                expected: 4,
+                found: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_shared_twiddles() {
+        let finite_field = Arc::new(FiniteField::new(3221225473, 5));
+        let domain = EvaluationDomain::coset(&finite_field, 256, finite_field.element(5)).unwrap();
+        let f = Polynomial::new(
+            finite_field.elements(1..200).collect(),
+            Arc::clone(&finite_field),
+        );
+        let cached = || TWIDDLES.with(|cache| cache.borrow().len());
+
+        let evaluations = f.evaluate_on(&domain);
+        let tables = cached();
+        let twiddles = cached_twiddles(domain.subgroup());
+        assert_eq!(
+            f.evaluate_on(&domain.with_offset(finite_field.element(7)).unwrap())
+                .len(),
+            256
+        );
+        assert_eq!(
+            Polynomial::interpolate(&domain, &evaluations),
+            Ok(f.clone())
+        );
+        // 199 + 199 - 1 coefficients, multiplied on the domain of 512
+        let product = f.mul_fft(&f).unwrap();
+        assert_eq!(product.coefficients.len(), 397);
+        assert_eq!(cached(), tables + 1);
+        assert!(Arc::ptr_eq(&twiddles, &cached_twiddles(domain.subgroup())));
+    }
+
+    #[test]
+    fn test_lde() {
+        let finite_field = Arc::new(FiniteField::new(3221225473, 5));
+        let trace_domain = EvaluationDomain::new(&finite_field, 8).unwrap();
+        let trace: Vec<_> = finite_field.elements([3, 1, 4, 1, 5, 9, 2, 6]).collect();
+        let f = Polynomial::interpolate(&trace_domain, &trace).unwrap();
+
+        let lde = f.lde(&trace_domain, 4).unwrap();
+        let lde_domain = trace_domain.lde_domain(4).unwrap();
+        assert_eq!(lde.len(), 32);
+        assert_eq!(lde_domain.offset(), &finite_field.element(5));
+        for (i, (x, y)) in lde_domain.elements().zip(&lde).enumerate() {
+            assert!(!trace_domain.contains(&x));
+            assert_eq!(f.evaluate(x), *y);
+            // every blowup-th point is on the coset g H of the trace domain
+            if i % 4 == 0 {
+                assert_eq!(*y, f.evaluate(trace_domain.element(i / 4) * 5));
+            }
+        }
+        assert_eq!(Polynomial::interpolate(&lde_domain, &lde), Ok(f.clone()));
+
+        assert_eq!(
+            f.lde(&trace_domain, 1 << 40).err(),
+            Some(AlgebraError::InvalidDomainSize(1 << 43))
+        );
+    }
+
+    #[test]
+    fn test_mul_fft() {
+        let finite_field = Arc::new(FiniteField::new(3221225473, 5));
+        let mut rng = StdRng::seed_from_u64(7);
+        let f = Polynomial::new(
+            finite_field.random_elements(100, &mut rng),
+            Arc::clone(&finite_field),
+        );
+        let g = Polynomial::new(
+            finite_field.random_elements(150, &mut rng),
+            Arc::clone(&finite_field),
+        );
+        let product = f.mul_fft(&g).unwrap();
+        assert_eq!(product, f.mul_schoolbook(&g));
+        assert_eq!(&f * &g, product);
+        assert_eq!(product.coefficients.len(), 249);
+
+        // F_97 has no subgroup of order 256, so the product falls back to schoolbook multiplication
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let f = Polynomial::new(
+            finite_field.random_elements(100, &mut rng),
+            Arc::clone(&finite_field),
+        );
+        assert_eq!(
+            f.mul_fft(&f).err(),
+            Some(AlgebraError::InvalidDomainSize(256))
+        );
+        assert_eq!(&f * &f, f.mul_schoolbook(&f));
+    }
+
+    #[test]
+    fn test_non_power_of_two_domain() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let domain = EvaluationDomain::coset(&finite_field, 6, finite_field.element(5)).unwrap();
+        let f = poly!(finite_field, [1, 2, 3, 4]);
+        let evaluations = f.evaluate_on(&domain);
+        for (x, y) in domain.elements().zip(&evaluations) {
+            assert_eq!(f.evaluate(x), *y);
+        }
+        assert_eq!(Polynomial::interpolate(&domain, &evaluations), Ok(f));
+    }
+}
@@ -0,0 +1,109 @@
+//! Opening a polynomial at several points with a single quotient.
+//!
+//! For points `S` with claimed values, `I` interpolates the values over `S` and
+//! `q = (f - I) / Z_S` where `Z_S` vanishes on `S`. The division is exact only if every claimed
+//! value is correct, so checking `f(r) - I(r) = q(r) * Z_S(r)` at a random `r` checks all of them at once.
+use crate::error::AlgebraError;
+use crate::finite_field::{FieldElement, FiniteField};
+use crate::polynomial::Polynomial;
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+pub struct MultiPointOpening {
+    pub points: Vec<FieldElement>,
+    pub values: Vec<FieldElement>,
+    pub quotient: Polynomial,
+}
+
+impl MultiPointOpening {
+    pub fn new(f: &Polynomial, points: &[FieldElement]) -> Result<Self, AlgebraError> {
+        check_distinct(points)?;
+        let finite_field = f.finite_field();
+        let values: Vec<FieldElement> = points.iter().map(|x| f.evaluate(x.clone())).collect();
+        let interpolant = interpolant(points, &values, finite_field);
+
+        let mut zerofier = Polynomial::new(vec![finite_field.one()], Arc::clone(finite_field));
+        for point in points {
+            zerofier = &zerofier
+                * &Polynomial::new(
+                    vec![(-point.clone()).abs(), finite_field.one()],
+                    Arc::clone(finite_field),
+                );
+        }
+        let (quotient, _) = (f - &interpolant).div_rem(&zerofier)?;
+
+        Ok(Self {
+            points: points.to_vec(),
+            values,
+            quotient,
+        })
+    }
+
+    /// checks the opening against `f(r)` for a point `r` chosen after the opening was sent
+    pub fn verify_at(&self, r: &FieldElement, f_at_r: &FieldElement) -> Result<bool, AlgebraError> {
+        check_distinct(&self.points)?;
+        if self.points.len() != self.values.len() {
+            return Ok(false);
+        }
+        let finite_field = r.finite_field();
+        let interpolant = interpolant(&self.points, &self.values, finite_field);
+
+        let mut zerofier_at_r = finite_field.one();
+        for point in &self.points {
+            zerofier_at_r = &zerofier_at_r * &(r - point);
+        }
+        Ok(f_at_r - &interpolant.evaluate(r.clone())
+            == &self.quotient.evaluate(r.clone()) * &zerofier_at_r)
+    }
+}
+
+fn interpolant(
+    points: &[FieldElement],
+    values: &[FieldElement],
+    finite_field: &Arc<FiniteField>,
+) -> Polynomial {
+    let pairs: Vec<(FieldElement, FieldElement)> =
+        points.iter().cloned().zip(values.iter().cloned()).collect();
+    Polynomial::lagrange_interpolation(&pairs, Arc::clone(finite_field))
+}
+
+fn check_distinct(points: &[FieldElement]) -> Result<(), AlgebraError> {
+    for (i, point) in points.iter().enumerate() {
+        if points[..i].contains(point) {
+            return Err(AlgebraError::DuplicatePoint(point.value()));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::AlgebraError;
+    use crate::finite_field::FiniteField;
+    use crate::opening::MultiPointOpening;
+    use crate::poly;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_multi_point_opening() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let f = poly!(finite_field, [3, 1, 4, 1, 5, 9, 2, 6]);
+        let points: Vec<_> = finite_field.elements([2, 7, 11]).collect();
+        let opening = MultiPointOpening::new(&f, &points).unwrap();
+        assert_eq!(opening.values[1], f.evaluate(finite_field.element(7)));
+
+        let r = finite_field.element(50);
+        let f_at_r = f.evaluate(r.clone());
+        assert_eq!(opening.verify_at(&r, &f_at_r), Ok(true));
+
+        let mut wrong_value = opening.clone();
+        wrong_value.values[2] = &wrong_value.values[2] + &finite_field.one();
+        assert_eq!(wrong_value.verify_at(&r, &f_at_r), Ok(false));
+
+        let duplicate: Vec<_> = finite_field.elements([2, 7, 2]).collect();
+        assert_eq!(
+            MultiPointOpening::new(&f, &duplicate).err(),
+            Some(AlgebraError::DuplicatePoint(2))
+        );
+    }
+}
@@ -0,0 +1,181 @@
+//! Parsing polynomials written like `3*x^2 + 5*x + 1`, the format of their [`Display`](std::fmt::Display).
+//!
+//! A term is a coefficient, a power of `x` or both, the `*` is optional (`3x^2`), whitespace is
+//! ignored and repeated powers are added together. Coefficients are non-negative integers reduced
+//! modulo the field prime, a `-` in front of a term negates it.
+use crate::error::PolynomialError;
+use crate::field::Field;
+use crate::finite_field::{FieldSize, FiniteField};
+use crate::fp::Fp;
+use crate::goldilocks::{GoldilocksElement, GoldilocksField, GOLDILOCKS_PRIME};
+use crate::polynomial::Polynomial;
+use crate::small_field::Field31;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// (exponent, negated, coefficient) for every term
+fn parse_terms(s: &str) -> Result<Vec<(usize, bool, u128)>, PolynomialError> {
+    let mut terms = vec![];
+    let mut current = String::new();
+    let mut negative = false;
+    // the operator before a term and a single sign in front of it, like `1 + -3*x`
+    let mut signs = 0;
+    for c in s.chars().filter(|c| !c.is_whitespace()) {
+        match c {
+            '+' | '-' if current.is_empty() => {
+                signs += 1;
+                if signs > 2 || (signs > 1 && (terms.is_empty() || c == '+')) {
+                    return Err(PolynomialError::InvalidTerm(s.trim().to_string()));
+                }
+                negative ^= c == '-';
+            }
+            '+' | '-' => {
+                terms.push(parse_term(&current, negative)?);
+                current.clear();
+                negative = c == '-';
+                signs = 1;
+            }
+            _ => current.push(c),
+        }
+    }
+    if current.is_empty() {
+        return Err(PolynomialError::InvalidTerm(s.trim().to_string()));
+    }
+    terms.push(parse_term(&current, negative)?);
+    Ok(terms)
+}
+
+fn parse_term(term: &str, negative: bool) -> Result<(usize, bool, u128), PolynomialError> {
+    let invalid = || PolynomialError::InvalidTerm(term.to_string());
+    let Some(position) = term.find('x') else {
+        return Ok((0, negative, term.parse().map_err(|_| invalid())?));
+    };
+    let coefficient = match &term[..position] {
+        "" => 1,
+        prefix => prefix
+            .strip_suffix('*')
+            .unwrap_or(prefix)
+            .parse()
+            .map_err(|_| invalid())?,
+    };
+    let exponent = match &term[position + 1..] {
+        "" => 1,
+        power => power
+            .strip_prefix('^')
+            .ok_or_else(invalid)?
+            .parse()
+            .map_err(|_| invalid())?,
+    };
+    Ok((exponent, negative, coefficient))
+}
+
+/// the terms summed into dense coefficients, `element` maps a coefficient into the field
+fn parse_with<F: Field>(
+    s: &str,
+    zero: F,
+    element: impl Fn(u128) -> F,
+) -> Result<Polynomial<F>, PolynomialError> {
+    let terms = parse_terms(s)?;
+    let len = terms
+        .iter()
+        .map(|(exponent, _, _)| exponent + 1)
+        .max()
+        .unwrap_or(0);
+    let mut coefficients = vec![zero.clone(); len];
+    for (exponent, negative, coefficient) in terms {
+        let value = element(coefficient);
+        let term = if negative { -value } else { value };
+        coefficients[exponent] = coefficients[exponent].clone() + term;
+    }
+    Ok(Polynomial::from_coefficients(coefficients, zero))
+}
+
+impl Polynomial {
+    /// `FromStr` can't carry the field, so polynomials over a [`FiniteField`] are parsed with a
+    /// handle to it, the fields known at compile time implement `FromStr`
+    pub fn parse(s: &str, finite_field: Arc<FiniteField>) -> Result<Self, PolynomialError> {
+        let prime = finite_field.prime as u128;
+        parse_with(s, finite_field.zero(), |coefficient| {
+            finite_field.element((coefficient % prime) as FieldSize)
+        })
+    }
+}
+
+impl<const P: u64> FromStr for Polynomial<Fp<P>> {
+    type Err = PolynomialError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_with(s, Fp::ZERO, |coefficient| {
+            Fp::new((coefficient % P as u128) as u64)
+        })
+    }
+}
+
+impl<const P: u32> FromStr for Polynomial<Field31<P>> {
+    type Err = PolynomialError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_with(s, Field31::ZERO, |coefficient| {
+            Field31::new((coefficient % P as u128) as u32)
+        })
+    }
+}
+
+impl FromStr for Polynomial<GoldilocksElement> {
+    type Err = PolynomialError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_with(s, GoldilocksField::zero(), |coefficient| {
+            GoldilocksField::element((coefficient % GOLDILOCKS_PRIME as u128) as u64)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::PolynomialError;
+    use crate::finite_field::FiniteField;
+    use crate::fp::Fp;
+    use crate::goldilocks::GoldilocksElement;
+    use crate::poly;
+    use crate::polynomial::Polynomial;
+    use crate::small_field::BabyBear;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_parse() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let parse = |s: &str| Polynomial::parse(s, Arc::clone(&finite_field));
+
+        assert_eq!(parse("3*x^2 + 5*x + 1"), Ok(poly!(finite_field, [1, 5, 3])));
+        assert_eq!(
+            parse(" -x^3+2x -  100 "),
+            Ok(poly!(finite_field, [-100, 2, 0, -1]))
+        );
+        assert_eq!(parse("x + x + 1 + -3"), Ok(poly!(finite_field, [-2, 2])));
+        assert_eq!(parse("x^2 - x^2"), Ok(poly!(finite_field, [])));
+
+        // the Display output parses back
+        let f = poly!(finite_field, [4, 0, 96, 13]);
+        assert_eq!(parse(&f.to_string()), Ok(f));
+
+        for invalid in ["", "3 +", "3 + + 2", "x^", "2**x", "y", "x^-1", "--x"] {
+            assert!(
+                matches!(parse(invalid), Err(PolynomialError::InvalidTerm(_))),
+                "{invalid:?} should not parse"
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_str() {
+        let f: Polynomial<BabyBear> = "2*x^2 - 1".parse().unwrap();
+        assert_eq!(f.evaluate(BabyBear::new(3)), BabyBear::new(17));
+
+        let g: Polynomial<Fp<97>> = "100x + 1".parse().unwrap();
+        assert_eq!(g.coefficients, vec![Fp::new(1), Fp::new(3)]);
+
+        let h: Polynomial<GoldilocksElement> = "x^3".parse().unwrap();
+        assert_eq!(h.degree(), 3);
+    }
+}
@@ -1,20 +1,31 @@
+use crate::error::{AlgebraError, PolynomialError};
+use crate::field::{batch_inverse, Field};
 use crate::finite_field::{FieldElement, FieldSize, FiniteField};
+use crate::ntt::FFT_MUL_THRESHOLD;
+use crate::product_tree::ProductTree;
+use rand::Rng;
 use std::fmt::{Display, Formatter};
-use std::ops::{Add, Div, Mul, Neg, Sub};
-use std::rc::Rc;
-
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+use std::sync::Arc;
+
+/// A univariate polynomial over any [`Field`], over the [`FieldElement`]s of a prime field unless
+/// stated otherwise.
+///
+/// Every constructor and operation trims the trailing zero coefficients, so the zero polynomial
+/// has no coefficients and `coefficients.len()` is the degree plus one.
 #[derive(Debug, Clone)]
-struct Polynomial {
-    /// c0 + c1*x^1 + c2*x^2 ...
-    pub coefficients: Vec<FieldElement>,
-    finite_field: Rc<FiniteField>,
+pub struct Polynomial<F: Field = FieldElement> {
+    /// c0 + c1*x^1 + c2*x^2 ..., see [`Polynomial::normalize`] after editing them by hand
+    pub coefficients: Vec<F>,
+    /// the zero of the coefficient field, the constants of every element type are derived from it
+    zero: F,
 }
 
-impl Display for Polynomial {
+impl<F: Field> Display for Polynomial<F> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let mut output = String::new();
         for (i, coeff) in self.coefficients.iter().enumerate() {
-            if *coeff != self.finite_field.zero() {
+            if *coeff != self.zero {
                 if !output.is_empty() {
                     output.push_str(" + ");
                 }
@@ -29,13 +40,16 @@ impl Display for Polynomial {
                 }
             }
         }
+        if output.is_empty() {
+            output.push('0');
+        }
         write!(f, "{}", output)
     }
 }
 
-impl PartialEq for Polynomial {
+impl<F: Field> PartialEq for Polynomial<F> {
     fn eq(&self, other: &Self) -> bool {
-        if self.finite_field != other.finite_field {
+        if self.zero != other.zero {
             return false;
         }
         if self.coefficients.len() != other.coefficients.len() {
@@ -51,50 +65,19 @@ impl PartialEq for Polynomial {
     }
 }
 
-impl Add for Polynomial {
+impl<F: Field> Add for Polynomial<F> {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
-        assert_eq!(
-            self.finite_field.prime, rhs.finite_field.prime,
-            "Elements of different finite field"
-        );
-
-        let shortest_length = self.coefficients.len().min(rhs.coefficients.len());
-
-        // Using iterators and zip
-        let mut result: Vec<_> = self
-            .coefficients
-            .iter()
-            .zip(rhs.coefficients.iter())
-            .take(shortest_length)
-            .map(|(a, b)| a + b)
-            .chain(self.coefficients.clone().into_iter().skip(shortest_length))
-            .chain(rhs.coefficients.clone().into_iter().skip(shortest_length))
-            .collect();
-        let zero = &self.finite_field.zero();
-        while let Some(element) = result.last() {
-            if element == zero {
-                result.pop();
-            } else {
-                break;
-            }
-        }
-        Self {
-            coefficients: result,
-            finite_field: self.finite_field,
-        }
+        &self + &rhs
     }
 }
 
-impl Add for &Polynomial {
-    type Output = Polynomial;
+impl<F: Field> Add for &Polynomial<F> {
+    type Output = Polynomial<F>;
 
     fn add(self, rhs: Self) -> Self::Output {
-        assert_eq!(
-            self.finite_field.prime, rhs.finite_field.prime,
-            "Elements of different finite field"
-        );
+        assert!(self.zero == rhs.zero, "Elements of different finite field");
 
         let shortest_length = self.coefficients.len().min(rhs.coefficients.len());
 
@@ -104,305 +87,637 @@ impl Add for &Polynomial {
             .iter()
             .zip(rhs.coefficients.iter())
             .take(shortest_length)
-            .map(|(a, b)| a + b)
-            .chain(self.coefficients.clone().into_iter().skip(shortest_length))
-            .chain(rhs.coefficients.clone().into_iter().skip(shortest_length))
+            .map(|(a, b)| a.clone() + b.clone())
+            .chain(self.coefficients.iter().skip(shortest_length).cloned())
+            .chain(rhs.coefficients.iter().skip(shortest_length).cloned())
             .collect();
-        let zero = &self.finite_field.zero();
         while let Some(element) = result.last() {
-            if element == zero {
+            if *element == self.zero {
                 result.pop();
             } else {
                 break;
             }
         }
-        Polynomial::new(result, Rc::clone(&self.finite_field))
+        Polynomial::from_coefficients(result, self.zero.clone())
     }
 }
 
-impl Mul for Polynomial {
+impl<F: Field> Mul for Polynomial<F> {
     type Output = Self;
 
     fn mul(self, rhs: Self) -> Self::Output {
-        let mut result_coefficients =
-            vec![self.finite_field.zero(); self.coefficients.len() + rhs.coefficients.len() - 1];
-
-        for (i, coef1) in self.coefficients.iter().enumerate() {
-            for (j, coef2) in rhs.coefficients.iter().enumerate() {
-                result_coefficients[i + j] += coef1 * coef2;
-            }
-        }
-
-        Self {
-            coefficients: result_coefficients,
-            finite_field: self.finite_field,
-        }
+        &self * &rhs
     }
 }
 
-impl Mul for &Polynomial {
-    type Output = Polynomial;
+impl<F: Field> Mul for &Polynomial<F> {
+    type Output = Polynomial<F>;
 
+    /// through [`Field::mul_polynomials`], NTT multiplication for large [`FieldElement`] polynomials
+    /// and schoolbook multiplication otherwise
     fn mul(self, rhs: Self) -> Self::Output {
-        let mut result_coefficients =
-            vec![self.finite_field.zero(); self.coefficients.len() + rhs.coefficients.len() - 1];
-
-        for (i, coef1) in self.coefficients.iter().enumerate() {
-            for (j, coef2) in rhs.coefficients.iter().enumerate() {
-                result_coefficients[i + j] += coef1 * coef2;
-            }
-        }
-
-        Polynomial {
-            coefficients: result_coefficients,
-            finite_field: Rc::clone(&self.finite_field),
+        if self.coefficients.is_empty() || rhs.coefficients.is_empty() {
+            return Polynomial::from_coefficients(vec![], self.zero.clone());
         }
+        Polynomial::from_coefficients(
+            F::mul_polynomials(&self.coefficients, &rhs.coefficients),
+            self.zero.clone(),
+        )
     }
 }
 
-impl Sub for Polynomial {
+impl<F: Field> Sub for Polynomial<F> {
     type Output = Self;
 
     fn sub(self, rhs: Self) -> Self::Output {
-        assert_eq!(
-            self.finite_field.prime, rhs.finite_field.prime,
-            "Elements of different finite field"
-        );
-        self + rhs.neg()
+        &self - &rhs
     }
 }
 
-impl Sub for &Polynomial {
-    type Output = Polynomial;
+impl<F: Field> Sub for &Polynomial<F> {
+    type Output = Polynomial<F>;
 
     fn sub(self, rhs: Self) -> Self::Output {
-        assert_eq!(
-            self.finite_field.prime, rhs.finite_field.prime,
-            "Elements of different finite field"
-        );
-        let res = self + &rhs.neg();
-        res
+        self + &-rhs
     }
 }
 
-impl Div for Polynomial {
-    type Output = (Polynomial, Polynomial);
-    fn div(self, rhs: Polynomial) -> Self::Output {
-        let mut dividend = self.clone();
+impl<F: Field> Div for Polynomial<F> {
+    type Output = (Polynomial<F>, Polynomial<F>);
 
-        let result_len = dividend.coefficients.len() - rhs.coefficients.len() + 1;
+    /// panics on a zero divisor, see [`Polynomial::checked_div`]
+    fn div(self, rhs: Polynomial<F>) -> Self::Output {
+        self.checked_div(&rhs).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
 
-        let mut result_coefficients: Vec<FieldElement> = vec![self.finite_field.zero(); result_len];
+impl<F: Field> Rem for &Polynomial<F> {
+    type Output = Polynomial<F>;
 
-        let leading_coeff_index_rhs = rhs.leading_coefficient_index();
-        let leading_coeff_rhs = rhs.coefficients[leading_coeff_index_rhs].element;
+    /// panics on a zero divisor, see [`Polynomial::checked_div`]
+    fn rem(self, rhs: Self) -> Self::Output {
+        let (_, remainder) = self.checked_div(rhs).unwrap_or_else(|e| panic!("{}", e));
+        remainder
+    }
+}
 
-        while dividend.coefficients.len() >= rhs.coefficients.len() {
-            let leading_coeff_index_dividend = dividend.coefficients.len() - 1;
-            let leading_coeff_dividend =
-                dividend.coefficients[leading_coeff_index_dividend].element;
+impl<F: Field> Rem for Polynomial<F> {
+    type Output = Polynomial<F>;
 
-            let leading_quotient = leading_coeff_dividend / leading_coeff_rhs;
-            let leading_quotient_index = dividend.coefficients.len() - rhs.coefficients.len();
-            result_coefficients[leading_quotient_index].element = leading_quotient;
+    fn rem(self, rhs: Self) -> Self::Output {
+        &self % &rhs
+    }
+}
 
-            let mut temp_quotient = vec![self.finite_field.zero(); leading_quotient_index + 1];
-            temp_quotient[leading_quotient_index].element = leading_quotient;
+impl<F: Field> Neg for Polynomial<F> {
+    type Output = Polynomial<F>;
 
-            let temp_quotient_polynomial =
-                Polynomial::new(temp_quotient, Rc::clone(&self.finite_field));
-            dividend = dividend - (&temp_quotient_polynomial * &rhs);
-        }
+    fn neg(self) -> Self::Output {
+        -&self
+    }
+}
 
-        (
-            Self {
-                // quotient
-                coefficients: result_coefficients,
-                finite_field: self.finite_field,
-            },
-            dividend, // remainder
+impl<F: Field> Neg for &Polynomial<F> {
+    type Output = Polynomial<F>;
+
+    fn neg(self) -> Self::Output {
+        Polynomial::from_coefficients(
+            self.coefficients.iter().map(|x| -x.clone()).collect(),
+            self.zero.clone(),
         )
     }
 }
 
-impl Neg for Polynomial {
-    type Output = Polynomial;
+/// Iterator returned by [`Polynomial::evaluations_iter`].
+pub struct EvaluationsIter {
+    /// p(x), Δp(x), Δ²p(x), ... at the current point
+    differences: Vec<FieldElement>,
+    zero: FieldElement,
+    remaining: usize,
+}
 
-    fn neg(self) -> Self::Output {
-        Self {
-            coefficients: self
-                .coefficients
-                .clone()
-                .into_iter()
-                .map(|x| x.neg())
-                .collect(),
-            finite_field: self.finite_field,
+impl Iterator for EvaluationsIter {
+    type Item = FieldElement;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
         }
+        self.remaining -= 1;
+
+        let value = self
+            .differences
+            .first()
+            .cloned()
+            .unwrap_or_else(|| self.zero.clone());
+        for i in 1..self.differences.len() {
+            self.differences[i - 1] = &self.differences[i - 1] + &self.differences[i];
+        }
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
     }
 }
 
-impl Neg for &Polynomial {
-    type Output = Polynomial;
+impl ExactSizeIterator for EvaluationsIter {}
 
-    fn neg(self) -> Self::Output {
-        Polynomial {
-            coefficients: self
-                .coefficients
-                .clone()
-                .into_iter()
-                .map(|x| x.neg())
-                .collect(),
-            finite_field: Rc::clone(&self.finite_field),
+/// the coefficients of the product of two non-empty coefficient lists, in `O(n m)`
+pub(crate) fn schoolbook<F: Field>(lhs: &[F], rhs: &[F]) -> Vec<F> {
+    let mut result_coefficients = vec![lhs[0].zero(); lhs.len() + rhs.len() - 1];
+
+    for (i, coef1) in lhs.iter().enumerate() {
+        for (j, coef2) in rhs.iter().enumerate() {
+            result_coefficients[i + j] =
+                result_coefficients[i + j].clone() + coef1.clone() * coef2.clone();
         }
     }
+    result_coefficients
 }
 
-impl Polynomial {
-    pub fn new(coefficients: Vec<FieldElement>, finite_field: Rc<FiniteField>) -> Self {
-        Self {
-            coefficients,
-            finite_field,
-        }
+impl<F: Field> Polynomial<F> {
+    /// `zero` is the zero of the field of the coefficients
+    pub fn from_coefficients(coefficients: Vec<F>, zero: F) -> Self {
+        let mut polynomial = Self { coefficients, zero };
+        polynomial.normalize();
+        polynomial
     }
 
-    pub fn from_slice(coefficients: &[FieldSize], finite_field: Rc<FiniteField>) -> Self {
-        let coeff_mod: Vec<FieldElement> = coefficients
-            .iter()
-            .map(|x| finite_field.element(*x))
-            .collect();
-        Self {
-            coefficients: coeff_mod.clone(),
-            finite_field,
+    /// drops the trailing zero coefficients
+    pub fn normalize(&mut self) {
+        while self.coefficients.last() == Some(&self.zero) {
+            self.coefficients.pop();
         }
     }
 
-    pub fn scalar_mul(self, scalar: FieldElement) -> Self {
-        Self {
-            coefficients: self
-                .coefficients
-                .clone()
-                .into_iter()
-                .map(|x| &x * &scalar)
-                .collect(),
-            finite_field: self.finite_field,
+    pub fn is_zero(&self) -> bool {
+        self.coefficients.iter().all(|c| *c == self.zero)
+    }
+
+    /// the coefficient of the highest power, `None` for the zero polynomial
+    pub fn leading_coefficient(&self) -> Option<&F> {
+        self.coefficients.iter().rev().find(|c| **c != self.zero)
+    }
+
+    /// the polynomial divided by its leading coefficient, the zero polynomial stays zero
+    pub fn into_monic(self) -> Self {
+        match self.leading_coefficient() {
+            Some(leading) => {
+                let inverse = leading
+                    .try_inverse()
+                    .expect("the leading coefficient is non-zero");
+                self.scalar_mul(inverse)
+            }
+            None => Self::from_coefficients(vec![], self.zero),
         }
     }
 
-    pub fn scalar_div(self, scalar: FieldElement) -> Self {
-        Self {
-            coefficients: self
-                .coefficients
-                .clone()
+    /// the zero of the coefficient field
+    pub fn field_zero(&self) -> &F {
+        &self.zero
+    }
+
+    pub fn scalar_mul(self, scalar: F) -> Self {
+        Self::from_coefficients(
+            self.coefficients
                 .into_iter()
-                .map(|x| &x / &scalar)
+                .map(|x| x * scalar.clone())
                 .collect(),
-            finite_field: self.finite_field,
+            self.zero,
+        )
+    }
+
+    /// panics on a zero scalar
+    pub fn scalar_div(self, scalar: F) -> Self {
+        let inverse = scalar
+            .try_inverse()
+            .expect("Division by zero is not allowed");
+        self.scalar_mul(inverse)
+    }
+
+    /// `p(c x)`, the coefficient of `x^i` is multiplied by `c^i`
+    pub fn scale(&self, c: &F) -> Self {
+        let mut power = self.zero.one();
+        let coefficients = self
+            .coefficients
+            .iter()
+            .map(|coefficient| {
+                let scaled = coefficient.clone() * power.clone();
+                power = power.clone() * c.clone();
+                scaled
+            })
+            .collect();
+        Self::from_coefficients(coefficients, self.zero.clone())
+    }
+
+    /// `p(x + c)`, with Horner's rule on the linear factor `x + c`
+    pub fn shift(&self, c: &F) -> Self {
+        let mut coefficients: Vec<F> = Vec::with_capacity(self.coefficients.len());
+        for coefficient in self.coefficients.iter().rev() {
+            // acc * (x + c) + coefficient
+            coefficients.insert(0, coefficient.clone());
+            // ascending, so coefficients[i + 1] still holds acc's coefficient of x^i
+            for i in 0..coefficients.len() - 1 {
+                let carry = coefficients[i + 1].clone() * c.clone();
+                coefficients[i] = coefficients[i].clone() + carry;
+            }
         }
+        Self::from_coefficients(coefficients, self.zero.clone())
     }
 
+    /// the formal derivative `Σ i a_i x^(i - 1)`, the factors `i` are taken modulo the characteristic
+    pub fn derivative(&self) -> Self {
+        let mut factor = self.zero.clone();
+        let coefficients = self
+            .coefficients
+            .iter()
+            .skip(1)
+            .map(|coefficient| {
+                factor = factor.clone() + self.zero.one();
+                coefficient.clone() * factor.clone()
+            })
+            .collect();
+        Self::from_coefficients(coefficients, self.zero.clone())
+    }
+
+    /// the highest power with a non-zero coefficient, -1 for the zero polynomial
     pub fn degree(&self) -> FieldSize {
-        if self.coefficients.is_empty() {
-            return -1;
+        self.coefficients
+            .iter()
+            .rposition(|c| *c != self.zero)
+            .map_or(-1, |index| index as FieldSize)
+    }
+
+    /// long division, returns (quotient, remainder)
+    pub fn div_rem(&self, rhs: &Self) -> Result<(Self, Self), AlgebraError> {
+        if self.zero != rhs.zero {
+            return Err(AlgebraError::DifferentFields);
         }
-        for (index, s) in self.coefficients.iter().rev().enumerate() {
-            if *s != self.finite_field.zero() {
-                let coeff_len = self.coefficients.len();
-                return (coeff_len - index) as FieldSize;
-            }
+
+        let zero = self.zero.clone();
+        let leading_coeff_index_rhs = rhs.leading_coefficient_index();
+        let leading_coeff_rhs = match rhs.coefficients.get(leading_coeff_index_rhs) {
+            Some(coeff) if *coeff != zero => coeff,
+            _ => return Err(AlgebraError::DivisionByZero),
+        };
+        let leading_coeff_rhs_inv = leading_coeff_rhs.try_inverse()?;
+        let rhs_len = leading_coeff_index_rhs + 1;
+
+        let mut dividend = self.clone();
+        while dividend.coefficients.last() == Some(&zero) {
+            dividend.coefficients.pop();
         }
-        0
+        if dividend.coefficients.len() < rhs_len {
+            return Ok((Self::from_coefficients(Vec::new(), zero), dividend));
+        }
+
+        let result_len = dividend.coefficients.len() - rhs_len + 1;
+        let mut result_coefficients: Vec<F> = vec![zero.clone(); result_len];
+
+        while dividend.coefficients.len() >= rhs_len {
+            let leading_coeff_index_dividend = dividend.coefficients.len() - 1;
+            let leading_coeff_dividend = &dividend.coefficients[leading_coeff_index_dividend];
+
+            let leading_quotient = leading_coeff_dividend.clone() * leading_coeff_rhs_inv.clone();
+            let leading_quotient_index = dividend.coefficients.len() - rhs_len;
+            result_coefficients[leading_quotient_index] = leading_quotient.clone();
+
+            let mut temp_quotient = vec![zero.clone(); leading_quotient_index + 1];
+            temp_quotient[leading_quotient_index] = leading_quotient;
+
+            let temp_quotient_polynomial = Self::from_coefficients(temp_quotient, zero.clone());
+            dividend = dividend - (&temp_quotient_polynomial * rhs);
+        }
+
+        Ok((
+            Self::from_coefficients(result_coefficients, zero), // quotient
+            dividend,                                           // remainder
+        ))
+    }
+
+    /// (quotient, remainder), failing on a zero divisor or polynomials over different fields
+    pub fn checked_div(&self, rhs: &Self) -> Result<(Self, Self), PolynomialError> {
+        self.div_rem(rhs).map_err(|error| match error {
+            AlgebraError::DivisionByZero => PolynomialError::DivisionByZero,
+            _ => PolynomialError::DifferentFields,
+        })
+    }
+
+    /// The quotient of a division expected to be exact, like a constraint polynomial by its
+    /// zerofier. A non-zero remainder is reported as [`PolynomialError::NonZeroRemainder`].
+    pub fn exact_div(&self, rhs: &Self) -> Result<Self, PolynomialError> {
+        let (quotient, remainder) = self.checked_div(rhs)?;
+        match remainder.coefficients.iter().rposition(|c| *c != self.zero) {
+            Some(degree) => Err(PolynomialError::NonZeroRemainder(degree)),
+            None => Ok(quotient),
+        }
+    }
+
+    /// the `O(n m)` product, whatever the size, the reference the NTT multiplication is tested against
+    pub fn mul_schoolbook(&self, rhs: &Self) -> Self {
+        if self.coefficients.is_empty() || rhs.coefficients.is_empty() {
+            return Self::from_coefficients(vec![], self.zero.clone());
+        }
+        Self::from_coefficients(
+            schoolbook(&self.coefficients, &rhs.coefficients),
+            self.zero.clone(),
+        )
     }
 
     fn leading_coefficient_index(&self) -> usize {
         for i in (0..self.coefficients.len()).rev() {
-            if self.coefficients[i] != self.finite_field.zero() {
+            if self.coefficients[i] != self.zero {
                 return i;
             }
         }
         0
     }
 
-    pub fn evaluate(&self, x: FieldElement) -> FieldElement {
-        if self.coefficients.is_empty() {
-            return self.finite_field.zero();
-        }
-        let mut result = self.finite_field.zero();
-        let mut pow = self.finite_field.one();
-        for element in &self.coefficients {
-            result += element * &pow;
-            pow = &pow * &x;
-        }
-        result
+    /// Horner's rule
+    pub fn evaluate(&self, x: F) -> F {
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(self.zero.clone(), |acc, coefficient| {
+                acc * x.clone() + coefficient.clone()
+            })
     }
 
-    pub fn lagrange_interpolation(
-        points: &[(FieldElement, FieldElement)],
-        finite_field: Rc<FiniteField>,
-    ) -> Self {
-        let x = Polynomial::from_slice(&[0, 1], Rc::clone(&finite_field));
-        let mut acc = Polynomial::new(Vec::new(), Rc::clone(&finite_field));
-        for (i, i_element) in points.iter().enumerate() {
+    /// Lagrange interpolation of the points in `O(n²)` over any field, `zero` gives the field when
+    /// there are no points
+    pub fn from_points(points: &[(F, F)], zero: F) -> Self {
+        let one = zero.one();
+        let x = Self::from_coefficients(vec![zero.clone(), one.clone()], zero.clone());
+        // Π_{j != i} (x_i - x_j) for every i, inverted together
+        let denominators: Vec<F> = points
+            .iter()
+            .enumerate()
+            .map(|(i, (x_i, _))| {
+                let mut denominator = one.clone();
+                for (j, (x_j, _)) in points.iter().enumerate() {
+                    if i != j {
+                        denominator = denominator * (x_i.clone() - x_j.clone());
+                    }
+                }
+                denominator
+            })
+            .collect();
+        let inverses = batch_inverse(&denominators).expect("Cannot compute the inverse of zero");
+
+        let mut acc = Self::from_coefficients(Vec::new(), zero.clone());
+        for (i, ((_, y_i), inverse)) in points.iter().zip(&inverses).enumerate() {
             let mut value =
-                Polynomial::new([i_element.clone().1].to_vec(), Rc::clone(&finite_field));
-            for (j, j_element) in points.iter().enumerate() {
+                Self::from_coefficients(vec![y_i.clone() * inverse.clone()], zero.clone());
+            for (j, (x_j, _)) in points.iter().enumerate() {
                 if i == j {
                     continue;
                 }
-                let basis = (&x
-                    - &Polynomial::new([j_element.0.clone()].to_vec(), Rc::clone(&finite_field)))
-                    * Polynomial::new(
-                        [(i_element.0.clone() - j_element.0.clone()).inverse()].to_vec(),
-                        Rc::clone(&finite_field),
-                    );
-                value = value * basis;
+                value = value * (&x - &Self::from_coefficients(vec![x_j.clone()], zero.clone()));
             }
             acc = acc + value;
         }
         acc
     }
+}
 
-    pub fn evaluate_on_domain(&self, domain: FieldSize) -> Vec<FieldElement> {
-        let mut result = Vec::with_capacity(domain as usize);
-        for i in 0..domain {
-            result.push(self.evaluate(self.finite_field.element(i)));
+impl Polynomial {
+    pub fn new(coefficients: Vec<FieldElement>, finite_field: Arc<FiniteField>) -> Self {
+        Self::from_coefficients(coefficients, finite_field.zero())
+    }
+
+    pub fn from_slice(coefficients: &[FieldSize], finite_field: Arc<FiniteField>) -> Self {
+        let coeff_mod: Vec<FieldElement> = coefficients
+            .iter()
+            .map(|x| finite_field.element(*x))
+            .collect();
+        Self::new(coeff_mod, finite_field)
+    }
+
+    pub fn finite_field(&self) -> &Arc<FiniteField> {
+        self.zero.finite_field()
+    }
+
+    /// `degree + 1` uniformly random coefficients, the leading one may be zero
+    pub fn random<R: Rng + ?Sized>(
+        degree: usize,
+        rng: &mut R,
+        finite_field: Arc<FiniteField>,
+    ) -> Self {
+        Self::new(finite_field.random_elements(degree + 1, rng), finite_field)
+    }
+
+    /// The number of coefficients as 8 little-endian bytes, then every coefficient as
+    /// [`FieldElement::to_bytes_le`]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let width = self.finite_field().byte_len();
+        let mut bytes = Vec::with_capacity(8 + self.coefficients.len() * width);
+        bytes.extend_from_slice(&(self.coefficients.len() as u64).to_le_bytes());
+        for coefficient in &self.coefficients {
+            bytes.extend_from_slice(&coefficient.to_bytes_le());
         }
-        result
+        bytes
     }
 
-    pub fn zerofier_domain(domain: FieldSize, finite_field: Rc<FiniteField>) -> Self {
-        let x = Polynomial::new(
-            vec![finite_field.zero(), finite_field.one()],
-            Rc::clone(&finite_field),
-        );
-        let mut acc = Polynomial::new(vec![finite_field.one()], Rc::clone(&finite_field));
-        for i in 0..domain {
-            acc = &acc
-                * &(&x - &Polynomial::new(vec![finite_field.element(i)], Rc::clone(&finite_field)));
+    /// Reads the encoding of [`Polynomial::to_bytes`], the length must match exactly and every
+    /// coefficient must be canonical
+    pub fn from_bytes(bytes: &[u8], finite_field: &Arc<FiniteField>) -> Result<Self, AlgebraError> {
+        let Some((prefix, body)) = bytes.split_first_chunk::<8>() else {
+            return Err(AlgebraError::InvalidByteLength {
+                expected: 8,
+                found: bytes.len(),
+            });
+        };
+        let width = finite_field.byte_len();
+        let expected = usize::try_from(u64::from_le_bytes(*prefix))
+            .ok()
+            .and_then(|len| len.checked_mul(width))
+            .and_then(|len| len.checked_add(8))
+            .unwrap_or(usize::MAX);
+        if bytes.len() != expected {
+            return Err(AlgebraError::InvalidByteLength {
+                expected,
+                found: bytes.len(),
+            });
         }
-        acc
+        let coefficients = body
+            .chunks_exact(width)
+            .map(|chunk| FieldElement::from_bytes(chunk, finite_field))
+            .collect::<Result<_, _>>()?;
+        Ok(Self::new(coefficients, Arc::clone(finite_field)))
+    }
+
+    /// highest degree first, coefficients above p/2 are rendered as negative numbers
+    pub fn to_latex(&self) -> String {
+        let prime = self.finite_field().prime;
+        let mut output = String::new();
+        for (i, coeff) in self.coefficients.iter().enumerate().rev() {
+            let value = coeff.value();
+            if value == 0 {
+                continue;
+            }
+            let (negative, magnitude) = if value > prime / 2 {
+                (true, prime - value)
+            } else {
+                (false, value)
+            };
+            match (output.is_empty(), negative) {
+                (true, true) => output.push('-'),
+                (true, false) => {}
+                (false, true) => output.push_str(" - "),
+                (false, false) => output.push_str(" + "),
+            }
+            if magnitude != 1 || i == 0 {
+                output.push_str(&magnitude.to_string());
+            }
+            match i {
+                0 => {}
+                1 => output.push('x'),
+                _ => output.push_str(&format!("x^{{{}}}", i)),
+            }
+        }
+        if output.is_empty() {
+            output.push('0');
+        }
+        output
+    }
+
+    /// The polynomial of degree below `n` through the points. Large point sets go through
+    /// [`ProductTree::interpolate`] in `O(n log² n)`.
+    pub fn lagrange_interpolation(
+        points: &[(FieldElement, FieldElement)],
+        finite_field: Arc<FiniteField>,
+    ) -> Self {
+        if points.len() > FFT_MUL_THRESHOLD {
+            let (xs, ys): (Vec<_>, Vec<_>) = points.iter().cloned().unzip();
+            return ProductTree::new(&xs, finite_field).interpolate(&ys);
+        }
+        Self::from_points(points, finite_field.zero())
+    }
+
+    pub fn evaluate_on_domain(&self, domain: FieldSize) -> Vec<FieldElement> {
+        self.evaluations_iter(domain).collect()
+    }
+
+    /// Lazily evaluates the polynomial at 0, 1, ..., domain - 1.
+    ///
+    /// Uses forward differences: after evaluating the first `coefficients.len()` points, every
+    /// following evaluation only costs `coefficients.len()` additions.
+    pub fn evaluations_iter(&self, domain: FieldSize) -> EvaluationsIter {
+        let mut differences: Vec<FieldElement> = (0..self.coefficients.len())
+            .map(|i| self.evaluate(self.finite_field().element(i as FieldSize)))
+            .collect();
+        for k in 1..differences.len() {
+            for i in (k..differences.len()).rev() {
+                differences[i] = &differences[i] - &differences[i - 1];
+            }
+        }
+
+        EvaluationsIter {
+            differences,
+            zero: self.zero.clone(),
+            remaining: domain.max(0) as usize,
+        }
+    }
+
+    /// `x (x - 1) ... (x - (domain - 1))`, through a [`ProductTree`]
+    pub fn zerofier_domain(domain: FieldSize, finite_field: Arc<FiniteField>) -> Self {
+        let points: Vec<_> = (0..domain).map(|i| finite_field.element(i)).collect();
+        Self::zerofier_points(&points, finite_field)
+    }
+
+    /// `x^n - g^n`, which vanishes on the coset `g H` of the subgroup of order `n`
+    pub fn zerofier_coset(subgroup_order: usize, offset: &FieldElement) -> Self {
+        let finite_field = offset.finite_field();
+        let mut coefficients = vec![finite_field.zero(); subgroup_order + 1];
+        coefficients[0] = (-offset.pow(subgroup_order as u128)).abs();
+        coefficients[subgroup_order] = finite_field.one();
+        Self::new(coefficients, Arc::clone(finite_field))
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::error::{AlgebraError, PolynomialError};
     use crate::finite_field::FiniteField;
+    use crate::goldilocks::GoldilocksField;
+    use crate::poly;
     use crate::polynomial::Polynomial;
-    use std::rc::Rc;
+    use crate::small_field::BabyBear;
+    use crate::test_utils::{nonzero_polynomial, polynomial};
+    use proptest::prelude::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::sync::Arc;
 
     #[test]
     fn new_polynomial() {
-        let finite_field = Rc::new(FiniteField::new(97, 1));
-        let polynomial = Polynomial::from_slice(&[2, 7, 1, 4, 0, 5], Rc::clone(&finite_field));
-        assert_eq!(polynomial.degree(), 6);
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let polynomial = Polynomial::from_slice(&[2, 7, 1, 4, 0, 5], Arc::clone(&finite_field));
+        assert_eq!(polynomial.degree(), 5);
+
+        // trailing zeros are trimmed whatever the construction path
+        let polynomial = Polynomial::from_slice(&[2, 7, 1, 4, 0, 0], Arc::clone(&finite_field));
+        assert_eq!(polynomial.degree(), 3);
+        assert_eq!(polynomial, poly!(finite_field, [2, 7, 1, 4]));
+        assert_eq!(
+            polynomial.clone().scalar_mul(finite_field.zero()).degree(),
+            -1
+        );
+    }
+
+    #[test]
+    fn test_normalization_helpers() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let mut polynomial = poly!(finite_field, [4, 0, 2]);
+        assert_eq!(
+            polynomial.leading_coefficient(),
+            Some(&finite_field.element(2))
+        );
+        assert_eq!(
+            polynomial.clone().into_monic(),
+            poly!(finite_field, [2, 0, 1])
+        );
+        assert!(!polynomial.is_zero());
+
+        polynomial.coefficients.push(finite_field.zero());
+        assert_eq!(polynomial.degree(), 2);
+        assert_ne!(polynomial, poly!(finite_field, [4, 0, 2]));
+        polynomial.normalize();
+        assert_eq!(polynomial, poly!(finite_field, [4, 0, 2]));
+
+        let zero = &polynomial - &polynomial;
+        assert!(zero.is_zero());
+        assert!(zero.coefficients.is_empty());
+        assert_eq!(zero.degree(), -1);
+        assert_eq!(zero.leading_coefficient(), None);
+        assert_eq!(zero.clone().into_monic(), zero);
+    }
 
-        let polynomial = Polynomial::from_slice(&[2, 7, 1, 4, 0, 0], Rc::clone(&finite_field));
-        assert_eq!(polynomial.degree(), 4);
+    #[test]
+    fn test_display() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        assert_eq!(poly!(finite_field, [5, 0, 3]).to_string(), "5 + 3*x^2");
+        assert_eq!(poly!(finite_field, [0, 0]).to_string(), "0");
+        assert_eq!(poly!(finite_field, []).to_string(), "0");
+    }
+
+    #[test]
+    fn test_to_latex() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        assert_eq!(poly!(finite_field, [1, 5, 3]).to_latex(), "3x^{2} + 5x + 1");
+        assert_eq!(
+            poly!(finite_field, [96, 0, 1, 0, 0, 0, 0, 0, 0, 0, 95]).to_latex(),
+            "-2x^{10} + x^{2} - 1"
+        );
+        assert_eq!(poly!(finite_field, [0, 96]).to_latex(), "-x");
+        assert_eq!(poly!(finite_field, [0, 0]).to_latex(), "0");
     }
 
     #[test]
     fn test_evaluate() {
-        let finite_field = Rc::new(FiniteField::new(13, 1));
-        let polynomial = Polynomial::from_slice(&[5, 2, 3], Rc::clone(&finite_field));
+        let finite_field = Arc::new(FiniteField::new(13, 2));
+        let polynomial = Polynomial::from_slice(&[5, 2, 3], Arc::clone(&finite_field));
         assert_eq!(
             polynomial.evaluate(finite_field.element(3)),
             finite_field.element(12)
@@ -413,72 +728,144 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_evaluations_iter() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let polynomial = poly!(finite_field, [5, 2, 3, 0, 11]);
+
+        let evaluations = polynomial.evaluations_iter(200);
+        assert_eq!(evaluations.len(), 200);
+        for (i, evaluation) in evaluations.enumerate() {
+            assert_eq!(
+                evaluation,
+                polynomial.evaluate(finite_field.element(i as i128))
+            );
+        }
+
+        let zero = poly!(finite_field, []);
+        assert_eq!(zero.evaluate_on_domain(3), vec![finite_field.zero(); 3]);
+    }
+
     #[test]
     fn test_add_polynomial() {
-        let finite_field = Rc::new(FiniteField::new(97, 1));
-        let polynomial1 = Polynomial::from_slice(&[2, 7, 1, 4, 0, 5], Rc::clone(&finite_field));
-        let polynomial2 = Polynomial::from_slice(&[1, 3, 4, 2, 7, 8], Rc::clone(&finite_field));
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let polynomial1 = Polynomial::from_slice(&[2, 7, 1, 4, 0, 5], Arc::clone(&finite_field));
+        let polynomial2 = Polynomial::from_slice(&[1, 3, 4, 2, 7, 8], Arc::clone(&finite_field));
 
-        let expected = Polynomial::from_slice(&[3, 10, 5, 6, 7, 13], Rc::clone(&finite_field));
+        let expected = Polynomial::from_slice(&[3, 10, 5, 6, 7, 13], Arc::clone(&finite_field));
         assert_eq!(polynomial1 + polynomial2, expected);
     }
 
     #[test]
     fn test_sub_polynomial() {
-        let finite_field = Rc::new(FiniteField::new(97, 1));
-        let polynomial1 = Polynomial::from_slice(&[2, 7, 7, 4, 8, 9], Rc::clone(&finite_field));
-        let polynomial2 = Polynomial::from_slice(&[1, 3, 4, 2, 3, 8], Rc::clone(&finite_field));
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let polynomial1 = Polynomial::from_slice(&[2, 7, 7, 4, 8, 9], Arc::clone(&finite_field));
+        let polynomial2 = Polynomial::from_slice(&[1, 3, 4, 2, 3, 8], Arc::clone(&finite_field));
 
-        let expected = Polynomial::from_slice(&[1, 4, 3, 2, 5, 1], Rc::clone(&finite_field));
+        let expected = Polynomial::from_slice(&[1, 4, 3, 2, 5, 1], Arc::clone(&finite_field));
         assert_eq!(polynomial1 - polynomial2, expected);
 
-        let polynomial1 = Polynomial::from_slice(&[2, 7, 7, 4, 8, 9], Rc::clone(&finite_field));
-        let polynomial2 = Polynomial::from_slice(&[1, 3, 4, 2], Rc::clone(&finite_field));
-        let expected = Polynomial::from_slice(&[1, 4, 3, 2, 8, 9], Rc::clone(&finite_field));
+        let polynomial1 = Polynomial::from_slice(&[2, 7, 7, 4, 8, 9], Arc::clone(&finite_field));
+        let polynomial2 = Polynomial::from_slice(&[1, 3, 4, 2], Arc::clone(&finite_field));
+        let expected = Polynomial::from_slice(&[1, 4, 3, 2, 8, 9], Arc::clone(&finite_field));
         assert_eq!(polynomial1 - polynomial2, expected);
 
-        let polynomial1 = Polynomial::from_slice(&[2, 7, 7], Rc::clone(&finite_field));
-        let polynomial2 = Polynomial::from_slice(&[1, 3, 7], Rc::clone(&finite_field));
-        let expected = Polynomial::from_slice(&[1, 4], Rc::clone(&finite_field));
+        let polynomial1 = Polynomial::from_slice(&[2, 7, 7], Arc::clone(&finite_field));
+        let polynomial2 = Polynomial::from_slice(&[1, 3, 7], Arc::clone(&finite_field));
+        let expected = Polynomial::from_slice(&[1, 4], Arc::clone(&finite_field));
         assert_eq!(polynomial1 - polynomial2, expected);
     }
 
     #[test]
     fn test_leading_coefficient_index() {
-        let finite_field = Rc::new(FiniteField::new(97, 1));
-        let polynomial1 = Polynomial::from_slice(&[2, 7, 7], Rc::clone(&finite_field));
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let polynomial1 = Polynomial::from_slice(&[2, 7, 7], Arc::clone(&finite_field));
         let leading_coeff_index = polynomial1.leading_coefficient_index();
         assert_eq!(leading_coeff_index, 2);
     }
 
     #[test]
     fn test_mul_polynomial() {
-        let finite_field = Rc::new(FiniteField::new(97, 1));
-        let polynomial1 = Polynomial::from_slice(&[2, 7, 7], Rc::clone(&finite_field));
-        let polynomial2 = Polynomial::from_slice(&[3, 5], Rc::clone(&finite_field));
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let polynomial1 = Polynomial::from_slice(&[2, 7, 7], Arc::clone(&finite_field));
+        let polynomial2 = Polynomial::from_slice(&[3, 5], Arc::clone(&finite_field));
 
         assert_eq!(
             &polynomial1 * &polynomial2,
-            Polynomial::from_slice(&[6, 31, 56, 35], Rc::clone(&finite_field))
+            Polynomial::from_slice(&[6, 31, 56, 35], Arc::clone(&finite_field))
         );
     }
 
     #[test]
     fn test_div_polynomial() {
-        let finite_field = Rc::new(FiniteField::new(97, 1));
-        let polynomial1 = Polynomial::from_slice(&[74, 79, 81, 1], Rc::clone(&finite_field));
-        let polynomial2 = Polynomial::from_slice(&[94, 1], Rc::clone(&finite_field));
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let polynomial1 = Polynomial::from_slice(&[74, 79, 81, 1], Arc::clone(&finite_field));
+        let polynomial2 = Polynomial::from_slice(&[94, 1], Arc::clone(&finite_field));
 
         let division = polynomial1 / polynomial2;
         assert_eq!(
             division.0,
-            Polynomial::from_slice(&[40, 84, 1], Rc::clone(&finite_field))
+            Polynomial::from_slice(&[40, 84, 1], Arc::clone(&finite_field))
+        );
+    }
+
+    #[test]
+    fn test_div_rem_errors() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let polynomial1 = Polynomial::from_slice(&[74, 79, 81, 1], Arc::clone(&finite_field));
+        let zero = Polynomial::from_slice(&[0, 0], Arc::clone(&finite_field));
+        assert_eq!(
+            polynomial1.div_rem(&zero),
+            Err(AlgebraError::DivisionByZero)
+        );
+
+        let other_field = Arc::new(FiniteField::new(13, 2));
+        let polynomial2 = Polynomial::from_slice(&[1, 1], other_field);
+        assert_eq!(
+            polynomial1.div_rem(&polynomial2),
+            Err(AlgebraError::DifferentFields)
+        );
+
+        // dividend of lower degree than the divisor
+        let polynomial2 = Polynomial::from_slice(&[3, 1], Arc::clone(&finite_field));
+        let (quotient, remainder) = polynomial2.div_rem(&polynomial1).unwrap();
+        assert!(quotient.coefficients.is_empty());
+        assert_eq!(remainder, polynomial2);
+    }
+
+    #[test]
+    fn test_checked_div_and_rem() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let zerofier = Polynomial::zerofier_domain(3, Arc::clone(&finite_field));
+        let quotient = poly!(finite_field, [4, 0, 2]);
+        let f = &quotient * &zerofier;
+
+        assert_eq!(f.exact_div(&zerofier), Ok(quotient.clone()));
+        let g = &f + &poly!(finite_field, [0, 1]);
+        assert_eq!(
+            g.exact_div(&zerofier),
+            Err(PolynomialError::NonZeroRemainder(1))
         );
+        assert_eq!(&g % &zerofier, poly!(finite_field, [0, 1]));
+        assert_eq!(
+            g.checked_div(&zerofier).map(|(q, _)| q),
+            Ok(quotient.clone())
+        );
+
+        // a dividend of lower degree is its own remainder
+        let (q, r) = zerofier.checked_div(&f).unwrap();
+        assert!(q.coefficients.is_empty());
+        assert_eq!(r, zerofier);
+
+        let zero = poly!(finite_field, [0]);
+        assert_eq!(f.checked_div(&zero), Err(PolynomialError::DivisionByZero));
+        let other = Polynomial::from_slice(&[1, 1], Arc::new(FiniteField::new(13, 2)));
+        assert_eq!(f.exact_div(&other), Err(PolynomialError::DifferentFields));
     }
 
     #[test]
     fn lagrange_interpolation() {
-        let finite_field = Rc::new(FiniteField::new(97, 1));
+        let finite_field = Arc::new(FiniteField::new(97, 5));
 
         let points = [
             (finite_field.element(1), finite_field.element(7)),
@@ -486,8 +873,8 @@ mod tests {
             (finite_field.element(3), finite_field.element(8)),
         ];
 
-        let p = Polynomial::lagrange_interpolation(&points, Rc::clone(&finite_field));
-        let expected = Polynomial::from_slice(&[11, 43, 50], Rc::clone(&finite_field));
+        let p = Polynomial::lagrange_interpolation(&points, Arc::clone(&finite_field));
+        let expected = Polynomial::from_slice(&[11, 43, 50], Arc::clone(&finite_field));
         assert_eq!(&p, &expected);
 
         assert_eq!(p.evaluate(points[0].0.clone()), points[0].1);
@@ -501,15 +888,184 @@ mod tests {
         assert_eq!(evaluation_on_domain[3], points[2].1);
     }
 
+    #[test]
+    fn test_scale_and_shift() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let p = poly!(finite_field, [3, 0, 5, 1, 8]);
+        let c = finite_field.element(7);
+
+        assert_eq!(p.scale(&c), poly!(finite_field, [3, 0, 245, 343, 19208]));
+        // (x + 1)^2 = x^2 + 2x + 1
+        assert_eq!(
+            poly!(finite_field, [0, 0, 1]).shift(&finite_field.one()),
+            poly!(finite_field, [1, 2, 1])
+        );
+        for x in finite_field.elements([0, 1, 11, 96]) {
+            assert_eq!(p.scale(&c).evaluate(x.clone()), p.evaluate(&x * &c));
+            assert_eq!(p.shift(&c).evaluate(x.clone()), p.evaluate(&x + &c));
+        }
+        assert_eq!(p.shift(&c).shift(&-&c), p);
+    }
+
+    #[test]
+    fn test_random() {
+        let finite_field = Arc::new(FiniteField::new(3221225473, 5));
+        let mut rng = StdRng::seed_from_u64(12);
+        let f = Polynomial::random(15, &mut rng, Arc::clone(&finite_field));
+        let g = Polynomial::random(15, &mut rng, Arc::clone(&finite_field));
+        assert_eq!(f.coefficients.len(), 16);
+        assert_ne!(f, g);
+        assert!(f
+            .coefficients
+            .iter()
+            .all(|c| c.value() < finite_field.prime));
+
+        let mut rng = StdRng::seed_from_u64(12);
+        assert_eq!(Polynomial::random(15, &mut rng, finite_field), f);
+    }
+
+    #[test]
+    fn test_derivative() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let p = poly!(finite_field, [3, 0, 5, 1, 8]);
+        assert_eq!(p.derivative(), poly!(finite_field, [0, 10, 3, 32]));
+        assert!(poly!(finite_field, [42])
+            .derivative()
+            .coefficients
+            .is_empty());
+
+        // x^97 has derivative 97 x^96 = 0 in characteristic 97
+        let mut coefficients = vec![finite_field.zero(); 98];
+        coefficients[97] = finite_field.one();
+        let frobenius = Polynomial::new(coefficients, Arc::clone(&finite_field));
+        assert!(frobenius.derivative().is_zero());
+
+        // product rule
+        let q = poly!(finite_field, [1, 2, 3]);
+        assert_eq!(
+            (&p * &q).derivative(),
+            &(&p.derivative() * &q) + &(&p * &q.derivative())
+        );
+    }
+
+    #[test]
+    fn test_generic_fields() {
+        let coefficients: Vec<_> = [3, 1, 4, 1, 5].map(BabyBear::new).to_vec();
+        let f = Polynomial::from_coefficients(coefficients, BabyBear::ZERO);
+        let g =
+            Polynomial::from_coefficients(vec![BabyBear::new(2), BabyBear::ONE], BabyBear::ZERO);
+        let (quotient, remainder) = (&f * &g).div_rem(&g).unwrap();
+        assert_eq!(quotient, f);
+        assert!(remainder.coefficients.is_empty());
+        assert_eq!(
+            f.evaluate(BabyBear::new(2)),
+            BabyBear::new(3 + 2 + 16 + 8 + 80)
+        );
+
+        let points: Vec<_> = [(1, 10), (2, 20), (5, 7)]
+            .map(|(x, y)| (GoldilocksField::element(x), GoldilocksField::element(y)))
+            .to_vec();
+        let h = Polynomial::from_points(&points, GoldilocksField::zero());
+        for (x, y) in &points {
+            assert_eq!(h.evaluate(*x), *y);
+        }
+        assert_eq!(h.derivative().degree(), 1);
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        let finite_field = Arc::new(FiniteField::new(65537, 3));
+        let f = poly!(finite_field, [1, 0, -1, 256]);
+        let bytes = f.to_bytes();
+        assert_eq!(bytes.len(), 8 + 4 * 3);
+        assert_eq!(bytes[..8], 4u64.to_le_bytes());
+        assert_eq!(bytes[8..11], [1, 0, 0]);
+        assert_eq!(Polynomial::from_bytes(&bytes, &finite_field), Ok(f));
+
+        let zero = Polynomial::new(vec![], Arc::clone(&finite_field));
+        assert_eq!(zero.to_bytes(), vec![0; 8]);
+        assert_eq!(
+            Polynomial::from_bytes(&zero.to_bytes(), &finite_field),
+            Ok(zero)
+        );
+
+        assert_eq!(
+            Polynomial::from_bytes(&bytes[..19], &finite_field),
+            Err(AlgebraError::InvalidByteLength {
+                expected: 20,
+                found: 19
+            })
+        );
+        assert!(Polynomial::from_bytes(&bytes[..5], &finite_field).is_err());
+        assert!(Polynomial::from_bytes(&[0xff; 8], &finite_field).is_err());
+        // 65537 doesn't fit the field
+        let mut non_canonical = bytes.clone();
+        non_canonical[8..11].copy_from_slice(&[1, 0, 1]);
+        assert_eq!(
+            Polynomial::from_bytes(&non_canonical, &finite_field),
+            Err(AlgebraError::NotCanonical(65537))
+        );
+    }
+
+    #[test]
+    fn test_zerofier_points_and_coset() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let points: Vec<_> = finite_field.elements([3, 14, 15, 92]).collect();
+        let zerofier = Polynomial::zerofier_points(&points, Arc::clone(&finite_field));
+        assert_eq!(zerofier.coefficients.len(), 5);
+        for x in &points {
+            assert_eq!(zerofier.evaluate(x.clone()), finite_field.zero());
+        }
+
+        // 33 has order 8 in F_97, so the coset 5 <33> is the set of roots of x^8 - 5^8
+        let offset = finite_field.element(5);
+        let coset: Vec<_> = (0..8)
+            .map(|i| &offset * &finite_field.element(33).pow(i))
+            .collect();
+        let closed_form = Polynomial::zerofier_coset(8, &offset);
+        assert_eq!(
+            closed_form,
+            Polynomial::zerofier_points(&coset, Arc::clone(&finite_field))
+        );
+        assert_ne!(
+            closed_form.evaluate(finite_field.element(2)),
+            finite_field.zero()
+        );
+    }
+
     #[test]
     fn test_zerofier_polynomial() {
-        let finite_field = Rc::new(FiniteField::new(97, 1));
+        let finite_field = Arc::new(FiniteField::new(97, 5));
 
         let domain = 7;
-        let p = Polynomial::zerofier_domain(domain, Rc::clone(&finite_field));
+        let p = Polynomial::zerofier_domain(domain, Arc::clone(&finite_field));
 
         for i in 0..domain {
             assert_eq!(p.evaluate(finite_field.element(i)), finite_field.zero());
         }
     }
+
+    proptest! {
+        #[test]
+        fn test_add_sub_round_trip(
+            (a, b) in {
+                let finite_field = Arc::new(FiniteField::new(97, 5));
+                (polynomial(Arc::clone(&finite_field), 8), polynomial(finite_field, 8))
+            }
+        ) {
+            prop_assert_eq!(&(&a + &b) - &b, a);
+        }
+
+        #[test]
+        fn test_mul_div_round_trip(
+            (a, b) in {
+                let finite_field = Arc::new(FiniteField::new(97, 5));
+                (nonzero_polynomial(Arc::clone(&finite_field), 8), nonzero_polynomial(finite_field, 4))
+            }
+        ) {
+            let (quotient, remainder) = &a * &b / b;
+            prop_assert_eq!(quotient, a);
+            prop_assert!(remainder.coefficients.is_empty());
+        }
+    }
 }
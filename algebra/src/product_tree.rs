@@ -0,0 +1,317 @@
+//! Subproduct tree of the linear factors `x - x_i` of a set of points.
+//!
+//! The leaves are the factors, every node is the product of its two children and the root is the
+//! zerofier of all the points. With NTT multiplication the whole tree costs `O(n log² n)` instead
+//! of the `O(n²)` of multiplying the factors one at a time. Multipoint evaluation and fast
+//! interpolation walk the same tree.
+use crate::finite_field::{FieldElement, FiniteField};
+use crate::ntt::FFT_MUL_THRESHOLD;
+use crate::polynomial::Polynomial;
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+pub struct ProductTree {
+    /// `layers[0]` holds the leaves, every layer pairs the nodes of the one below, an odd node is
+    /// carried up unchanged
+    layers: Vec<Vec<Polynomial>>,
+    finite_field: Arc<FiniteField>,
+}
+
+impl ProductTree {
+    pub fn new(points: &[FieldElement], finite_field: Arc<FiniteField>) -> Self {
+        let leaves: Vec<_> = points
+            .iter()
+            .map(|x| Polynomial::new(vec![-x, finite_field.one()], Arc::clone(&finite_field)))
+            .collect();
+        let mut layers = vec![leaves];
+        while layers.last().is_some_and(|layer| layer.len() > 1) {
+            let next = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => left * right,
+                    [single] => single.clone(),
+                    _ => unreachable!(),
+                })
+                .collect();
+            layers.push(next);
+        }
+        Self {
+            layers,
+            finite_field,
+        }
+    }
+
+    /// the number of points
+    pub fn len(&self) -> usize {
+        self.layers[0].len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.layers[0].is_empty()
+    }
+
+    /// `Π (x - x_i)`, the constant 1 without points
+    pub fn root(&self) -> Polynomial {
+        match self.layers.last().and_then(|layer| layer.first()) {
+            Some(root) => root.clone(),
+            None => Polynomial::new(
+                vec![self.finite_field.one()],
+                Arc::clone(&self.finite_field),
+            ),
+        }
+    }
+
+    pub fn layers(&self) -> &[Vec<Polynomial>] {
+        &self.layers
+    }
+
+    /// `f(x_i)` for every point: `f` is reduced modulo the root, then every node's remainder is
+    /// reduced modulo its children, down to the constants at the leaves
+    pub fn evaluate(&self, f: &Polynomial) -> Vec<FieldElement> {
+        let Some(top) = self.layers.last() else {
+            return vec![];
+        };
+        let mut remainders: Vec<_> = top.iter().map(|node| rem_monic(f, node)).collect();
+        for layer in self.layers.iter().rev().skip(1) {
+            remainders = layer
+                .iter()
+                .enumerate()
+                .map(|(j, node)| rem_monic(&remainders[j / 2], node))
+                .collect();
+        }
+        remainders
+            .into_iter()
+            .map(|r| {
+                r.coefficients
+                    .into_iter()
+                    .next()
+                    .unwrap_or_else(|| self.finite_field.zero())
+            })
+            .collect()
+    }
+}
+
+impl ProductTree {
+    /// The polynomial of degree below `n` through `(x_i, y_i)`. With `Z` the root,
+    /// `f = Σ y_i / Z'(x_i) * Z / (x - x_i)`: the weights come from one multipoint evaluation of `Z'`
+    /// and the sum is assembled up the tree as `f_node = f_left * right + f_right * left`.
+    pub fn interpolate(&self, values: &[FieldElement]) -> Polynomial {
+        assert_eq!(
+            values.len(),
+            self.len(),
+            "There must be one value per point"
+        );
+        let derivatives = self.evaluate(&self.root().derivative());
+        let inverses = FieldElement::batch_inverse(&derivatives);
+        let mut combined: Vec<_> = values
+            .iter()
+            .zip(&inverses)
+            .map(|(y, inverse)| Polynomial::new(vec![y * inverse], Arc::clone(&self.finite_field)))
+            .collect();
+        for layer in &self.layers[..self.layers.len() - 1] {
+            combined = combined
+                .chunks(2)
+                .zip(layer.chunks(2))
+                .map(|(values, nodes)| match (values, nodes) {
+                    ([left, right], [left_node, right_node]) => {
+                        &(left * right_node) + &(right * left_node)
+                    }
+                    ([single], _) => single.clone(),
+                    _ => unreachable!(),
+                })
+                .collect();
+        }
+        combined
+            .pop()
+            .unwrap_or_else(|| Polynomial::new(vec![], Arc::clone(&self.finite_field)))
+    }
+}
+
+/// `f mod d` for a monic `d` without trailing zeros, the nodes of the tree
+pub(crate) fn rem_monic(f: &Polynomial, d: &Polynomial) -> Polynomial {
+    let finite_field = f.finite_field();
+    let zero = finite_field.zero();
+    let mut remainder = f.coefficients.clone();
+    while remainder.last() == Some(&zero) {
+        remainder.pop();
+    }
+    let m = d.coefficients.len();
+    if remainder.len() < m {
+        return Polynomial::new(remainder, Arc::clone(finite_field));
+    }
+    let quotient_len = remainder.len() - m + 1;
+    if quotient_len.min(m) <= FFT_MUL_THRESHOLD {
+        for i in (0..quotient_len).rev() {
+            let factor = remainder[i + m - 1].clone();
+            for (j, coefficient) in d.coefficients.iter().enumerate() {
+                remainder[i + j] -= &(&factor * coefficient);
+            }
+        }
+        remainder.truncate(m - 1);
+        return Polynomial::new(remainder, Arc::clone(finite_field));
+    }
+
+    // rev(f) = rev(q) rev(d) mod x^k and rev(d) has constant term 1, so rev(q) = rev(f) / rev(d)
+    let reversed = |coefficients: &[FieldElement]| {
+        Polynomial::new(
+            coefficients.iter().rev().cloned().collect(),
+            Arc::clone(finite_field),
+        )
+    };
+    let inverse = inverse_series(&reversed(&d.coefficients), quotient_len);
+    let mut quotient = truncated(&(&reversed(&remainder) * &inverse), quotient_len);
+    quotient.coefficients.resize(quotient_len, zero);
+    quotient.coefficients.reverse();
+    truncated(&(f - &(&quotient * d)), m - 1)
+}
+
+/// `1 / h mod x^len` for `h(0) = 1`, by Newton iteration `g <- g (2 - h g)`, doubling the precision
+fn inverse_series(h: &Polynomial, len: usize) -> Polynomial {
+    let finite_field = h.finite_field();
+    let two = Polynomial::new(vec![finite_field.element(2)], Arc::clone(finite_field));
+    let mut g = Polynomial::new(vec![finite_field.one()], Arc::clone(finite_field));
+    let mut precision = 1;
+    while precision < len {
+        precision = (2 * precision).min(len);
+        let hg = truncated(&(&truncated(h, precision) * &g), precision);
+        g = truncated(&(&g * &(&two - &hg)), precision);
+    }
+    g
+}
+
+fn truncated(p: &Polynomial, len: usize) -> Polynomial {
+    Polynomial::new(
+        p.coefficients.iter().take(len).cloned().collect(),
+        Arc::clone(p.finite_field()),
+    )
+}
+
+impl Polynomial {
+    /// `Π (x - x_i)` over arbitrary points, like the rows of boundary constraints, through a
+    /// [`ProductTree`]. For a coset `g H` the closed form of [`Polynomial::zerofier_coset`] is
+    /// cheaper still.
+    pub fn zerofier_points(points: &[FieldElement], finite_field: Arc<FiniteField>) -> Self {
+        ProductTree::new(points, finite_field).root()
+    }
+
+    /// The evaluations at every point. Beyond a handful of points the polynomial is reduced down a
+    /// [`ProductTree`], `O(n log² n)` with NTT-friendly fields instead of a Horner pass per point.
+    pub fn evaluate_batch(&self, points: &[FieldElement]) -> Vec<FieldElement> {
+        if points.len() <= FFT_MUL_THRESHOLD {
+            return points.iter().map(|x| self.evaluate(x.clone())).collect();
+        }
+        ProductTree::new(points, Arc::clone(self.finite_field())).evaluate(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::finite_field::FiniteField;
+    use crate::polynomial::Polynomial;
+    use crate::product_tree::{rem_monic, ProductTree};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_product_tree() {
+        // 3 * 2^30 + 1, so the inner products go through NTTs
+        let finite_field = Arc::new(FiniteField::new(3221225473, 5));
+        let mut rng = StdRng::seed_from_u64(8);
+        let points = finite_field.random_elements(301, &mut rng);
+
+        let tree = ProductTree::new(&points, Arc::clone(&finite_field));
+        assert_eq!(tree.len(), 301);
+        assert_eq!(tree.layers().len(), 10);
+        let zerofier = tree.root();
+        assert_eq!(zerofier.coefficients.len(), 302);
+        assert_eq!(zerofier.coefficients[301], finite_field.one());
+        for x in &points {
+            assert_eq!(zerofier.evaluate(x.clone()), finite_field.zero());
+        }
+
+        let mut expected = Polynomial::new(vec![finite_field.one()], Arc::clone(&finite_field));
+        for x in &points {
+            expected = expected.mul_schoolbook(&Polynomial::new(
+                vec![-x, finite_field.one()],
+                Arc::clone(&finite_field),
+            ));
+        }
+        assert_eq!(zerofier, expected);
+
+        let empty = ProductTree::new(&[], Arc::clone(&finite_field));
+        assert!(empty.is_empty());
+        assert_eq!(
+            empty.root(),
+            Polynomial::new(vec![finite_field.one()], finite_field)
+        );
+    }
+
+    #[test]
+    fn test_evaluate_batch() {
+        let finite_field = Arc::new(FiniteField::new(3221225473, 5));
+        let mut rng = StdRng::seed_from_u64(9);
+        let f = Polynomial::new(
+            finite_field.random_elements(500, &mut rng),
+            Arc::clone(&finite_field),
+        );
+        let points = finite_field.random_elements(300, &mut rng);
+        let expected: Vec<_> = points.iter().map(|x| f.evaluate(x.clone())).collect();
+        assert_eq!(f.evaluate_batch(&points), expected);
+        assert_eq!(f.evaluate_batch(&points[..10]), expected[..10]);
+
+        // without large subgroups the products fall back to schoolbook multiplication
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let f = Polynomial::new(
+            finite_field.random_elements(150, &mut rng),
+            Arc::clone(&finite_field),
+        );
+        let points = finite_field.random_elements(90, &mut rng);
+        let expected: Vec<_> = points.iter().map(|x| f.evaluate(x.clone())).collect();
+        assert_eq!(f.evaluate_batch(&points), expected);
+    }
+
+    #[test]
+    fn test_interpolate() {
+        let finite_field = Arc::new(FiniteField::new(3221225473, 5));
+        let mut rng = StdRng::seed_from_u64(11);
+        let f = Polynomial::new(
+            finite_field.random_elements(200, &mut rng),
+            Arc::clone(&finite_field),
+        );
+        let xs = finite_field.random_elements(200, &mut rng);
+        let ys = f.evaluate_batch(&xs);
+        let tree = ProductTree::new(&xs, Arc::clone(&finite_field));
+        assert_eq!(tree.interpolate(&ys), f);
+
+        // lagrange_interpolation switches to the tree above the threshold
+        let points: Vec<_> = xs.into_iter().zip(ys).collect();
+        assert_eq!(
+            Polynomial::lagrange_interpolation(&points, Arc::clone(&finite_field)),
+            f
+        );
+        let small = &points[..5];
+        let g = Polynomial::lagrange_interpolation(small, Arc::clone(&finite_field));
+        for (x, y) in small {
+            assert_eq!(g.evaluate(x.clone()), *y);
+        }
+    }
+
+    #[test]
+    fn test_rem_monic() {
+        let finite_field = Arc::new(FiniteField::new(3221225473, 5));
+        let mut rng = StdRng::seed_from_u64(10);
+        let f = Polynomial::new(
+            finite_field.random_elements(400, &mut rng),
+            Arc::clone(&finite_field),
+        );
+        let mut coefficients = finite_field.random_elements(150, &mut rng);
+        coefficients.push(finite_field.one());
+        let d = Polynomial::new(coefficients, Arc::clone(&finite_field));
+
+        let (_, expected) = f.div_rem(&d).unwrap();
+        assert_eq!(rem_monic(&f, &d), expected);
+    }
+}
@@ -0,0 +1,230 @@
+//! Quotients of polynomials kept as a numerator/denominator pair.
+//!
+//! The arithmetic doesn't divide anything: constraint quotients can be added, scaled and combined
+//! symbolically, then [`RationalFunction::simplify`] cancels the common factors and
+//! [`RationalFunction::to_polynomial`] performs the one division that's left.
+use crate::error::PolynomialError;
+use crate::finite_field::FieldElement;
+use crate::polynomial::Polynomial;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+pub struct RationalFunction {
+    numerator: Polynomial,
+    denominator: Polynomial,
+}
+
+impl RationalFunction {
+    pub fn new(numerator: Polynomial, denominator: Polynomial) -> Result<Self, PolynomialError> {
+        if numerator.finite_field() != denominator.finite_field() {
+            return Err(PolynomialError::DifferentFields);
+        }
+        if denominator.is_zero() {
+            return Err(PolynomialError::DivisionByZero);
+        }
+        Ok(Self {
+            numerator,
+            denominator,
+        })
+    }
+
+    pub fn numerator(&self) -> &Polynomial {
+        &self.numerator
+    }
+
+    pub fn denominator(&self) -> &Polynomial {
+        &self.denominator
+    }
+
+    /// `1 / self`, undefined for a zero numerator
+    pub fn inverse(&self) -> Result<Self, PolynomialError> {
+        Self::new(self.denominator.clone(), self.numerator.clone())
+    }
+
+    /// Divides both sides by their gcd and makes the denominator monic, so equal functions have
+    /// the same representation
+    pub fn simplify(&self) -> Self {
+        let finite_field = self.numerator.finite_field();
+        if self.numerator.is_zero() {
+            return Self {
+                numerator: Polynomial::new(vec![], Arc::clone(finite_field)),
+                denominator: Polynomial::new(vec![finite_field.one()], Arc::clone(finite_field)),
+            };
+        }
+        let gcd = gcd(&self.numerator, &self.denominator);
+        let numerator = self
+            .numerator
+            .exact_div(&gcd)
+            .expect("the gcd divides both sides");
+        let denominator = self
+            .denominator
+            .exact_div(&gcd)
+            .expect("the gcd divides both sides");
+        let leading_inv = denominator
+            .leading_coefficient()
+            .expect("the denominator is non-zero")
+            .inverse();
+        Self {
+            numerator: numerator.scalar_mul(leading_inv),
+            denominator: denominator.into_monic(),
+        }
+    }
+
+    /// the quotient when the denominator divides the numerator
+    pub fn to_polynomial(&self) -> Result<Polynomial, PolynomialError> {
+        self.numerator.exact_div(&self.denominator)
+    }
+
+    /// fails where the denominator vanishes
+    pub fn evaluate(&self, x: &FieldElement) -> Result<FieldElement, PolynomialError> {
+        let denominator = self.denominator.evaluate(x.clone());
+        if denominator == x.finite_field().zero() {
+            return Err(PolynomialError::DivisionByZero);
+        }
+        Ok(&self.numerator.evaluate(x.clone()) / &denominator)
+    }
+}
+
+/// the monic gcd, by Euclid's algorithm
+fn gcd(a: &Polynomial, b: &Polynomial) -> Polynomial {
+    let (mut a, mut b) = (a.clone(), b.clone());
+    while !b.is_zero() {
+        let remainder = &a % &b;
+        a = b;
+        b = remainder;
+    }
+    a.into_monic()
+}
+
+/// `a / b = c / d` exactly when `a d = c b`
+impl PartialEq for RationalFunction {
+    fn eq(&self, other: &Self) -> bool {
+        &self.numerator * &other.denominator == &other.numerator * &self.denominator
+    }
+}
+
+impl From<Polynomial> for RationalFunction {
+    fn from(polynomial: Polynomial) -> Self {
+        let one = Polynomial::new(
+            vec![polynomial.finite_field().one()],
+            Arc::clone(polynomial.finite_field()),
+        );
+        Self {
+            numerator: polynomial,
+            denominator: one,
+        }
+    }
+}
+
+impl Add for &RationalFunction {
+    type Output = RationalFunction;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        RationalFunction {
+            numerator: &(&self.numerator * &rhs.denominator)
+                + &(&rhs.numerator * &self.denominator),
+            denominator: &self.denominator * &rhs.denominator,
+        }
+    }
+}
+
+impl Sub for &RationalFunction {
+    type Output = RationalFunction;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + &-rhs
+    }
+}
+
+impl Mul for &RationalFunction {
+    type Output = RationalFunction;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        RationalFunction {
+            numerator: &self.numerator * &rhs.numerator,
+            denominator: &self.denominator * &rhs.denominator,
+        }
+    }
+}
+
+impl Div for &RationalFunction {
+    type Output = RationalFunction;
+
+    /// panics when `rhs` is zero, see [`RationalFunction::inverse`]
+    fn div(self, rhs: Self) -> Self::Output {
+        let inverse = rhs.inverse().unwrap_or_else(|e| panic!("{}", e));
+        self.mul(&inverse)
+    }
+}
+
+impl Neg for &RationalFunction {
+    type Output = RationalFunction;
+
+    fn neg(self) -> Self::Output {
+        RationalFunction {
+            numerator: -&self.numerator,
+            denominator: self.denominator.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::PolynomialError;
+    use crate::finite_field::FiniteField;
+    use crate::poly;
+    use crate::polynomial::Polynomial;
+    use crate::rational::RationalFunction;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_simplify() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        // (x - 1)(x - 2) / 3(x - 1)
+        let numerator = poly!(finite_field, [2, -3, 1]);
+        let denominator = poly!(finite_field, [-3, 3]);
+        let f = RationalFunction::new(numerator, denominator).unwrap();
+        let simplified = f.simplify();
+        assert_eq!(simplified.denominator(), &poly!(finite_field, [1]));
+        assert_eq!(
+            simplified.numerator(),
+            &poly!(finite_field, [-2, 1]).scalar_mul(finite_field.element(3).inverse())
+        );
+        assert_eq!(simplified, f);
+        assert_eq!(f.to_polynomial(), Ok(simplified.numerator().clone()));
+
+        let x = finite_field.element(10);
+        assert_eq!(f.evaluate(&x), simplified.evaluate(&x));
+        assert_eq!(
+            f.evaluate(&finite_field.one()),
+            Err(PolynomialError::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let f = RationalFunction::new(poly!(finite_field, [1, 2]), poly!(finite_field, [3, 0, 1]))
+            .unwrap();
+        let g =
+            RationalFunction::new(poly!(finite_field, [5]), poly!(finite_field, [-1, 1])).unwrap();
+        let x = finite_field.element(7);
+        let (fx, gx) = (f.evaluate(&x).unwrap(), g.evaluate(&x).unwrap());
+
+        assert_eq!((&f + &g).evaluate(&x), Ok(&fx + &gx));
+        assert_eq!((&f - &g).evaluate(&x), Ok(&fx - &gx));
+        assert_eq!((&f * &g).evaluate(&x), Ok(&fx * &gx));
+        assert_eq!((&f / &g).evaluate(&x), Ok(&fx / &gx));
+        assert_eq!(&(&f + &g) - &g, f);
+        assert_eq!((&(&f + &g) - &g).simplify(), f.simplify());
+
+        let zero = RationalFunction::from(Polynomial::new(vec![], Arc::clone(&finite_field)));
+        assert_eq!(zero.inverse().err(), Some(PolynomialError::DivisionByZero));
+        assert_eq!(
+            (&f * &zero).simplify().denominator(),
+            &poly!(finite_field, [1])
+        );
+        assert_eq!(f.to_polynomial(), Err(PolynomialError::NonZeroRemainder(1)));
+    }
+}
@@ -0,0 +1,274 @@
+//! `serde` support, enabled with the `serde` feature.
+//!
+//! A [`FiniteField`] is stored as its prime and generator and checked again when it's read back.
+//! A [`FieldElement`] is stored as its modulus and canonical value. Reading one back needs the field
+//! it belongs to, so elements are deserialized through [`FieldElementSeed`], which checks the modulus
+//! and the canonical range and attaches the element to the given `Arc`. A [`Polynomial`] is stored as
+//! its coefficients and read back the same way, through [`PolynomialSeed`].
+use crate::error::AlgebraError;
+use crate::finite_field::{FieldElement, FieldSize, FiniteField, MAX_BARRETT_PRIME};
+use crate::polynomial::Polynomial;
+use serde::de::{DeserializeSeed, Error as _, MapAccess, SeqAccess, Visitor};
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "FiniteField")]
+struct FiniteFieldRepr {
+    prime: FieldSize,
+    generator: FieldSize,
+    barrett_reduction: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "FieldElement")]
+struct FieldElementRepr {
+    modulus: FieldSize,
+    value: FieldSize,
+}
+
+impl Serialize for FiniteField {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        FiniteFieldRepr {
+            prime: self.prime,
+            generator: self.generator,
+            barrett_reduction: self.uses_barrett_reduction(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for FiniteField {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = FiniteFieldRepr::deserialize(deserializer)?;
+        let finite_field =
+            FiniteField::try_new(repr.prime, repr.generator).map_err(D::Error::custom)?;
+        if !repr.barrett_reduction {
+            return Ok(finite_field);
+        }
+        if repr.prime <= 1 || repr.prime >= MAX_BARRETT_PRIME {
+            return Err(D::Error::custom(
+                "Barrett reduction needs a prime below 2^126",
+            ));
+        }
+        Ok(finite_field.with_barrett_reduction())
+    }
+}
+
+impl Serialize for FieldElement {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        FieldElementRepr {
+            modulus: self.finite_field().prime,
+            value: self.value(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Deserializes a [`FieldElement`] of the given field, rejecting other moduli and non-canonical values.
+#[derive(Debug, Clone, Copy)]
+pub struct FieldElementSeed<'a>(pub &'a Arc<FiniteField>);
+
+impl<'de> DeserializeSeed<'de> for FieldElementSeed<'_> {
+    type Value = FieldElement;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        let repr = FieldElementRepr::deserialize(deserializer)?;
+        if repr.modulus != self.0.prime {
+            return Err(D::Error::custom(AlgebraError::ModulusMismatch(
+                repr.modulus,
+            )));
+        }
+        FieldElement::try_from((repr.value, self.0)).map_err(D::Error::custom)
+    }
+}
+
+/// Deserializes a sequence of [`FieldElement`]s of the given field.
+#[derive(Debug, Clone, Copy)]
+pub struct FieldElementsSeed<'a>(pub &'a Arc<FiniteField>);
+
+impl<'de> DeserializeSeed<'de> for FieldElementsSeed<'_> {
+    type Value = Vec<FieldElement>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for FieldElementsSeed<'_> {
+    type Value = Vec<FieldElement>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a sequence of field elements")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut elements = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(element) = seq.next_element_seed(FieldElementSeed(self.0))? {
+            elements.push(element);
+        }
+        Ok(elements)
+    }
+}
+
+impl FieldElement {
+    /// `FieldElementSeed(finite_field).deserialize(deserializer)`
+    pub fn deserialize_in<'de, D: Deserializer<'de>>(
+        deserializer: D,
+        finite_field: &Arc<FiniteField>,
+    ) -> Result<Self, D::Error> {
+        FieldElementSeed(finite_field).deserialize(deserializer)
+    }
+}
+
+impl Serialize for Polynomial {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Polynomial", 1)?;
+        state.serialize_field("coefficients", &self.coefficients)?;
+        state.end()
+    }
+}
+
+/// Deserializes a [`Polynomial`] over the given field, trimming its trailing zeros.
+#[derive(Debug, Clone, Copy)]
+pub struct PolynomialSeed<'a>(pub &'a Arc<FiniteField>);
+
+impl<'de> DeserializeSeed<'de> for PolynomialSeed<'_> {
+    type Value = Polynomial;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_struct("Polynomial", &["coefficients"], self)
+    }
+}
+
+impl<'de> Visitor<'de> for PolynomialSeed<'_> {
+    type Value = Polynomial;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a polynomial")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let coefficients = seq
+            .next_element_seed(FieldElementsSeed(self.0))?
+            .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+        Ok(Polynomial::new(coefficients, Arc::clone(self.0)))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut coefficients = None;
+        while let Some(key) = map.next_key::<String>()? {
+            if key != "coefficients" {
+                return Err(A::Error::unknown_field(&key, &["coefficients"]));
+            }
+            if coefficients.is_some() {
+                return Err(A::Error::duplicate_field("coefficients"));
+            }
+            coefficients = Some(map.next_value_seed(FieldElementsSeed(self.0))?);
+        }
+        let coefficients = coefficients.ok_or_else(|| A::Error::missing_field("coefficients"))?;
+        Ok(Polynomial::new(coefficients, Arc::clone(self.0)))
+    }
+}
+
+impl Polynomial {
+    /// `PolynomialSeed(finite_field).deserialize(deserializer)`
+    pub fn deserialize_in<'de, D: Deserializer<'de>>(
+        deserializer: D,
+        finite_field: &Arc<FiniteField>,
+    ) -> Result<Self, D::Error> {
+        PolynomialSeed(finite_field).deserialize(deserializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::finite_field::{FieldElement, FiniteField};
+    use crate::poly;
+    use crate::polynomial::Polynomial;
+    use crate::serde_compat::{FieldElementSeed, FieldElementsSeed};
+    use serde::de::DeserializeSeed;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_finite_field_round_trip() {
+        let finite_field = FiniteField::new(97, 5);
+        let json = serde_json::to_string(&finite_field).unwrap();
+        assert_eq!(
+            json,
+            r#"{"prime":97,"generator":5,"barrett_reduction":false}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<FiniteField>(&json).unwrap(),
+            finite_field
+        );
+
+        let barrett = FiniteField::new(97, 5).with_barrett_reduction();
+        let json = serde_json::to_string(&barrett).unwrap();
+        assert_eq!(serde_json::from_str::<FiniteField>(&json).unwrap(), barrett);
+
+        assert!(serde_json::from_str::<FiniteField>(
+            r#"{"prime":97,"generator":0,"barrett_reduction":false}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_field_element_round_trip() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let element = finite_field.element(-3);
+        let json = serde_json::to_string(&element).unwrap();
+        assert_eq!(json, r#"{"modulus":97,"value":94}"#);
+
+        let mut deserializer = serde_json::Deserializer::from_str(&json);
+        let decoded = FieldElementSeed(&finite_field)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        assert_eq!(decoded, element);
+        assert!(Arc::ptr_eq(decoded.finite_field(), &finite_field));
+
+        let elements: Vec<FieldElement> = finite_field.elements([1, 2, 3]).collect();
+        let json = serde_json::to_string(&elements).unwrap();
+        let mut deserializer = serde_json::Deserializer::from_str(&json);
+        assert_eq!(
+            FieldElementsSeed(&finite_field)
+                .deserialize(&mut deserializer)
+                .unwrap(),
+            elements
+        );
+    }
+
+    #[test]
+    fn test_rejects_invalid_elements() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        for json in [
+            r#"{"modulus":97,"value":97}"#,
+            r#"{"modulus":97,"value":-1}"#,
+            r#"{"modulus":13,"value":3}"#,
+        ] {
+            let mut deserializer = serde_json::Deserializer::from_str(json);
+            assert!(FieldElement::deserialize_in(&mut deserializer, &finite_field).is_err());
+        }
+    }
+
+    #[test]
+    fn test_polynomial_round_trip() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let f = poly!(finite_field, [3, 0, -1]);
+        let json = serde_json::to_string(&f).unwrap();
+        assert_eq!(
+            json,
+            r#"{"coefficients":[{"modulus":97,"value":3},{"modulus":97,"value":0},{"modulus":97,"value":96}]}"#
+        );
+        let mut deserializer = serde_json::Deserializer::from_str(&json);
+        let decoded = Polynomial::deserialize_in(&mut deserializer, &finite_field).unwrap();
+        assert_eq!(decoded, f);
+        assert!(Arc::ptr_eq(decoded.finite_field(), &finite_field));
+
+        let other = Arc::new(FiniteField::new(13, 2));
+        let mut deserializer = serde_json::Deserializer::from_str(&json);
+        assert!(Polynomial::deserialize_in(&mut deserializer, &other).is_err());
+    }
+}
@@ -0,0 +1,126 @@
+//! Element-wise arithmetic on slices of [`FieldElement`]s, in place.
+//!
+//! The results are written into the values of the left slice, so no element and no field handle
+//! is created per entry. With the `parallel` feature the slices are split across the rayon thread pool.
+use crate::finite_field::{FieldElement, FieldSize};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::sync::Arc;
+
+/// `a[i] += b[i]`
+pub fn add_slices(a: &mut [FieldElement], b: &[FieldElement]) {
+    zip_apply(a, b, |x, y| {
+        let prime = x.finite_field().prime;
+        let sum = x.canonical() + y.canonical();
+        x.element = if sum >= prime { sum - prime } else { sum };
+    });
+}
+
+/// `a[i] -= b[i]`
+pub fn sub_slices(a: &mut [FieldElement], b: &[FieldElement]) {
+    zip_apply(a, b, |x, y| {
+        let difference = x.canonical() - y.canonical();
+        x.element = if difference < 0 {
+            difference + x.finite_field().prime
+        } else {
+            difference
+        };
+    });
+}
+
+/// `a[i] *= b[i]`
+pub fn mul_slices(a: &mut [FieldElement], b: &[FieldElement]) {
+    zip_apply(a, b, |x, y| x.element = multiply(x, y.canonical()));
+}
+
+/// `a[i] *= c`
+pub fn scale_slice(a: &mut [FieldElement], c: &FieldElement) {
+    let scalar = c.canonical();
+    let apply = |x: &mut FieldElement| {
+        assert!(
+            Arc::ptr_eq(x.finite_field(), c.finite_field()),
+            "Cannot mul elements from different finite fields"
+        );
+        x.element = multiply(x, scalar);
+    };
+    #[cfg(feature = "parallel")]
+    a.par_iter_mut().for_each(apply);
+    #[cfg(not(feature = "parallel"))]
+    a.iter_mut().for_each(apply);
+}
+
+fn multiply(x: &FieldElement, y: FieldSize) -> FieldSize {
+    let finite_field = x.finite_field();
+    finite_field
+        .multiply(x.canonical(), y)
+        .rem_euclid(finite_field.prime)
+}
+
+fn zip_apply<F>(a: &mut [FieldElement], b: &[FieldElement], op: F)
+where
+    F: Fn(&mut FieldElement, &FieldElement) + Send + Sync,
+{
+    assert_eq!(a.len(), b.len(), "The slices must have the same length");
+    let apply = |(x, y): (&mut FieldElement, &FieldElement)| {
+        assert!(
+            Arc::ptr_eq(x.finite_field(), y.finite_field()),
+            "Cannot combine elements from different finite fields"
+        );
+        op(x, y);
+    };
+    #[cfg(feature = "parallel")]
+    a.par_iter_mut().zip(b.par_iter()).for_each(apply);
+    #[cfg(not(feature = "parallel"))]
+    a.iter_mut().zip(b).for_each(apply);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::finite_field::{FieldElement, FiniteField};
+    use crate::slice::{add_slices, mul_slices, scale_slice, sub_slices};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_slice_ops() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let mut rng = StdRng::seed_from_u64(5);
+        let a = finite_field.random_elements(33, &mut rng);
+        let b = finite_field.random_elements(33, &mut rng);
+
+        let mut sum = a.clone();
+        add_slices(&mut sum, &b);
+        let mut difference = a.clone();
+        sub_slices(&mut difference, &b);
+        let mut product = a.clone();
+        mul_slices(&mut product, &b);
+        let mut scaled = a.clone();
+        scale_slice(&mut scaled, &finite_field.element(-2));
+        for i in 0..a.len() {
+            assert_eq!(sum[i].value(), (&a[i] + &b[i]).value());
+            assert_eq!(difference[i].value(), (&a[i] - &b[i]).value());
+            assert_eq!(product[i].value(), (&a[i] * &b[i]).value());
+            assert_eq!(scaled[i].value(), (&a[i] * -2).value());
+        }
+
+        // unreduced inputs give canonical outputs
+        let mut x = vec![finite_field.element(-1), finite_field.element(200)];
+        add_slices(
+            &mut x,
+            &[finite_field.element(-1), finite_field.element(-3)],
+        );
+        assert_eq!(
+            x.iter().map(FieldElement::value).collect::<Vec<_>>(),
+            vec![95, 3]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "same length")]
+    fn test_length_mismatch() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let mut a = vec![finite_field.one(); 2];
+        add_slices(&mut a, &[finite_field.one()]);
+    }
+}
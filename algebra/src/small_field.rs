@@ -0,0 +1,383 @@
+//! 31 bit prime fields, BabyBear (`15 * 2^27 + 1`) and Mersenne31 (`2^31 - 1`).
+//!
+//! Elements are canonical `u32`s, so the sum of two elements never overflows and eight of them fit
+//! in a 256 bit register. The slice operations use AVX2 when the CPU supports it and fall back to
+//! plain loops otherwise.
+use crate::error::AlgebraError;
+use crate::field::Field;
+use rand::Rng;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// 15 * 2^27 + 1
+pub const BABY_BEAR_PRIME: u32 = 0x7800_0001;
+pub const BABY_BEAR_GENERATOR: u32 = 31;
+/// 2^31 - 1
+pub const MERSENNE31_PRIME: u32 = 0x7fff_ffff;
+pub const MERSENNE31_GENERATOR: u32 = 7;
+
+pub type BabyBear = Field31<BABY_BEAR_PRIME>;
+pub type Mersenne31 = Field31<MERSENNE31_PRIME>;
+
+/// element of the prime field of order `P < 2^31`, the primality of `P` isn't checked
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+#[repr(transparent)]
+pub struct Field31<const P: u32>(u32);
+
+impl<const P: u32> Field31<P> {
+    pub const ZERO: Self = Self(0);
+    pub const ONE: Self = Self(1);
+    pub const MODULUS: u32 = P;
+
+    /// reduces the value modulo `P`
+    pub const fn new(value: u32) -> Self {
+        assert!(P > 1 && P < 1 << 31, "The modulus must be below 2^31");
+        Self(value % P)
+    }
+
+    pub const fn value(&self) -> u32 {
+        self.0
+    }
+
+    pub fn inverse(&self) -> Self {
+        self.try_inverse()
+            .expect("Cannot compute the inverse of zero")
+    }
+
+    /// `x^(P - 2)`
+    pub fn try_inverse(&self) -> Result<Self, AlgebraError> {
+        if self.0 == 0 {
+            return Err(AlgebraError::DivisionByZero);
+        }
+        Ok(self.pow(P as u64 - 2))
+    }
+
+    pub fn pow(&self, mut exponent: u64) -> Self {
+        let mut result = Self::ONE;
+        let mut square = *self;
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result *= square;
+            }
+            square *= square;
+            exponent >>= 1;
+        }
+        result
+    }
+
+    pub fn random_element() -> Self {
+        Self::sample(&mut rand::thread_rng())
+    }
+
+    /// uniform element drawn from `rng` by rejection
+    pub fn sample<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let mask = u32::MAX >> P.leading_zeros();
+        loop {
+            let value = rng.next_u32() & mask;
+            if value < P {
+                return Self(value);
+            }
+        }
+    }
+
+    pub fn random_elements<R: Rng + ?Sized>(n: usize, rng: &mut R) -> Vec<Self> {
+        (0..n).map(|_| Self::sample(rng)).collect()
+    }
+
+    /// `a[i] += b[i]`
+    pub fn add_slices(a: &mut [Self], b: &[Self]) {
+        assert_eq!(a.len(), b.len(), "The slices must have the same length");
+        #[cfg(target_arch = "x86_64")]
+        if is_x86_feature_detected!("avx2") {
+            // SAFETY: AVX2 is available and both slices have the same length
+            unsafe { avx2::add_slices::<P>(a, b) };
+            return;
+        }
+        for (x, y) in a.iter_mut().zip(b) {
+            *x += *y;
+        }
+    }
+
+    /// `a[i] -= b[i]`
+    pub fn sub_slices(a: &mut [Self], b: &[Self]) {
+        assert_eq!(a.len(), b.len(), "The slices must have the same length");
+        #[cfg(target_arch = "x86_64")]
+        if is_x86_feature_detected!("avx2") {
+            // SAFETY: AVX2 is available and both slices have the same length
+            unsafe { avx2::sub_slices::<P>(a, b) };
+            return;
+        }
+        for (x, y) in a.iter_mut().zip(b) {
+            *x -= *y;
+        }
+    }
+
+    /// `a[i] *= b[i]`, a plain loop the compiler vectorizes where the target allows it
+    pub fn mul_slices(a: &mut [Self], b: &[Self]) {
+        assert_eq!(a.len(), b.len(), "The slices must have the same length");
+        for (x, y) in a.iter_mut().zip(b) {
+            *x *= *y;
+        }
+    }
+
+    /// `a[i] *= c`
+    pub fn scale_slice(a: &mut [Self], c: Self) {
+        for x in a.iter_mut() {
+            *x *= c;
+        }
+    }
+
+    /// `x mod P` for `x < P^2`. For Mersenne31 `2^31 = 1`, so the high bits are added to the low ones.
+    fn reduce(x: u64) -> u32 {
+        if P == MERSENNE31_PRIME {
+            let folded = (x & P as u64) + (x >> 31);
+            let folded = folded as u32;
+            if folded >= P {
+                folded - P
+            } else {
+                folded
+            }
+        } else {
+            (x % P as u64) as u32
+        }
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+mod avx2 {
+    use super::Field31;
+    use std::arch::x86_64::*;
+
+    /// Both inputs are below `P < 2^31`, so `a + b` doesn't overflow and `min(s, s - P)` picks
+    /// `s - P` exactly when `s >= P`, since otherwise `s - P` wraps above `2^31`.
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn add_slices<const P: u32>(a: &mut [Field31<P>], b: &[Field31<P>]) {
+        let modulus = _mm256_set1_epi32(P as i32);
+        let chunks = a.len() / 8;
+        let (pa, pb) = (a.as_mut_ptr() as *mut __m256i, b.as_ptr() as *const __m256i);
+        for i in 0..chunks {
+            let x = _mm256_loadu_si256(pa.add(i));
+            let y = _mm256_loadu_si256(pb.add(i));
+            let sum = _mm256_add_epi32(x, y);
+            let reduced = _mm256_min_epu32(sum, _mm256_sub_epi32(sum, modulus));
+            _mm256_storeu_si256(pa.add(i), reduced);
+        }
+        for i in chunks * 8..a.len() {
+            a[i] += b[i];
+        }
+    }
+
+    /// `min(d, d + P)` picks `d + P` exactly when `a - b` wrapped
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn sub_slices<const P: u32>(a: &mut [Field31<P>], b: &[Field31<P>]) {
+        let modulus = _mm256_set1_epi32(P as i32);
+        let chunks = a.len() / 8;
+        let (pa, pb) = (a.as_mut_ptr() as *mut __m256i, b.as_ptr() as *const __m256i);
+        for i in 0..chunks {
+            let x = _mm256_loadu_si256(pa.add(i));
+            let y = _mm256_loadu_si256(pb.add(i));
+            let difference = _mm256_sub_epi32(x, y);
+            let reduced = _mm256_min_epu32(difference, _mm256_add_epi32(difference, modulus));
+            _mm256_storeu_si256(pa.add(i), reduced);
+        }
+        for i in chunks * 8..a.len() {
+            a[i] -= b[i];
+        }
+    }
+}
+
+impl<const P: u32> From<u32> for Field31<P> {
+    fn from(value: u32) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<const P: u32> Display for Field31<P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<const P: u32> Add for Field31<P> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let sum = self.0 + rhs.0;
+        if sum >= P {
+            Self(sum - P)
+        } else {
+            Self(sum)
+        }
+    }
+}
+
+impl<const P: u32> AddAssign for Field31<P> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const P: u32> Sub for Field31<P> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        if self.0 >= rhs.0 {
+            Self(self.0 - rhs.0)
+        } else {
+            Self(self.0 + P - rhs.0)
+        }
+    }
+}
+
+impl<const P: u32> SubAssign for Field31<P> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const P: u32> Mul for Field31<P> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self(Self::reduce(self.0 as u64 * rhs.0 as u64))
+    }
+}
+
+impl<const P: u32> MulAssign for Field31<P> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<const P: u32> Div for Field31<P> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        assert_ne!(rhs.0, 0, "Division by zero is not allowed");
+        self * rhs.inverse()
+    }
+}
+
+impl<const P: u32> Neg for Field31<P> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::ZERO - self
+    }
+}
+
+impl<const P: u32> Field for Field31<P> {
+    fn zero(&self) -> Self {
+        Self::ZERO
+    }
+
+    fn one(&self) -> Self {
+        Self::ONE
+    }
+
+    fn try_inverse(&self) -> Result<Self, AlgebraError> {
+        Field31::try_inverse(self)
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self {
+        Self::sample(rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::AlgebraError;
+    use crate::fp::Fp;
+    use crate::small_field::{
+        BabyBear, Mersenne31, BABY_BEAR_GENERATOR, BABY_BEAR_PRIME, MERSENNE31_GENERATOR,
+        MERSENNE31_PRIME,
+    };
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_matches_fp() {
+        let mut rng = StdRng::seed_from_u64(3);
+        for _ in 0..1000 {
+            let (a, b) = (BabyBear::sample(&mut rng), BabyBear::sample(&mut rng));
+            let (x, y) = (
+                Fp::<{ BABY_BEAR_PRIME as u64 }>::new(a.value() as u64),
+                Fp::<{ BABY_BEAR_PRIME as u64 }>::new(b.value() as u64),
+            );
+            assert_eq!((a + b).value() as u64, (x + y).value());
+            assert_eq!((a - b).value() as u64, (x - y).value());
+            assert_eq!((a * b).value() as u64, (x * y).value());
+
+            let (a, b) = (Mersenne31::sample(&mut rng), Mersenne31::sample(&mut rng));
+            let (x, y) = (
+                Fp::<{ MERSENNE31_PRIME as u64 }>::new(a.value() as u64),
+                Fp::<{ MERSENNE31_PRIME as u64 }>::new(b.value() as u64),
+            );
+            assert_eq!((a + b).value() as u64, (x + y).value());
+            assert_eq!((a - b).value() as u64, (x - y).value());
+            assert_eq!((a * b).value() as u64, (x * y).value());
+        }
+
+        let minus_one = Mersenne31::new(MERSENNE31_PRIME - 1);
+        assert_eq!(minus_one * minus_one, Mersenne31::ONE);
+        assert_eq!(-Mersenne31::ONE, minus_one);
+        let x = BabyBear::new(123456);
+        assert_eq!(x * x.inverse(), BabyBear::ONE);
+        assert_eq!(
+            BabyBear::ZERO.try_inverse(),
+            Err(AlgebraError::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn test_generators() {
+        let generator = BabyBear::new(BABY_BEAR_GENERATOR);
+        let order = BABY_BEAR_PRIME as u64 - 1;
+        assert_eq!(generator.pow(order), BabyBear::ONE);
+        for factor in [2, 3, 5] {
+            assert_ne!(generator.pow(order / factor), BabyBear::ONE);
+        }
+
+        let generator = Mersenne31::new(MERSENNE31_GENERATOR);
+        let order = MERSENNE31_PRIME as u64 - 1;
+        for factor in [2, 3, 7, 11, 31, 151, 331] {
+            assert_ne!(generator.pow(order / factor), Mersenne31::ONE);
+        }
+    }
+
+    #[test]
+    fn test_slice_ops() {
+        let mut rng = StdRng::seed_from_u64(4);
+        // not a multiple of 8, so the tail after the vector lanes is covered too
+        let a = BabyBear::random_elements(21, &mut rng);
+        let b = BabyBear::random_elements(21, &mut rng);
+        // the extremes, where the lane reductions wrap
+        let mut edges = vec![BabyBear::ZERO, BabyBear::new(BABY_BEAR_PRIME - 1)];
+        edges.resize(8, BabyBear::ONE);
+
+        for (a, b) in [
+            (a, b),
+            (edges.clone(), edges.iter().rev().copied().collect()),
+        ] {
+            let mut sum = a.clone();
+            BabyBear::add_slices(&mut sum, &b);
+            let mut difference = a.clone();
+            BabyBear::sub_slices(&mut difference, &b);
+            let mut product = a.clone();
+            BabyBear::mul_slices(&mut product, &b);
+            for i in 0..a.len() {
+                assert_eq!(sum[i], a[i] + b[i]);
+                assert_eq!(difference[i], a[i] - b[i]);
+                assert_eq!(product[i], a[i] * b[i]);
+            }
+        }
+
+        let a = Mersenne31::random_elements(17, &mut rng);
+        let mut scaled = a.clone();
+        Mersenne31::scale_slice(&mut scaled, Mersenne31::new(3));
+        let mut tripled = a.clone();
+        Mersenne31::add_slices(&mut tripled, &a);
+        Mersenne31::add_slices(&mut tripled, &a);
+        assert_eq!(scaled, tripled);
+    }
+}
@@ -0,0 +1,328 @@
+//! Polynomials stored as their non-zero terms, like the zerofier `x^n - 1` or a transition constraint
+//! that only touches a few powers.
+//!
+//! Multiplying or dividing a dense [`Polynomial`] by a sparse one costs `O(len * terms)` instead of
+//! `O(len * degree)`.
+use crate::error::AlgebraError;
+use crate::finite_field::{FieldElement, FiniteField};
+use crate::polynomial::Polynomial;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Mul, Neg, Sub};
+use std::sync::Arc;
+
+/// `Σ c_i x^(e_i)`, the terms are sorted by exponent with no zero coefficients
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparsePolynomial {
+    terms: Vec<(usize, FieldElement)>,
+    finite_field: Arc<FiniteField>,
+}
+
+impl SparsePolynomial {
+    /// terms with the same exponent are added together
+    pub fn new<I: IntoIterator<Item = (usize, FieldElement)>>(
+        terms: I,
+        finite_field: Arc<FiniteField>,
+    ) -> Self {
+        let mut combined: BTreeMap<usize, FieldElement> = BTreeMap::new();
+        for (exponent, coefficient) in terms {
+            combined
+                .entry(exponent)
+                .and_modify(|sum| *sum += &coefficient)
+                .or_insert_with(|| coefficient.abs());
+        }
+        let zero = finite_field.zero();
+        Self {
+            terms: combined.into_iter().filter(|(_, c)| *c != zero).collect(),
+            finite_field,
+        }
+    }
+
+    /// `c x^exponent`
+    pub fn monomial(exponent: usize, coefficient: FieldElement) -> Self {
+        let finite_field = Arc::clone(coefficient.finite_field());
+        Self::new([(exponent, coefficient)], finite_field)
+    }
+
+    pub fn zero(finite_field: Arc<FiniteField>) -> Self {
+        Self {
+            terms: vec![],
+            finite_field,
+        }
+    }
+
+    pub fn terms(&self) -> &[(usize, FieldElement)] {
+        &self.terms
+    }
+
+    pub fn finite_field(&self) -> &Arc<FiniteField> {
+        &self.finite_field
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// `None` for the zero polynomial
+    pub fn degree(&self) -> Option<usize> {
+        self.terms.last().map(|(exponent, _)| *exponent)
+    }
+
+    pub fn from_dense(polynomial: &Polynomial) -> Self {
+        Self::new(
+            polynomial.coefficients.iter().cloned().enumerate(),
+            Arc::clone(polynomial.finite_field()),
+        )
+    }
+
+    pub fn to_dense(&self) -> Polynomial {
+        let len = self.degree().map_or(0, |degree| degree + 1);
+        let mut coefficients = vec![self.finite_field.zero(); len];
+        for (exponent, coefficient) in &self.terms {
+            coefficients[*exponent] = coefficient.clone();
+        }
+        Polynomial::new(coefficients, Arc::clone(&self.finite_field))
+    }
+
+    /// each power is reached from the previous one, so the cost depends on the gaps between exponents
+    pub fn evaluate(&self, x: &FieldElement) -> FieldElement {
+        let mut result = self.finite_field.zero();
+        let mut power = self.finite_field.one();
+        let mut previous = 0;
+        for (exponent, coefficient) in &self.terms {
+            power = &power * &x.pow((exponent - previous) as u128);
+            previous = *exponent;
+            result += coefficient * &power;
+        }
+        result
+    }
+}
+
+impl Display for SparsePolynomial {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_dense())
+    }
+}
+
+impl From<&Polynomial> for SparsePolynomial {
+    fn from(polynomial: &Polynomial) -> Self {
+        Self::from_dense(polynomial)
+    }
+}
+
+impl From<&SparsePolynomial> for Polynomial {
+    fn from(polynomial: &SparsePolynomial) -> Self {
+        polynomial.to_dense()
+    }
+}
+
+impl Add for &SparsePolynomial {
+    type Output = SparsePolynomial;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        SparsePolynomial::new(
+            self.terms.iter().chain(&rhs.terms).cloned(),
+            Arc::clone(&self.finite_field),
+        )
+    }
+}
+
+impl Sub for &SparsePolynomial {
+    type Output = SparsePolynomial;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + &-rhs
+    }
+}
+
+impl Mul for &SparsePolynomial {
+    type Output = SparsePolynomial;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let products = self
+            .terms
+            .iter()
+            .flat_map(|(e1, c1)| rhs.terms.iter().map(move |(e2, c2)| (e1 + e2, c1 * c2)));
+        SparsePolynomial::new(products, Arc::clone(&self.finite_field))
+    }
+}
+
+impl Neg for &SparsePolynomial {
+    type Output = SparsePolynomial;
+
+    fn neg(self) -> Self::Output {
+        SparsePolynomial {
+            terms: self
+                .terms
+                .iter()
+                .map(|(exponent, coefficient)| (*exponent, (-coefficient).abs()))
+                .collect(),
+            finite_field: Arc::clone(&self.finite_field),
+        }
+    }
+}
+
+impl Add<&SparsePolynomial> for &Polynomial {
+    type Output = Polynomial;
+
+    fn add(self, rhs: &SparsePolynomial) -> Self::Output {
+        let len = self
+            .coefficients
+            .len()
+            .max(rhs.degree().map_or(0, |degree| degree + 1));
+        let mut coefficients = self.coefficients.clone();
+        coefficients.resize(len, self.finite_field().zero());
+        for (exponent, coefficient) in &rhs.terms {
+            coefficients[*exponent] += coefficient;
+        }
+        Polynomial::new(coefficients, Arc::clone(self.finite_field()))
+    }
+}
+
+impl Sub<&SparsePolynomial> for &Polynomial {
+    type Output = Polynomial;
+
+    fn sub(self, rhs: &SparsePolynomial) -> Self::Output {
+        self + &-rhs
+    }
+}
+
+impl Add<&Polynomial> for &SparsePolynomial {
+    type Output = Polynomial;
+
+    fn add(self, rhs: &Polynomial) -> Self::Output {
+        rhs + self
+    }
+}
+
+impl Mul<&SparsePolynomial> for &Polynomial {
+    type Output = Polynomial;
+
+    /// every term shifts and scales a copy of the dense coefficients
+    fn mul(self, rhs: &SparsePolynomial) -> Self::Output {
+        let finite_field = self.finite_field();
+        let (Some(degree), false) = (rhs.degree(), self.coefficients.is_empty()) else {
+            return Polynomial::new(vec![], Arc::clone(finite_field));
+        };
+        let mut coefficients = vec![finite_field.zero(); self.coefficients.len() + degree];
+        for (exponent, c2) in &rhs.terms {
+            for (i, c1) in self.coefficients.iter().enumerate() {
+                coefficients[i + exponent] += c1 * c2;
+            }
+        }
+        Polynomial::new(coefficients, Arc::clone(finite_field))
+    }
+}
+
+impl Mul<&Polynomial> for &SparsePolynomial {
+    type Output = Polynomial;
+
+    fn mul(self, rhs: &Polynomial) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl Polynomial {
+    /// Long division by a sparse divisor, returns (quotient, remainder). Every step only touches the
+    /// divisor's terms, so dividing by `x^n - c` is linear in the length of the dividend.
+    pub fn div_rem_sparse(
+        &self,
+        divisor: &SparsePolynomial,
+    ) -> Result<(Polynomial, Polynomial), AlgebraError> {
+        let finite_field = self.finite_field();
+        let Some((degree, leading)) = divisor.terms.last() else {
+            return Err(AlgebraError::DivisionByZero);
+        };
+        let leading_inv = leading.inverse();
+        let mut remainder = self.coefficients.clone();
+        let quotient_len = (remainder.len() + 1).saturating_sub(degree + 1);
+        let mut quotient = vec![finite_field.zero(); quotient_len];
+        for i in (0..quotient_len).rev() {
+            let factor = &remainder[i + degree] * &leading_inv;
+            for (exponent, coefficient) in &divisor.terms {
+                remainder[i + exponent] -= &(&factor * coefficient);
+            }
+            quotient[i] = factor;
+        }
+        remainder.truncate(*degree.min(&remainder.len()));
+        Ok((
+            Polynomial::new(quotient, Arc::clone(finite_field)),
+            Polynomial::new(remainder, Arc::clone(finite_field)),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::AlgebraError;
+    use crate::finite_field::FiniteField;
+    use crate::poly;
+    use crate::sparse::SparsePolynomial;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_conversions() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let f = poly!(finite_field, [3, 0, 0, 0, 0, 7]);
+        let sparse = SparsePolynomial::from_dense(&f);
+        assert_eq!(sparse.terms().len(), 2);
+        assert_eq!(sparse.degree(), Some(5));
+        assert_eq!(sparse.to_dense(), f);
+
+        let x = finite_field.element(11);
+        assert_eq!(sparse.evaluate(&x), f.evaluate(x));
+
+        // repeated exponents are combined, cancelled terms are dropped
+        let terms = [(2, 5), (2, 92), (4, 1), (0, 96)].map(|(e, c)| (e, finite_field.element(c)));
+        let sparse = SparsePolynomial::new(terms, Arc::clone(&finite_field));
+        assert_eq!(
+            sparse,
+            SparsePolynomial::from_dense(&poly!(finite_field, [-1, 0, 0, 0, 1]))
+        );
+        assert!(SparsePolynomial::zero(Arc::clone(&finite_field)).is_zero());
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let f = poly!(finite_field, [1, 2, 3]);
+        let g = poly!(finite_field, [4, 0, 0, 0, 0, 0, 0, 5]);
+        let h = poly!(finite_field, [0, 6, 0, 0, 0, 0, 0, 0, 0, 0, 7]);
+        let (sparse_g, sparse_h) = (SparsePolynomial::from(&g), SparsePolynomial::from(&h));
+
+        assert_eq!((&sparse_g + &sparse_h).to_dense(), &g + &h);
+        assert_eq!((&sparse_g - &sparse_h).to_dense(), &g - &h);
+        assert_eq!((&sparse_g * &sparse_h).to_dense(), &g * &h);
+        assert_eq!(&f * &sparse_g, &f * &g);
+        assert_eq!(&sparse_h * &f, &h * &f);
+        assert_eq!(&f + &sparse_h, &f + &h);
+        assert_eq!(&f - &sparse_g, &f - &g);
+    }
+
+    #[test]
+    fn test_div_rem_sparse() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        // x^4 - 1
+        let zerofier = SparsePolynomial::new(
+            [(4, finite_field.one()), (0, -finite_field.one())],
+            Arc::clone(&finite_field),
+        );
+        let q = poly!(finite_field, [5, 4, 3, 2, 1]);
+        let r = poly!(finite_field, [9, 8, 7]);
+        let f = &(&q * &zerofier) + &r;
+
+        let (quotient, remainder) = f.div_rem_sparse(&zerofier).unwrap();
+        assert_eq!(quotient, q);
+        assert_eq!(remainder, r);
+
+        let (quotient, remainder) = r.div_rem_sparse(&zerofier).unwrap();
+        assert!(quotient.coefficients.is_empty());
+        assert_eq!(remainder, r);
+
+        assert_eq!(
+            f.div_rem_sparse(&SparsePolynomial::zero(Arc::clone(&finite_field)))
+                .err(),
+            Some(AlgebraError::DivisionByZero)
+        );
+    }
+}
@@ -0,0 +1,95 @@
+//! Multiplicative subgroups of a prime field, the domains of NTTs and FRI.
+use crate::error::AlgebraError;
+use crate::finite_field::{FieldElement, FiniteField};
+use std::sync::Arc;
+
+/// `{1, ω, ω^2, ..., ω^(n - 1)}` for a primitive `n`-th root of unity `ω`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Subgroup {
+    generator: FieldElement,
+    order: usize,
+}
+
+impl Subgroup {
+    /// `order` must divide `p - 1`, see [`FiniteField::primitive_nth_root`]
+    pub fn new(finite_field: &Arc<FiniteField>, order: usize) -> Result<Self, AlgebraError> {
+        Ok(Self {
+            generator: finite_field.primitive_nth_root(order)?,
+            order,
+        })
+    }
+
+    pub fn order(&self) -> usize {
+        self.order
+    }
+
+    /// ω
+    pub fn generator(&self) -> &FieldElement {
+        &self.generator
+    }
+
+    pub fn finite_field(&self) -> &Arc<FiniteField> {
+        self.generator.finite_field()
+    }
+
+    /// ω^index, the index is taken modulo the order
+    pub fn element(&self, index: usize) -> FieldElement {
+        self.generator.pow((index % self.order) as u128)
+    }
+
+    /// ω^0, ω^1, ..., ω^(n - 1)
+    pub fn elements(&self) -> impl Iterator<Item = FieldElement> + '_ {
+        let mut current = self.finite_field().one();
+        (0..self.order).map(move |_| {
+            let element = current.clone();
+            current = &current * &self.generator;
+            element
+        })
+    }
+
+    /// `x^n = 1`
+    pub fn contains(&self, x: &FieldElement) -> bool {
+        x.pow(self.order as u128) == self.finite_field().one()
+    }
+
+    /// the subgroup of half the order, generated by ω^2, for an even order
+    pub fn square(&self) -> Option<Self> {
+        self.order.is_multiple_of(2).then(|| Self {
+            generator: &self.generator * &self.generator,
+            order: self.order / 2,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::AlgebraError;
+    use crate::finite_field::FiniteField;
+    use crate::subgroup::Subgroup;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_subgroup() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let subgroup = Subgroup::new(&finite_field, 8).unwrap();
+        let elements: Vec<_> = subgroup.elements().collect();
+        assert_eq!(elements.len(), 8);
+        assert_eq!(elements[0], finite_field.one());
+        for (i, element) in elements.iter().enumerate() {
+            assert_eq!(*element, subgroup.element(i));
+            assert_eq!(*element, subgroup.element(i + 8));
+            assert!(subgroup.contains(element));
+            assert!(!elements[..i].contains(element));
+        }
+        assert!(!subgroup.contains(&finite_field.element(5)));
+
+        let half = subgroup.square().unwrap();
+        assert_eq!(half.order(), 4);
+        assert!(half.elements().all(|x| subgroup.contains(&x)));
+
+        assert_eq!(
+            Subgroup::new(&finite_field, 5),
+            Err(AlgebraError::InvalidDomainSize(5))
+        );
+    }
+}
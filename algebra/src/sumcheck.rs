@@ -0,0 +1,125 @@
+//! Univariate sum-check (Aurora): proves `Σ_{x ∈ H} f(x) = s` for a multiplicative subgroup `H` of order `n`.
+//!
+//! Any `f` splits as `f = g * Z_H + x * h + s / n` with `Z_H = x^n - 1` and `deg h < n - 1`, because the
+//! powers `x^k` with `0 < k < n` sum to zero over `H`. The prover sends `g` and `h`, the verifier checks the
+//! identity at a random point and the degree bound on `h`.
+use crate::error::AlgebraError;
+use crate::finite_field::{FieldElement, FieldSize, FiniteField};
+use crate::polynomial::Polynomial;
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+pub struct SumcheckProof {
+    /// g, the quotient by the zerofier of H
+    pub quotient: Polynomial,
+    /// h, the remainder without its constant term, divided by x
+    pub remainder: Polynomial,
+    pub sum: FieldElement,
+}
+
+/// `Z_H = x^n - 1`
+pub fn subgroup_zerofier(finite_field: &Arc<FiniteField>, subgroup_size: usize) -> Polynomial {
+    Polynomial::zerofier_coset(subgroup_size, &finite_field.one())
+}
+
+/// `subgroup_size` must divide `p - 1`, the sum is taken over the subgroup of that order
+pub fn prove(f: &Polynomial, subgroup_size: usize) -> Result<SumcheckProof, AlgebraError> {
+    check_subgroup_size(f, subgroup_size)?;
+    let finite_field = f.finite_field();
+    let (quotient, remainder) = f.div_rem(&subgroup_zerofier(finite_field, subgroup_size))?;
+
+    let constant = remainder
+        .coefficients
+        .first()
+        .cloned()
+        .unwrap_or(finite_field.zero());
+    let sum = &constant * &finite_field.element(subgroup_size as FieldSize);
+    let remainder = Polynomial::new(
+        remainder.coefficients.into_iter().skip(1).collect(),
+        Arc::clone(finite_field),
+    );
+
+    Ok(SumcheckProof {
+        quotient,
+        remainder,
+        sum,
+    })
+}
+
+/// Checks `f(z) = g(z) * Z_H(z) + z * h(z) + s / n` given `f(z)`, and `deg h < n - 1`.
+pub fn verify(
+    proof: &SumcheckProof,
+    subgroup_size: usize,
+    z: &FieldElement,
+    f_at_z: &FieldElement,
+) -> Result<bool, AlgebraError> {
+    check_subgroup_size(&proof.remainder, subgroup_size)?;
+    let finite_field = z.finite_field();
+    let zero = finite_field.zero();
+
+    let remainder_len = proof
+        .remainder
+        .coefficients
+        .iter()
+        .rposition(|c| *c != zero)
+        .map_or(0, |index| index + 1);
+    if remainder_len > subgroup_size - 1 {
+        return Ok(false);
+    }
+
+    let zerofier_at_z = subgroup_zerofier(finite_field, subgroup_size).evaluate(z.clone());
+    let n_inv = finite_field
+        .element(subgroup_size as FieldSize)
+        .try_inverse()?;
+    let expected = &(&(&proof.quotient.evaluate(z.clone()) * &zerofier_at_z)
+        + &(z * &proof.remainder.evaluate(z.clone())))
+        + &(&proof.sum * &n_inv);
+    Ok(expected == *f_at_z)
+}
+
+fn check_subgroup_size(f: &Polynomial, subgroup_size: usize) -> Result<(), AlgebraError> {
+    let order = f.finite_field().prime - 1;
+    if subgroup_size == 0 || order % subgroup_size as FieldSize != 0 {
+        return Err(AlgebraError::InvalidDomainSize(subgroup_size));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::AlgebraError;
+    use crate::finite_field::FiniteField;
+    use crate::poly;
+    use crate::sumcheck::{prove, verify};
+    use crate::twiddles::Twiddles;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_sumcheck() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let f = poly!(finite_field, [4, 8, 15, 16, 23, 42, 1, 2, 3, 5, 7, 11]);
+        let subgroup = Twiddles::new(&finite_field, 8).unwrap().roots;
+        let mut sum = finite_field.zero();
+        for x in &subgroup {
+            sum += f.evaluate(x.clone());
+        }
+
+        let proof = prove(&f, 8).unwrap();
+        assert_eq!(proof.sum, sum);
+
+        let z = finite_field.element(31);
+        let f_at_z = f.evaluate(z.clone());
+        assert_eq!(verify(&proof, 8, &z, &f_at_z), Ok(true));
+
+        let mut wrong_sum = proof.clone();
+        wrong_sum.sum = &wrong_sum.sum + &finite_field.one();
+        assert_eq!(verify(&wrong_sum, 8, &z, &f_at_z), Ok(false));
+
+        // h must stay below degree n - 1
+        let mut high_degree = proof.clone();
+        high_degree.remainder.coefficients.push(finite_field.one());
+        assert_eq!(verify(&high_degree, 8, &z, &f_at_z), Ok(false));
+
+        assert_eq!(prove(&f, 7).err(), Some(AlgebraError::InvalidDomainSize(7)));
+    }
+}
@@ -0,0 +1,55 @@
+//! `proptest` strategies for the algebra types, enabled with the `test-utils` feature.
+use crate::finite_field::{FieldElement, FiniteField};
+use crate::polynomial::Polynomial;
+use proptest::prelude::*;
+use std::sync::Arc;
+
+/// uniformly chosen element of the field
+pub fn field_element(finite_field: Arc<FiniteField>) -> impl Strategy<Value = FieldElement> {
+    (0..finite_field.prime).prop_map(move |value| finite_field.element(value))
+}
+
+pub fn nonzero_field_element(
+    finite_field: Arc<FiniteField>,
+) -> impl Strategy<Value = FieldElement> {
+    (1..finite_field.prime).prop_map(move |value| finite_field.element(value))
+}
+
+/// polynomial of degree at most `max_degree`, without trailing zero coefficients
+pub fn polynomial(
+    finite_field: Arc<FiniteField>,
+    max_degree: usize,
+) -> impl Strategy<Value = Polynomial> {
+    let prime = finite_field.prime;
+    (prop::collection::vec(0..prime, 0..=max_degree), 0..prime).prop_map(
+        move |(mut coefficients, leading)| {
+            if leading != 0 {
+                coefficients.push(leading);
+            } else {
+                // the zero polynomial
+                coefficients.clear();
+            }
+            Polynomial::from_slice(&coefficients, Arc::clone(&finite_field))
+        },
+    )
+}
+
+pub fn nonzero_polynomial(
+    finite_field: Arc<FiniteField>,
+    max_degree: usize,
+) -> impl Strategy<Value = Polynomial> {
+    polynomial(finite_field, max_degree)
+        .prop_filter("zero polynomial", |p| !p.coefficients.is_empty())
+}
+
+/// trace table of `width` columns and a power of two number of rows (at most 2^max_log_height)
+pub fn trace_table(
+    finite_field: Arc<FiniteField>,
+    width: usize,
+    max_log_height: u32,
+) -> impl Strategy<Value = Vec<Vec<FieldElement>>> {
+    (0..=max_log_height).prop_flat_map(move |log_height| {
+        let row = prop::collection::vec(field_element(Arc::clone(&finite_field)), width);
+        prop::collection::vec(row, 1usize << log_height)
+    })
+}
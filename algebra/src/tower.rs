@@ -0,0 +1,470 @@
+//! Extensions `F[x] / (m(x))` of any degree over any [`Field`], so extensions can be stacked into towers.
+//!
+//! A degree `n` extension of a field of size `q` has `q^n` elements, so a random challenge drawn from
+//! it hits one of the `d` roots of a bad polynomial with probability `d / q^n` instead of `d / q`.
+//! For BabyBear (`q ≈ 2^31`) the quartic extension gets this below `2^-100`.
+use crate::error::AlgebraError;
+use crate::field::Field;
+use crate::finite_field::{prime_factors, FieldElement, FieldSize, FiniteField};
+use crate::polynomial::Polynomial;
+use rand::Rng;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Mul, Neg, Sub};
+use std::sync::Arc;
+
+/// `F[x] / (m(x))` for a monic irreducible modulus `m` of degree `n >= 2`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtensionField<F: Field = FieldElement> {
+    /// coefficients of `m`, lowest degree first, the last one is 1
+    modulus: Vec<F>,
+}
+
+impl<F: Field> ExtensionField<F> {
+    /// The irreducibility of the modulus can't be checked for an arbitrary base field, reducible moduli
+    /// give a ring in which some inversions fail with [`AlgebraError::InvalidModulus`].
+    pub fn new(modulus: Vec<F>) -> Result<Self, AlgebraError> {
+        let modulus = trim(modulus);
+        match modulus.last() {
+            Some(leading) if modulus.len() > 2 && *leading == leading.one() => Ok(Self { modulus }),
+            _ => Err(AlgebraError::InvalidModulus),
+        }
+    }
+
+    pub fn degree(&self) -> usize {
+        self.modulus.len() - 1
+    }
+
+    pub fn modulus(&self) -> &[F] {
+        &self.modulus
+    }
+
+    fn base_zero(&self) -> F {
+        self.modulus[0].zero()
+    }
+
+    /// `Σ coefficients[i] * x^i`, reduced modulo `m`
+    pub fn element(self: &Arc<Self>, coefficients: Vec<F>) -> ExtensionElement<F> {
+        ExtensionElement {
+            coefficients: self.reduce(coefficients),
+            extension: Arc::clone(self),
+        }
+    }
+
+    pub fn from_base(self: &Arc<Self>, x: F) -> ExtensionElement<F> {
+        self.element(vec![x])
+    }
+
+    pub fn zero(self: &Arc<Self>) -> ExtensionElement<F> {
+        self.element(vec![])
+    }
+
+    pub fn one(self: &Arc<Self>) -> ExtensionElement<F> {
+        self.from_base(self.modulus[0].one())
+    }
+
+    /// the class of `x`, a root of the modulus
+    pub fn root(self: &Arc<Self>) -> ExtensionElement<F> {
+        self.element(vec![self.base_zero(), self.modulus[0].one()])
+    }
+
+    pub fn random_element(self: &Arc<Self>) -> ExtensionElement<F> {
+        self.sample(&mut rand::thread_rng())
+    }
+
+    /// uniform element drawn from `rng`, one base field coefficient at a time
+    pub fn sample<R: Rng + ?Sized>(self: &Arc<Self>, rng: &mut R) -> ExtensionElement<F> {
+        let coefficients = (0..self.degree())
+            .map(|_| self.modulus[0].sample(rng))
+            .collect();
+        self.element(coefficients)
+    }
+
+    /// Reduces modulo `m` using `x^n = -Σ m_i x^i`, and pads the result to `n` coefficients.
+    fn reduce(&self, mut coefficients: Vec<F>) -> Vec<F> {
+        let n = self.degree();
+        while coefficients.len() > n {
+            let top = coefficients.pop().expect("more than n coefficients");
+            let shift = coefficients.len() - n;
+            for (i, m) in self.modulus[..n].iter().enumerate() {
+                coefficients[shift + i] = coefficients[shift + i].clone() - top.clone() * m.clone();
+            }
+        }
+        coefficients.resize(n, self.base_zero());
+        coefficients
+    }
+}
+
+impl ExtensionField<FieldElement> {
+    /// Checks the modulus with Rabin's test: a monic `m` of degree `n` over `Fp` is irreducible iff
+    /// `x^(p^n) = x (mod m)` and `gcd(x^(p^(n / r)) - x, m) = 1` for every prime `r` dividing `n`.
+    pub fn try_from_polynomial(modulus: &Polynomial) -> Result<Self, AlgebraError> {
+        let extension = Arc::new(Self::new(modulus.coefficients.clone())?);
+        let prime = modulus.finite_field().prime as u128;
+        let n = extension.degree();
+        let x = extension.root();
+
+        // x^(p^k) for k = 0..=n
+        let mut frobenius = vec![x.clone()];
+        for k in 0..n {
+            frobenius.push(frobenius[k].pow(prime));
+        }
+        if frobenius[n] != x {
+            return Err(AlgebraError::InvalidModulus);
+        }
+        for r in prime_factors(n as u128) {
+            let r = r as usize;
+            let difference = trim((&frobenius[n / r] - &x).coefficients);
+            let gcd = poly_gcd(extension.modulus.clone(), difference)?;
+            if gcd.len() != 1 {
+                return Err(AlgebraError::InvalidModulus);
+            }
+        }
+        Ok(Arc::unwrap_or_clone(extension))
+    }
+
+    /// `x^3 - β`, irreducible when β is not a cube
+    pub fn cubic(base: &Arc<FiniteField>, non_residue: FieldSize) -> Result<Self, AlgebraError> {
+        Self::binomial(base, 3, non_residue)
+    }
+
+    /// `x^n - β`
+    pub fn binomial(
+        base: &Arc<FiniteField>,
+        degree: usize,
+        non_residue: FieldSize,
+    ) -> Result<Self, AlgebraError> {
+        let mut coefficients = vec![base.zero(); degree + 1];
+        coefficients[0] = (-base.element(non_residue).abs()).abs();
+        coefficients[degree] = base.one();
+        Self::try_from_polynomial(&Polynomial::new(coefficients, Arc::clone(base)))
+    }
+}
+
+/// element of an [`ExtensionField`], always `n` coefficients over the base field
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtensionElement<F: Field = FieldElement> {
+    coefficients: Vec<F>,
+    extension: Arc<ExtensionField<F>>,
+}
+
+impl<F: Field> ExtensionElement<F> {
+    pub fn coefficients(&self) -> &[F] {
+        &self.coefficients
+    }
+
+    pub fn extension(&self) -> &Arc<ExtensionField<F>> {
+        &self.extension
+    }
+
+    /// the element is in the base field when only its constant coefficient is non-zero
+    pub fn to_base(&self) -> Option<F> {
+        self.coefficients[1..]
+            .iter()
+            .all(F::is_zero)
+            .then(|| self.coefficients[0].clone())
+    }
+
+    pub fn inverse(&self) -> Self {
+        self.try_inverse()
+            .expect("Cannot compute the inverse of zero")
+    }
+
+    /// extended Euclid on the representative and the modulus
+    pub fn try_inverse(&self) -> Result<Self, AlgebraError> {
+        let zero = self.extension.base_zero();
+        let one = zero.one();
+        let mut r0 = self.extension.modulus.clone();
+        let mut r1 = trim(self.coefficients.clone());
+        if r1.is_empty() {
+            return Err(AlgebraError::DivisionByZero);
+        }
+        let (mut s0, mut s1) = (vec![], vec![one]);
+        while !r1.is_empty() {
+            let (quotient, remainder) = poly_div_rem(&r0, &r1)?;
+            let next = poly_sub(&s0, &poly_mul(&quotient, &s1));
+            r0 = std::mem::replace(&mut r1, remainder);
+            s0 = std::mem::replace(&mut s1, next);
+        }
+        // r0 is the gcd, a non-zero constant unless the modulus is reducible
+        if r0.len() != 1 {
+            return Err(AlgebraError::InvalidModulus);
+        }
+        let gcd_inv = r0[0].try_inverse()?;
+        let coefficients = s0.into_iter().map(|s| s * gcd_inv.clone()).collect();
+        Ok(self.extension.element(coefficients))
+    }
+
+    pub fn pow(&self, mut exponent: u128) -> Self {
+        let mut result = self.extension.one();
+        let mut square = self.clone();
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = &result * &square;
+            }
+            square = &square * &square;
+            exponent >>= 1;
+        }
+        result
+    }
+}
+
+impl<F: Field> Display for ExtensionElement<F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let terms: Vec<String> = self.coefficients.iter().map(F::to_string).collect();
+        write!(f, "[{}]", terms.join(", "))
+    }
+}
+
+impl<F: Field> Add for &ExtensionElement<F> {
+    type Output = ExtensionElement<F>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        assert!(
+            Arc::ptr_eq(&self.extension, &rhs.extension),
+            "Cannot add elements from different extension fields"
+        );
+        ExtensionElement {
+            coefficients: self
+                .coefficients
+                .iter()
+                .zip(&rhs.coefficients)
+                .map(|(a, b)| a.clone() + b.clone())
+                .collect(),
+            extension: Arc::clone(&self.extension),
+        }
+    }
+}
+
+impl<F: Field> Add for ExtensionElement<F> {
+    type Output = ExtensionElement<F>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        &self + &rhs
+    }
+}
+
+impl<F: Field> Sub for &ExtensionElement<F> {
+    type Output = ExtensionElement<F>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        assert!(
+            Arc::ptr_eq(&self.extension, &rhs.extension),
+            "Cannot sub elements from different extension fields"
+        );
+        ExtensionElement {
+            coefficients: self
+                .coefficients
+                .iter()
+                .zip(&rhs.coefficients)
+                .map(|(a, b)| a.clone() - b.clone())
+                .collect(),
+            extension: Arc::clone(&self.extension),
+        }
+    }
+}
+
+impl<F: Field> Sub for ExtensionElement<F> {
+    type Output = ExtensionElement<F>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        &self - &rhs
+    }
+}
+
+impl<F: Field> Mul for &ExtensionElement<F> {
+    type Output = ExtensionElement<F>;
+
+    /// schoolbook product followed by the reduction modulo `m`
+    fn mul(self, rhs: Self) -> Self::Output {
+        assert!(
+            Arc::ptr_eq(&self.extension, &rhs.extension),
+            "Cannot mul elements from different extension fields"
+        );
+        self.extension
+            .element(poly_mul(&self.coefficients, &rhs.coefficients))
+    }
+}
+
+impl<F: Field> Mul for ExtensionElement<F> {
+    type Output = ExtensionElement<F>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        &self * &rhs
+    }
+}
+
+impl<F: Field> Neg for ExtensionElement<F> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            coefficients: self.coefficients.into_iter().map(F::neg).collect(),
+            extension: self.extension,
+        }
+    }
+}
+
+impl<F: Field> Field for ExtensionElement<F> {
+    fn zero(&self) -> Self {
+        self.extension.zero()
+    }
+
+    fn one(&self) -> Self {
+        self.extension.one()
+    }
+
+    fn try_inverse(&self) -> Result<Self, AlgebraError> {
+        ExtensionElement::try_inverse(self)
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self {
+        self.extension.sample(rng)
+    }
+
+    fn in_field_of(&self, other: &Self) -> Self {
+        let base = other.extension.modulus.last().expect("a monic modulus");
+        Self {
+            coefficients: self
+                .coefficients
+                .iter()
+                .map(|c| c.in_field_of(base))
+                .collect(),
+            extension: Arc::clone(&other.extension),
+        }
+    }
+}
+
+fn trim<F: Field>(mut coefficients: Vec<F>) -> Vec<F> {
+    while coefficients.last().is_some_and(F::is_zero) {
+        coefficients.pop();
+    }
+    coefficients
+}
+
+fn poly_mul<F: Field>(a: &[F], b: &[F]) -> Vec<F> {
+    let (Some(first), false) = (a.first(), b.is_empty()) else {
+        return vec![];
+    };
+    let mut result = vec![first.zero(); a.len() + b.len() - 1];
+    for (i, a) in a.iter().enumerate() {
+        for (j, b) in b.iter().enumerate() {
+            result[i + j] = result[i + j].clone() + a.clone() * b.clone();
+        }
+    }
+    result
+}
+
+fn poly_sub<F: Field>(a: &[F], b: &[F]) -> Vec<F> {
+    let mut result = a.to_vec();
+    for (i, b) in b.iter().enumerate() {
+        if i < result.len() {
+            result[i] = result[i].clone() - b.clone();
+        } else {
+            result.push(-b.clone());
+        }
+    }
+    trim(result)
+}
+
+/// `b` must be trimmed and non-zero
+fn poly_div_rem<F: Field>(a: &[F], b: &[F]) -> Result<(Vec<F>, Vec<F>), AlgebraError> {
+    let leading_inv = b
+        .last()
+        .ok_or(AlgebraError::DivisionByZero)?
+        .try_inverse()?;
+    let mut remainder = a.to_vec();
+    if a.len() < b.len() {
+        return Ok((vec![], trim(remainder)));
+    }
+    let mut quotient = vec![leading_inv.zero(); a.len() - b.len() + 1];
+    for i in (0..quotient.len()).rev() {
+        let coefficient = remainder[i + b.len() - 1].clone() * leading_inv.clone();
+        for (j, b) in b.iter().enumerate() {
+            remainder[i + j] = remainder[i + j].clone() - coefficient.clone() * b.clone();
+        }
+        quotient[i] = coefficient;
+    }
+    Ok((trim(quotient), trim(remainder)))
+}
+
+fn poly_gcd<F: Field>(mut a: Vec<F>, mut b: Vec<F>) -> Result<Vec<F>, AlgebraError> {
+    while !b.is_empty() {
+        let (_, remainder) = poly_div_rem(&a, &b)?;
+        a = std::mem::replace(&mut b, remainder);
+    }
+    Ok(a)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::AlgebraError;
+    use crate::field::Field;
+    use crate::finite_field::FiniteField;
+    use crate::poly;
+    use crate::tower::{ExtensionElement, ExtensionField};
+    use std::sync::Arc;
+
+    const BABY_BEAR_PRIME: i128 = 15 * (1 << 27) + 1;
+
+    #[test]
+    fn test_cubic_extension() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let extension = Arc::new(ExtensionField::cubic(&finite_field, 5).unwrap());
+        let x = extension.root();
+        assert_eq!(x.pow(3), extension.from_base(finite_field.element(5)));
+
+        let y = extension.element(finite_field.elements([13, 42, 7]).collect());
+        assert_eq!(&y * &y.inverse(), extension.one());
+        assert_eq!(y.pow(97 * 97 * 97 - 1), extension.one());
+        assert_eq!(
+            extension.zero().try_inverse(),
+            Err(AlgebraError::DivisionByZero)
+        );
+
+        // 8 = 2^3 is a cube, x^3 - 8 has the root 2
+        assert_eq!(
+            ExtensionField::cubic(&finite_field, 8),
+            Err(AlgebraError::InvalidModulus)
+        );
+    }
+
+    #[test]
+    fn test_baby_bear_quartic_extension() {
+        let finite_field = Arc::new(FiniteField::new(BABY_BEAR_PRIME, 31));
+        let extension = Arc::new(ExtensionField::binomial(&finite_field, 4, 11).unwrap());
+        assert_eq!(extension.degree(), 4);
+        let y = extension.element(finite_field.elements([1, 2, 3, 4]).collect());
+        assert_eq!(&y * &y.inverse(), extension.one());
+
+        // x^4 - 16 = (x^2 - 4)(x^2 + 4)
+        assert_eq!(
+            ExtensionField::binomial(&finite_field, 4, 16),
+            Err(AlgebraError::InvalidModulus)
+        );
+        // a product of two quadratics passes the first check and is caught by the gcd
+        let reducible = &poly!(finite_field, [-11, 0, 1]) * &poly!(finite_field, [-13, 0, 1]);
+        assert_eq!(
+            ExtensionField::try_from_polynomial(&reducible),
+            Err(AlgebraError::InvalidModulus)
+        );
+    }
+
+    #[test]
+    fn test_tower() {
+        // Fp2 = Fp[u] / (u^2 - 5), then Fp6 = Fp2[v] / (v^3 - u)
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let fp2 = Arc::new(ExtensionField::binomial(&finite_field, 2, 5).unwrap());
+        let u = fp2.root();
+        let fp6: Arc<ExtensionField<ExtensionElement>> =
+            Arc::new(ExtensionField::new(vec![-u.clone(), u.zero(), u.zero(), u.one()]).unwrap());
+
+        let v = fp6.root();
+        assert_eq!(v.pow(3), fp6.from_base(u.clone()));
+        let y = fp6.element(vec![u.clone(), u.one(), &u + &u.one()]);
+        assert_eq!(&y * &y.inverse(), fp6.one());
+        assert_eq!(
+            Field::square(&v),
+            fp6.element(vec![u.zero(), u.zero(), u.one()])
+        );
+    }
+}
@@ -0,0 +1,181 @@
+use crate::error::AlgebraError;
+use crate::finite_field::{FieldElement, FieldSize, FiniteField};
+use crate::subgroup::Subgroup;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Powers of a primitive `size`-th root of unity ω and of its inverse.
+#[derive(Debug, Clone)]
+pub struct Twiddles {
+    /// ω^0, ω^1, ..., ω^(size - 1)
+    pub roots: Vec<FieldElement>,
+    /// ω^0, ω^-1, ..., ω^-(size - 1)
+    pub inverse_roots: Vec<FieldElement>,
+}
+
+impl Twiddles {
+    /// ω is `generator^((p - 1) / size)`, so the field generator must generate the multiplicative group
+    pub fn new(finite_field: &Arc<FiniteField>, size: usize) -> Result<Self, AlgebraError> {
+        let order = finite_field.prime - 1;
+        if !size.is_power_of_two() || order % size as FieldSize != 0 {
+            return Err(AlgebraError::InvalidDomainSize(size));
+        }
+        Ok(Self::from_subgroup(&Subgroup::new(finite_field, size)?))
+    }
+
+    /// powers of the generator of the subgroup, which may have any order
+    pub fn from_subgroup(subgroup: &Subgroup) -> Self {
+        let finite_field = subgroup.finite_field();
+        let omega = subgroup.generator();
+        Self {
+            roots: powers(finite_field, omega, subgroup.order()),
+            inverse_roots: powers(finite_field, &omega.inverse(), subgroup.order()),
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.roots.len()
+    }
+
+    fn finite_field(&self) -> &Arc<FiniteField> {
+        self.roots[0].finite_field()
+    }
+}
+
+fn powers(finite_field: &Arc<FiniteField>, base: &FieldElement, size: usize) -> Vec<FieldElement> {
+    let mut powers = Vec::with_capacity(size);
+    let mut current = finite_field.one();
+    for _ in 0..size {
+        powers.push(current.clone());
+        current = &current * base;
+    }
+    powers
+}
+
+/// Root of unity tables keyed by (field, domain size), shared between transforms over the same domain.
+#[derive(Debug, Default)]
+pub struct TwiddleCache {
+    tables: HashMap<(FieldSize, FieldSize, usize), Arc<Twiddles>>,
+}
+
+impl TwiddleCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Computes the tables on the first request for a domain size. A table built for another
+    /// `Arc` of an equal field is rebuilt, since field elements only combine within the same `Arc`.
+    pub fn get(
+        &mut self,
+        finite_field: &Arc<FiniteField>,
+        size: usize,
+    ) -> Result<Arc<Twiddles>, AlgebraError> {
+        let key = (finite_field.prime, finite_field.generator, size);
+        if let Some(twiddles) = self.tables.get(&key) {
+            if Arc::ptr_eq(twiddles.finite_field(), finite_field) {
+                return Ok(Arc::clone(twiddles));
+            }
+        }
+        let twiddles = Arc::new(Twiddles::new(finite_field, size)?);
+        self.tables.insert(key, Arc::clone(&twiddles));
+        Ok(twiddles)
+    }
+
+    /// The tables of the generator of `subgroup`, cached like `get` by the order of the subgroup. A
+    /// subgroup generated by another root of the same order gets its own tables in place of the cached
+    /// ones.
+    pub fn get_for_subgroup(&mut self, subgroup: &Subgroup) -> Arc<Twiddles> {
+        let finite_field = subgroup.finite_field();
+        let key = (finite_field.prime, finite_field.generator, subgroup.order());
+        if let Some(twiddles) = self.tables.get(&key) {
+            if Arc::ptr_eq(twiddles.finite_field(), finite_field)
+                && twiddles
+                    .roots
+                    .get(1)
+                    .is_none_or(|omega| omega == subgroup.generator())
+            {
+                return Arc::clone(twiddles);
+            }
+        }
+        let twiddles = Arc::new(Twiddles::from_subgroup(subgroup));
+        self.tables.insert(key, Arc::clone(&twiddles));
+        twiddles
+    }
+
+    pub fn len(&self) -> usize {
+        self.tables.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tables.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.tables.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::AlgebraError;
+    use crate::finite_field::FiniteField;
+    use crate::subgroup::Subgroup;
+    use crate::twiddles::TwiddleCache;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_twiddles() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let mut cache = TwiddleCache::new();
+        let twiddles = cache.get(&finite_field, 8).unwrap();
+
+        assert_eq!(twiddles.size(), 8);
+        let omega = &twiddles.roots[1];
+        assert_eq!(omega * &twiddles.roots[7], finite_field.one());
+        assert_ne!(twiddles.roots[4], finite_field.one());
+        for (root, inverse_root) in twiddles.roots.iter().zip(&twiddles.inverse_roots) {
+            assert_eq!(root * inverse_root, finite_field.one());
+        }
+
+        assert!(Arc::ptr_eq(
+            &twiddles,
+            &cache.get(&finite_field, 8).unwrap()
+        ));
+        assert_eq!(cache.len(), 1);
+
+        // equal field behind another Arc gets its own elements
+        let other_field = Arc::new(FiniteField::new(97, 5));
+        let other = cache.get(&other_field, 8).unwrap();
+        assert!(!Arc::ptr_eq(&twiddles, &other));
+        assert_eq!(other.roots, twiddles.roots);
+
+        assert_eq!(
+            cache.get(&finite_field, 6).err(),
+            Some(AlgebraError::InvalidDomainSize(6))
+        );
+        assert_eq!(
+            cache.get(&finite_field, 64).err(),
+            Some(AlgebraError::InvalidDomainSize(64))
+        );
+    }
+
+    #[test]
+    fn test_twiddles_for_subgroup() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let mut cache = TwiddleCache::new();
+        let subgroup = Subgroup::new(&finite_field, 8).unwrap();
+        let twiddles = cache.get_for_subgroup(&subgroup);
+        assert_eq!(&twiddles.roots[1], subgroup.generator());
+        // the same tables as by size
+        assert!(Arc::ptr_eq(
+            &twiddles,
+            &cache.get(&finite_field, 8).unwrap()
+        ));
+        assert!(Arc::ptr_eq(&twiddles, &cache.get_for_subgroup(&subgroup)));
+
+        // the squares of the roots of order 16 generate the subgroup of order 8
+        let squared = Subgroup::new(&finite_field, 16).unwrap().square().unwrap();
+        assert!(Arc::ptr_eq(&twiddles, &cache.get_for_subgroup(&squared)));
+        assert_eq!(cache.len(), 1);
+    }
+}
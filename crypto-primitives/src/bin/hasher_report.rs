@@ -0,0 +1,96 @@
+//! Hashes the same inputs with every hasher and commits them to a Merkle tree.
+//!
+//! Usage: `cargo run --release --bin hasher_report -- [prime] [log2 of the number of leafs]`
+use algebra::finite_field::{FieldElement, FieldSize, FiniteField};
+use algebra::matrix::Matrix;
+use crypto_primitives::hash::{Blake3Hasher, Hasher, PoseidonHash, RescueHash, Sha256Hasher};
+use crypto_primitives::merkle_tree::MerkleTree;
+use std::hint::black_box;
+use std::sync::Arc;
+use std::time::Instant;
+
+const HASH_INPUTS: usize = 1 << 12;
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let prime: FieldSize = args
+        .next()
+        .map(|arg| arg.parse().expect("the prime must be an integer"))
+        .unwrap_or(97);
+    let log_leafs: u32 = args
+        .next()
+        .map(|arg| {
+            arg.parse()
+                .expect("log2 of the number of leafs must be an integer")
+        })
+        .unwrap_or(10);
+
+    let finite_field = Arc::new(FiniteField::from_prime(prime).unwrap());
+    let alpha = (3..)
+        .find(|alpha| FiniteField::extended_euclidean(*alpha, prime - 1).0 == 1)
+        .unwrap();
+
+    let rescue = RescueHash::builder(Arc::clone(&finite_field))
+        .alpha(alpha)
+        .mds_matrix(random_matrix(&finite_field))
+        .round_constants(random_constants(&finite_field, 108))
+        .build()
+        .expect("valid Rescue parameters");
+    let poseidon = PoseidonHash::new(
+        Arc::clone(&finite_field),
+        alpha,
+        8,
+        22,
+        random_matrix(&finite_field),
+        random_constants(&finite_field, 60),
+    )
+    .expect("valid Poseidon parameters");
+
+    let inputs: Vec<FieldElement> = (0..HASH_INPUTS.max(1 << log_leafs))
+        .map(|_| finite_field.random_element())
+        .collect();
+
+    println!(
+        "field p = {}, alpha = {}, {} hashes, {} Merkle leafs",
+        prime,
+        alpha,
+        HASH_INPUTS,
+        1 << log_leafs
+    );
+    println!(
+        "{:<10} {:>16} {:>20}",
+        "hasher", "hashes / s", "merkle commit (ms)"
+    );
+    report("Rescue", rescue, &inputs, log_leafs);
+    report("Poseidon", poseidon, &inputs, log_leafs);
+    report("Blake3", Blake3Hasher, &inputs, log_leafs);
+    report("SHA-256", Sha256Hasher, &inputs, log_leafs);
+}
+
+fn random_matrix(finite_field: &Arc<FiniteField>) -> Matrix {
+    let rows = (0..2).map(|_| random_constants(finite_field, 2)).collect();
+    Matrix::from_rows(rows, finite_field.zero()).expect("the rows have the same length")
+}
+
+fn random_constants(finite_field: &Arc<FiniteField>, len: usize) -> Vec<FieldElement> {
+    (0..len).map(|_| finite_field.random_element()).collect()
+}
+
+fn report<H: Hasher + Clone>(name: &str, hasher: H, inputs: &[FieldElement], log_leafs: u32) {
+    let start = Instant::now();
+    for input in &inputs[..HASH_INPUTS] {
+        black_box(hasher.hash(input.clone()));
+    }
+    let hashes_per_second = HASH_INPUTS as f64 / start.elapsed().as_secs_f64();
+
+    let leafs = inputs[..1 << log_leafs].to_vec();
+    let start = Instant::now();
+    let mut tree = MerkleTree::new(hasher, leafs).expect("at least one leaf");
+    black_box(tree.commit());
+    let commit_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+    println!(
+        "{:<10} {:>16.0} {:>20.3}",
+        name, hashes_per_second, commit_ms
+    );
+}
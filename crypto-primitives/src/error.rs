@@ -0,0 +1,109 @@
+use algebra::finite_field::FieldSize;
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum CommitmentError {
+    #[error("the list doesn't contain any elements")]
+    EmptyLeafs,
+    #[error("the tree has not been committed yet")]
+    NotCommitted,
+    #[error("the element is not a leaf of the tree")]
+    LeafNotFound,
+    #[error("the proof is empty")]
+    EmptyProof,
+    #[error("leaf index {index} is out of range for {len} leafs")]
+    IndexOutOfRange { index: usize, len: usize },
+    #[error("the path has {found} siblings for a tree of depth {expected}")]
+    PathLengthMismatch { expected: usize, found: usize },
+    #[error("a sparse tree has between 1 and 128 levels, not {0}")]
+    InvalidDepth(usize),
+    #[error("key {key} doesn't fit in {depth} bits")]
+    KeyOutOfRange { key: u128, depth: usize },
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum WireError {
+    #[error("unsupported wire format version {0}")]
+    UnsupportedVersion(u8),
+    #[error("expected a message of kind {expected}, found {found}")]
+    UnexpectedTag { expected: u8, found: u8 },
+    #[error("the message ended unexpectedly")]
+    Truncated,
+    #[error("varint is not minimally encoded or overflows")]
+    NonCanonicalVarint,
+    #[error("field element is not reduced modulo the field prime")]
+    NonCanonicalElement,
+    #[error("the message was encoded for the field of modulus {0}")]
+    ModulusMismatch(u128),
+    #[error("{0} unexpected trailing bytes")]
+    TrailingBytes(usize),
+    #[error("the padding bits of a packed slice must be zero")]
+    NonZeroPadding,
+    #[error("presence flag {0} is neither 0 nor 1")]
+    InvalidFlag(u8),
+    #[error("digests must have at least one element")]
+    EmptyDigest,
+    #[error(transparent)]
+    InvalidOptions(#[from] ProofOptionsError),
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ProofOptionsError {
+    #[error("blowup factor {0} is not a power of 2 greater than 1")]
+    InvalidBlowup(usize),
+    #[error("at least one query is required")]
+    NoQueries,
+    #[error("{0} grinding bits exceed the supported maximum")]
+    TooManyGrindingBits(u8),
+    #[error("unsupported folding factor {0}")]
+    InvalidFoldingFactor(usize),
+    #[error("FRI remainder degree {0} is not one less than a power of 2 up to 255")]
+    InvalidRemainderDegree(usize),
+    #[error("{0} is not a valid field modulus")]
+    InvalidField(FieldSize),
+    #[error("unknown hash kind {0}")]
+    UnknownHashKind(u8),
+    #[error("unknown DEEP batching mode {0}")]
+    UnknownDeepBatching(u8),
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum HashParameterError {
+    #[error("missing hash parameter `{0}`")]
+    MissingParameter(&'static str),
+    #[error("rate and capacity must be at least 1, got rate {rate} and capacity {capacity}")]
+    InvalidStateSize { rate: usize, capacity: usize },
+    #[error(
+        "the MDS matrix must be {expected}x{expected} to match rate + capacity, got {rows}x{cols}"
+    )]
+    MdsDimension {
+        expected: usize,
+        rows: usize,
+        cols: usize,
+    },
+    #[error(
+        "alpha {alpha} is not invertible modulo p - 1 = {p_minus_one}, gcd(alpha, p - 1) must be 1"
+    )]
+    InvalidAlpha {
+        alpha: FieldSize,
+        p_minus_one: FieldSize,
+    },
+    #[error("{rounds} rounds need {expected} round constants, got {found}")]
+    ConstantsLength {
+        rounds: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum TranscriptError {
+    #[error("expected transcript step {expected}, found {found}")]
+    UnexpectedLabel { expected: String, found: String },
+    #[error("transcript step {0} comes after the end of the schedule")]
+    ScheduleExhausted(String),
+    #[error("transcript ended before step {0}")]
+    IncompleteSchedule(String),
+    #[error("failed to write the round log: {0}")]
+    Log(String),
+}
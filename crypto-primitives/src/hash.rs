@@ -1,127 +1,782 @@
+use crate::error::HashParameterError;
+use algebra::big_field::{BigFieldElement, U256};
+use algebra::field::Field;
 use algebra::finite_field::{FieldElement, FieldSize, FiniteField};
-use ndarray::{arr1, array, s, Array1, Array2, Axis};
-use std::rc::Rc;
+use algebra::matrix::Matrix;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
 
-pub trait Hasher {
-    fn hash(&self, value: FieldElement) -> FieldElement;
+pub trait Hasher<F: Field = FieldElement> {
+    fn hash(&self, value: F) -> F;
+
+    /// Number of elements of the digests making up the nodes of Merkle trees, squeezed by
+    /// `hash_elements`. One by default, see [`WideDigest`] for more.
+    fn digest_len(&self) -> usize {
+        1
+    }
+
+    /// Digest of `output_len` elements for a message of one or more elements.
+    ///
+    /// The default chains `hash` for absorbing and squeezing, so its collision resistance stays that of a
+    /// single element whatever `output_len` is. Hashers with a wider state override it.
+    fn hash_elements(&self, inputs: &[F], output_len: usize) -> Vec<F> {
+        assert!(!inputs.is_empty(), "At least one input is required");
+        let mut state = self.hash(inputs[0].clone());
+        for input in &inputs[1..] {
+            state = self.hash(state.in_field_of(input) + input.clone());
+        }
+        let mut digest = Vec::with_capacity(output_len);
+        for _ in 0..output_len {
+            digest.push(state.clone());
+            state = self.hash(state);
+        }
+        digest
+    }
 }
 
+/// `H` squeezing Merkle digests of `digest_len` elements, for collision resistance beyond a single
+/// element of a small field. Only hashers overriding [`Hasher::hash_elements`] with a wider state
+/// gain from it; the default chaining stays single-element strength.
 #[derive(Clone)]
-pub struct RescueHash {
-    alpha: FieldElement,
-    alpha_inv: FieldElement,
-    finite_field: Rc<FiniteField>,
-    rate: usize,
-    capacity: usize,
-    mds_matrix: Array2<FieldElement>,
-    constants: Array1<FieldElement>,
+pub struct WideDigest<H> {
+    hasher: H,
+    digest_len: usize,
 }
 
-impl Hasher for RescueHash {
-    fn hash(&self, value: FieldElement) -> FieldElement {
-        let state_len: usize = self.rate + self.capacity;
-        let t: Array1<FieldElement> = arr1(&[self.finite_field.zero()])
-            * arr1(&[self.finite_field.element(state_len as FieldSize)]);
+impl<H> WideDigest<H> {
+    pub fn new(hasher: H, digest_len: usize) -> Self {
+        assert!(digest_len > 0, "A digest has at least one element");
+        Self { hasher, digest_len }
+    }
+}
 
-        let mut state: Array1<FieldElement> = arr1(&[value]);
-        state
-            .append(Axis(0), t.slice(s![..]))
-            .expect("Can't append");
+impl<F: Field, H: Hasher<F>> Hasher<F> for WideDigest<H> {
+    fn hash(&self, value: F) -> F {
+        self.hasher.hash(value)
+    }
 
-        state.map(|x| x.pow(&self.alpha)); // S-box function
+    fn digest_len(&self) -> usize {
+        self.digest_len
+    }
 
-        // round 1
-        let mut temp = Array1::<FieldElement>::from_elem(state_len, self.finite_field.zero());
+    fn hash_elements(&self, inputs: &[F], output_len: usize) -> Vec<F> {
+        self.hasher.hash_elements(inputs, output_len)
+    }
+}
 
-        for i in 0..state_len {
-            for j in 0..state_len {
-                temp[i] = &temp[i] + &(&self.mds_matrix[[i, j]] * &state[j]);
-            }
+/// `x^exponent` by square and multiply, for element types without their own `pow`
+fn power<F: Field>(x: &F, exponent: u128) -> F {
+    let mut result = x.one();
+    let mut square = x.clone();
+    let mut exponent = exponent;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * square.clone();
         }
+        square = square.square();
+        exponent >>= 1;
+    }
+    result
+}
 
-        for (i, el) in &mut state.iter_mut().enumerate() {
-            *el = &temp[i] + &self.constants[2 * self.rate * state_len + i].abs();
-        }
+#[derive(Clone)]
+pub struct RescueHash<F: Field = FieldElement> {
+    alpha: u128,
+    alpha_inv: u128,
+    rate: usize,
+    capacity: usize,
+    rounds: usize,
+    mds_matrix: Matrix<F>,
+    constants: Vec<F>,
+}
 
-        state.map(|x| x.pow(&self.alpha_inv)); // S-box function
-                                               // round 2
-        let mut temp = Array1::<FieldElement>::from_elem(state_len, self.finite_field.zero());
+impl<F: Field> Hasher<F> for RescueHash<F> {
+    fn hash(&self, value: F) -> F {
+        let state_len: usize = self.rate + self.capacity;
+        let mut state = vec![self.constants[0].zero(); state_len];
+        state[0] = value.in_field_of(&self.constants[0]);
 
-        for i in 0..state_len {
-            for j in 0..state_len {
-                temp[i] = &temp[i] + &(&self.mds_matrix[[i, j]] * &state[j]);
+        // every round is two steps, S-box then MDS and constants, the second S-box with the inverse
+        // exponent; each step has its own `state_len` constants
+        for step in 0..2 * self.rounds {
+            let exponent = if step % 2 == 0 {
+                self.alpha
+            } else {
+                self.alpha_inv
+            };
+            for el in state.iter_mut() {
+                *el = power(el, exponent);
             }
-        }
 
-        for (i, el) in &mut state.iter_mut().enumerate() {
-            *el = &temp[i] + &self.constants[2 * self.rate * state_len + i].abs();
+            let temp = self
+                .mds_matrix
+                .mul_vector(&state)
+                .expect("the MDS matrix matches the state");
+            for (i, el) in state.iter_mut().enumerate() {
+                *el = temp[i].clone() + self.constants[step * state_len + i].clone();
+            }
         }
 
         state[0].clone()
     }
 }
 
+impl<F: Field> RescueHash<F> {
+    /// Rescue over any field from its exponents, without checks: `alpha_inv` must invert `alpha` modulo
+    /// the order of the multiplicative group, and there must be two constants per state element for
+    /// each of the `rounds` rounds. [`RescueHash::builder`] checks them for [`FieldElement`].
+    pub fn with_exponents(
+        rate: usize,
+        capacity: usize,
+        rounds: usize,
+        alpha: u128,
+        alpha_inv: u128,
+        mds_matrix: Matrix<F>,
+        constants: Vec<F>,
+    ) -> Self {
+        Self {
+            alpha,
+            alpha_inv,
+            rate,
+            capacity,
+            rounds,
+            mds_matrix,
+            constants,
+        }
+    }
+}
+
 impl Default for RescueHash {
     fn default() -> Self {
-        let finite_field = Rc::new(FiniteField::new(97, 1));
+        let finite_field = Arc::new(FiniteField::new(97, 5));
         let alpha = finite_field.element(5);
-        let mds_matrix = array![
-            [finite_field.random_element(), finite_field.random_element()],
-            [finite_field.random_element(), finite_field.random_element()],
-        ];
-        let constants = Array1::from_elem(108, finite_field.random_element());
-        RescueHash::new(Rc::clone(&finite_field), 1, 1, alpha, mds_matrix, constants)
+        let mds_matrix = Matrix::from_rows(
+            vec![
+                vec![finite_field.random_element(), finite_field.random_element()],
+                vec![finite_field.random_element(), finite_field.random_element()],
+            ],
+            finite_field.zero(),
+        )
+        .expect("the rows have the same length");
+        let constants = vec![finite_field.random_element(); 108];
+        RescueHash::builder(Arc::clone(&finite_field))
+            .alpha(alpha.value())
+            .mds_matrix(mds_matrix)
+            .round_constants(constants)
+            .build()
+            .expect("valid default parameters")
+    }
+}
+
+/// Builds a [`RescueHash`] checking that the parameters are consistent with each other.
+pub struct RescueHashBuilder {
+    finite_field: Arc<FiniteField>,
+    rate: usize,
+    capacity: usize,
+    rounds: usize,
+    alpha: Option<FieldSize>,
+    mds_matrix: Option<Matrix>,
+    constants: Option<Vec<FieldElement>>,
+}
+
+impl RescueHashBuilder {
+    pub const DEFAULT_ROUNDS: usize = 27;
+
+    pub fn new(finite_field: Arc<FiniteField>) -> Self {
+        Self {
+            finite_field,
+            rate: 1,
+            capacity: 1,
+            rounds: Self::DEFAULT_ROUNDS,
+            alpha: None,
+            mds_matrix: None,
+            constants: None,
+        }
+    }
+
+    pub fn rate(mut self, rate: usize) -> Self {
+        self.rate = rate;
+        self
+    }
+
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    pub fn rounds(mut self, rounds: usize) -> Self {
+        self.rounds = rounds;
+        self
+    }
+
+    pub fn alpha(mut self, alpha: FieldSize) -> Self {
+        self.alpha = Some(alpha);
+        self
+    }
+
+    pub fn mds_matrix(mut self, mds_matrix: Matrix) -> Self {
+        self.mds_matrix = Some(mds_matrix);
+        self
+    }
+
+    /// two constants per state element for each round
+    pub fn round_constants(mut self, constants: Vec<FieldElement>) -> Self {
+        self.constants = Some(constants);
+        self
+    }
+
+    pub fn build(self) -> Result<RescueHash, HashParameterError> {
+        if self.rate == 0 || self.capacity == 0 {
+            return Err(HashParameterError::InvalidStateSize {
+                rate: self.rate,
+                capacity: self.capacity,
+            });
+        }
+        let state_len = self.rate + self.capacity;
+
+        let alpha = self
+            .alpha
+            .ok_or(HashParameterError::MissingParameter("alpha"))?;
+        let p_minus_one = self.finite_field.prime - 1;
+        if alpha <= 1 || FiniteField::extended_euclidean(alpha, p_minus_one).0 != 1 {
+            return Err(HashParameterError::InvalidAlpha { alpha, p_minus_one });
+        }
+
+        let mds_matrix = self
+            .mds_matrix
+            .ok_or(HashParameterError::MissingParameter("mds_matrix"))?;
+        let (rows, cols) = mds_matrix.dim();
+        if rows != state_len || cols != state_len {
+            return Err(HashParameterError::MdsDimension {
+                expected: state_len,
+                rows,
+                cols,
+            });
+        }
+
+        let constants = self
+            .constants
+            .ok_or(HashParameterError::MissingParameter("round_constants"))?;
+        let expected = 2 * self.rounds * state_len;
+        if constants.len() != expected {
+            return Err(HashParameterError::ConstantsLength {
+                rounds: self.rounds,
+                expected,
+                found: constants.len(),
+            });
+        }
+
+        Ok(RescueHash::new(
+            Arc::clone(&self.finite_field),
+            self.rate,
+            self.capacity,
+            self.finite_field.element(alpha),
+            mds_matrix,
+            constants,
+        ))
     }
 }
 
 impl RescueHash {
+    pub fn builder(finite_field: Arc<FiniteField>) -> RescueHashBuilder {
+        RescueHashBuilder::new(finite_field)
+    }
+
+    /// Rescue over a [`FiniteField`], running as many rounds as there are constants for, two per state
+    /// element each.
     pub fn new(
-        finite_field: Rc<FiniteField>,
+        finite_field: Arc<FiniteField>,
         rate: usize,
         capacity: usize,
         alpha: FieldElement,
-        mds_matrix: Array2<FieldElement>,
-        constants: Array1<FieldElement>,
+        mds_matrix: Matrix,
+        constants: Vec<FieldElement>,
     ) -> Self {
-        assert_ne!(
-            (finite_field.prime - 1) % alpha.value(),
-            0,
-            "Alpha should not divide p-1"
-        );
-        let alpha_inv = alpha.inverse();
+        // x -> x^alpha is a permutation with inverse x -> x^alpha_inv when alpha * alpha_inv = 1
+        // modulo p - 1, the order of the multiplicative group
+        let p_minus_one = finite_field.prime - 1;
+        let (gcd, inverse, _) = FiniteField::extended_euclidean(alpha.value(), p_minus_one);
+        assert_eq!(gcd, 1, "Alpha should be coprime with p-1");
+        let alpha_inv = inverse.rem_euclid(p_minus_one);
 
-        Self {
-            alpha,
-            alpha_inv,
-            finite_field,
+        let rounds = constants.len() / (2 * (rate + capacity));
+        Self::with_exponents(
             rate,
             capacity,
+            rounds,
+            alpha.value() as u128,
+            alpha_inv as u128,
             mds_matrix,
             constants,
+        )
+    }
+}
+
+/// Poseidon over a state of `rate + capacity` elements: full S-box rounds around partial rounds that
+/// only apply the S-box to the first element.
+#[derive(Clone)]
+pub struct PoseidonHash {
+    alpha: FieldElement,
+    finite_field: Arc<FiniteField>,
+    full_rounds: usize,
+    partial_rounds: usize,
+    mds_matrix: Matrix,
+    constants: Vec<FieldElement>,
+}
+
+impl Hasher for PoseidonHash {
+    fn hash(&self, value: FieldElement) -> FieldElement {
+        let mut state = vec![self.finite_field.zero(); self.mds_matrix.rows()];
+        state[0] = self.finite_field.element(value.value());
+        self.permute(&mut state);
+        state[0].clone()
+    }
+
+    /// Sponge with the last state element as capacity, initialized with the message length.
+    fn hash_elements(&self, inputs: &[FieldElement], output_len: usize) -> Vec<FieldElement> {
+        let state_len = self.mds_matrix.rows();
+        let rate = state_len - 1;
+        let mut state = vec![self.finite_field.zero(); state_len];
+        state[rate] = self.finite_field.element(inputs.len() as FieldSize);
+
+        for chunk in inputs.chunks(rate) {
+            for (el, input) in state.iter_mut().zip(chunk) {
+                *el = &*el + &self.finite_field.element(input.value());
+            }
+            self.permute(&mut state);
         }
+
+        let mut digest = Vec::with_capacity(output_len);
+        loop {
+            for el in &state[..rate] {
+                if digest.len() == output_len {
+                    return digest;
+                }
+                digest.push(el.clone());
+            }
+            self.permute(&mut state);
+        }
+    }
+}
+
+impl PoseidonHash {
+    fn permute(&self, state: &mut Vec<FieldElement>) {
+        let state_len = state.len();
+        let half_full_rounds = self.full_rounds / 2;
+        for round in 0..self.full_rounds + self.partial_rounds {
+            for (i, el) in state.iter_mut().enumerate() {
+                *el = &*el + &self.constants[round * state_len + i];
+            }
+
+            if round < half_full_rounds || round >= half_full_rounds + self.partial_rounds {
+                for el in state.iter_mut() {
+                    *el = el.pow_field(&self.alpha);
+                }
+            } else {
+                state[0] = state[0].pow_field(&self.alpha);
+            }
+
+            *state = (0..state_len)
+                .map(|i| {
+                    (0..state_len).fold(self.finite_field.zero(), |acc, j| {
+                        &acc + &(&self.mds_matrix[(i, j)] * &state[j])
+                    })
+                })
+                .collect();
+        }
+    }
+}
+
+impl Default for PoseidonHash {
+    fn default() -> Self {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let mds_matrix = Matrix::from_rows(
+            vec![
+                vec![finite_field.random_element(), finite_field.random_element()],
+                vec![finite_field.random_element(), finite_field.random_element()],
+            ],
+            finite_field.zero(),
+        )
+        .expect("the rows have the same length");
+        let constants = (0..60).map(|_| finite_field.random_element()).collect();
+        PoseidonHash::new(Arc::clone(&finite_field), 5, 8, 22, mds_matrix, constants)
+            .expect("valid default parameters")
+    }
+}
+
+impl PoseidonHash {
+    pub fn new(
+        finite_field: Arc<FiniteField>,
+        alpha: FieldSize,
+        full_rounds: usize,
+        partial_rounds: usize,
+        mds_matrix: Matrix,
+        constants: Vec<FieldElement>,
+    ) -> Result<Self, HashParameterError> {
+        let (rows, cols) = mds_matrix.dim();
+        if rows < 2 || rows != cols {
+            return Err(HashParameterError::MdsDimension {
+                expected: rows.max(2),
+                rows,
+                cols,
+            });
+        }
+
+        let p_minus_one = finite_field.prime - 1;
+        if alpha <= 1 || FiniteField::extended_euclidean(alpha, p_minus_one).0 != 1 {
+            return Err(HashParameterError::InvalidAlpha { alpha, p_minus_one });
+        }
+
+        let rounds = full_rounds + partial_rounds;
+        if constants.len() != rounds * rows {
+            return Err(HashParameterError::ConstantsLength {
+                rounds,
+                expected: rounds * rows,
+                found: constants.len(),
+            });
+        }
+
+        Ok(Self {
+            alpha: finite_field.element(alpha),
+            finite_field,
+            full_rounds,
+            partial_rounds,
+            mds_matrix,
+            constants,
+        })
+    }
+}
+
+/// interprets the first 16 bytes of a digest as an integer reduced into the field of `value`
+fn digest_to_element(value: &FieldElement, digest: &[u8]) -> FieldElement {
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&digest[..16]);
+    let prime = value.finite_field().prime as u128;
+    value
+        .finite_field()
+        .element((u128::from_le_bytes(bytes) % prime) as FieldSize)
+}
+
+/// Output element `i` is the hash of the inputs' little-endian encodings followed by `i` as a u64,
+/// so every element is derived from the full 256 bit digest of the message.
+fn byte_hash_elements(
+    inputs: &[FieldElement],
+    output_len: usize,
+    hash: impl Fn(&[u8]) -> [u8; 32],
+) -> Vec<FieldElement> {
+    assert!(!inputs.is_empty(), "At least one input is required");
+    let mut message: Vec<u8> = inputs
+        .iter()
+        .flat_map(|input| input.value().to_le_bytes())
+        .collect();
+    let message_len = message.len();
+    (0..output_len as u64)
+        .map(|i| {
+            message.truncate(message_len);
+            message.extend_from_slice(&i.to_le_bytes());
+            digest_to_element(&inputs[0], &hash(&message))
+        })
+        .collect()
+}
+
+/// SHA-256 of the little-endian encoding of the element
+#[derive(Clone, Default)]
+pub struct Sha256Hasher;
+
+impl Hasher for Sha256Hasher {
+    fn hash(&self, value: FieldElement) -> FieldElement {
+        let digest = Sha256::digest(value.value().to_le_bytes());
+        digest_to_element(&value, &digest)
+    }
+
+    fn hash_elements(&self, inputs: &[FieldElement], output_len: usize) -> Vec<FieldElement> {
+        byte_hash_elements(inputs, output_len, |message| Sha256::digest(message).into())
+    }
+}
+
+/// BLAKE3 of the little-endian encoding of the element
+#[derive(Clone, Default)]
+pub struct Blake3Hasher;
+
+impl Hasher for Blake3Hasher {
+    fn hash(&self, value: FieldElement) -> FieldElement {
+        let digest = blake3::hash(&value.value().to_le_bytes());
+        digest_to_element(&value, digest.as_bytes())
+    }
+
+    fn hash_elements(&self, inputs: &[FieldElement], output_len: usize) -> Vec<FieldElement> {
+        byte_hash_elements(inputs, output_len, |message| {
+            *blake3::hash(message).as_bytes()
+        })
+    }
+}
+
+impl Hasher<BigFieldElement> for Sha256Hasher {
+    fn hash(&self, value: BigFieldElement) -> BigFieldElement {
+        let digest = Sha256::digest(value.value().to_le_bytes());
+        value
+            .finite_field()
+            .element(U256::from_le_bytes(digest.into()))
+    }
+}
+
+impl Hasher<BigFieldElement> for Blake3Hasher {
+    fn hash(&self, value: BigFieldElement) -> BigFieldElement {
+        let digest = blake3::hash(&value.value().to_le_bytes());
+        value
+            .finite_field()
+            .element(U256::from_le_bytes(*digest.as_bytes()))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::hash::{Hasher, RescueHash};
-    use algebra::finite_field::FiniteField;
-    use ndarray::{array, Array1};
-    use std::rc::Rc;
+    use crate::error::HashParameterError;
+    use crate::hash::{Blake3Hasher, Hasher, PoseidonHash, RescueHash, Sha256Hasher};
+    use algebra::finite_field::{FieldSize, FiniteField};
+    use algebra::fp::Fp;
+    use algebra::matrix;
+    use algebra::matrix::Matrix;
+    use std::sync::Arc;
 
     #[test]
     fn test_new() {
-        let finite_field = Rc::new(FiniteField::new(97, 1));
+        let finite_field = Arc::new(FiniteField::new(97, 5));
         let alpha = finite_field.element(5);
-        let mds_matrix = array![
-            [finite_field.random_element(), finite_field.random_element()],
-            [finite_field.random_element(), finite_field.random_element()],
-        ];
-        let constants = Array1::from_elem(108, finite_field.random_element());
-        let hash_func =
-            RescueHash::new(Rc::clone(&finite_field), 1, 1, alpha, mds_matrix, constants);
+        let mds_matrix = Matrix::from_rows(
+            vec![
+                vec![finite_field.random_element(), finite_field.random_element()],
+                vec![finite_field.random_element(), finite_field.random_element()],
+            ],
+            finite_field.zero(),
+        )
+        .expect("the rows have the same length");
+        let constants = vec![finite_field.random_element(); 108];
+        let hash_func = RescueHash::new(
+            Arc::clone(&finite_field),
+            1,
+            1,
+            alpha,
+            mds_matrix,
+            constants,
+        );
         let hash = hash_func.hash(finite_field.element(15));
 
         println!("Hash: {}", hash);
     }
+
+    #[test]
+    fn test_sbox_round_trip() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let hash_func = RescueHash::builder(Arc::clone(&finite_field))
+            .alpha(5)
+            .mds_matrix(matrix!(finite_field, [[2, 3], [5, 7]]))
+            .round_constants(vec![finite_field.element(11); 108])
+            .build()
+            .unwrap();
+
+        // 5 * 77 = 385 = 1 mod 96
+        assert_eq!(hash_func.alpha_inv, 77);
+        for x in finite_field.elements(0..97) {
+            let sbox = x.pow(hash_func.alpha);
+            assert_eq!(sbox.pow(hash_func.alpha_inv), x);
+        }
+
+        // the MDS steps alone, without the S-boxes, rate 1 and a state of 2
+        assert_eq!(hash_func.rounds, 27);
+        let affine = |value| {
+            let mut state = vec![value, finite_field.zero()];
+            for step in 0..2 * hash_func.rounds {
+                state = hash_func.mds_matrix.mul_vector(&state).unwrap();
+                for (i, el) in state.iter_mut().enumerate() {
+                    *el = &*el + &hash_func.constants[step * 2 + i];
+                }
+            }
+            state[0].clone()
+        };
+        let value = finite_field.element(15);
+        assert_ne!(hash_func.hash(value.clone()), affine(value));
+    }
+
+    #[test]
+    fn test_generic_field() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let hash_func = RescueHash::builder(Arc::clone(&finite_field))
+            .alpha(5)
+            .mds_matrix(matrix!(finite_field, [[2, 3], [5, 7]]))
+            .round_constants(vec![finite_field.element(11); 108])
+            .build()
+            .unwrap();
+        let mds_matrix = Matrix::from_rows(
+            vec![
+                vec![Fp::<97>::new(2), Fp::new(3)],
+                vec![Fp::new(5), Fp::new(7)],
+            ],
+            Fp::new(0),
+        )
+        .unwrap();
+        let fp_hash =
+            RescueHash::with_exponents(1, 1, 27, 5, 77, mds_matrix, vec![Fp::new(11); 108]);
+        for x in 0..97 {
+            let hash = hash_func.hash(finite_field.element(x));
+            assert_eq!(
+                fp_hash.hash(Fp::new(x as u64)),
+                Fp::new(hash.value() as u64)
+            );
+        }
+
+        // inputs from another handle to the same field are chained too
+        let other = Arc::new(FiniteField::new(97, 5));
+        let inputs: Vec<_> = other.elements([1, 2, 3]).collect();
+        let digest = hash_func.hash_elements(&inputs, 2);
+        let inputs: Vec<_> = finite_field.elements([1, 2, 3]).collect();
+        assert_eq!(digest, hash_func.hash_elements(&inputs, 2));
+    }
+
+    #[test]
+    fn test_builder_validation() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let mds_matrix = matrix!(finite_field, [[2, 3], [5, 7]]);
+        let constants = vec![finite_field.element(11); 108];
+        let builder = || {
+            RescueHash::builder(Arc::clone(&finite_field))
+                .alpha(5)
+                .mds_matrix(mds_matrix.clone())
+                .round_constants(constants.clone())
+        };
+
+        assert!(builder().build().is_ok());
+        assert_eq!(
+            RescueHash::builder(Arc::clone(&finite_field)).build().err(),
+            Some(HashParameterError::MissingParameter("alpha"))
+        );
+        assert_eq!(
+            builder().rate(0).build().err(),
+            Some(HashParameterError::InvalidStateSize {
+                rate: 0,
+                capacity: 1
+            })
+        );
+        // 3 divides 96
+        assert_eq!(
+            builder().alpha(3).build().err(),
+            Some(HashParameterError::InvalidAlpha {
+                alpha: 3,
+                p_minus_one: 96
+            })
+        );
+        // rate + capacity = 3 doesn't match the 2x2 matrix
+        assert_eq!(
+            builder().rate(2).build().err(),
+            Some(HashParameterError::MdsDimension {
+                expected: 3,
+                rows: 2,
+                cols: 2
+            })
+        );
+        assert_eq!(
+            builder().rounds(10).build().err(),
+            Some(HashParameterError::ConstantsLength {
+                rounds: 10,
+                expected: 40,
+                found: 108
+            })
+        );
+    }
+
+    #[test]
+    fn test_builder_rounds_and_rate() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let mds_matrix = matrix!(finite_field, [[2, 3, 5], [7, 11, 13], [17, 19, 23]]);
+        let build = |rounds, constants: &[FieldSize]| {
+            RescueHash::builder(Arc::clone(&finite_field))
+                .rate(2)
+                .capacity(1)
+                .rounds(rounds)
+                .alpha(5)
+                .mds_matrix(mds_matrix.clone())
+                .round_constants(finite_field.elements(constants.iter().copied()).collect())
+                .build()
+                .unwrap()
+        };
+
+        // a single round: S-box, MDS and the first 3 constants, inverse S-box, MDS and the last 3
+        let one_round = build(1, &[1, 2, 3, 4, 5, 6]);
+        assert_eq!(one_round.rounds, 1);
+        let value = finite_field.element(15);
+        let mut state = vec![value.clone(), finite_field.zero(), finite_field.zero()];
+        for (step, exponent) in [5, 77].into_iter().enumerate() {
+            let sboxed: Vec<_> = state.iter().map(|x| x.pow(exponent)).collect();
+            state = mds_matrix.mul_vector(&sboxed).unwrap();
+            for (i, el) in state.iter_mut().enumerate() {
+                *el = &*el + &finite_field.element((3 * step + i + 1) as FieldSize);
+            }
+        }
+        assert_eq!(one_round.hash(value.clone()), state[0]);
+
+        // every round uses its own constants, here the first ones of the second round
+        let constants: Vec<_> = (1..=18).collect();
+        let three_rounds = build(3, &constants);
+        assert_eq!(three_rounds.rounds, 3);
+        let mut shifted = constants.clone();
+        shifted[6] = 0;
+        assert_ne!(
+            three_rounds.hash(value.clone()),
+            build(3, &shifted).hash(value.clone())
+        );
+        assert_ne!(three_rounds.hash(value.clone()), one_round.hash(value));
+    }
+
+    #[test]
+    fn test_poseidon() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let mds_matrix = matrix!(finite_field, [[2, 3], [5, 7]]);
+        let constants = vec![finite_field.element(11); 60];
+        let hash_func = PoseidonHash::new(
+            Arc::clone(&finite_field),
+            5,
+            8,
+            22,
+            mds_matrix.clone(),
+            constants.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            hash_func.hash(finite_field.element(15)),
+            hash_func.hash(finite_field.element(15))
+        );
+
+        assert!(matches!(
+            PoseidonHash::new(Arc::clone(&finite_field), 5, 8, 20, mds_matrix, constants),
+            Err(HashParameterError::ConstantsLength { expected: 56, .. })
+        ));
+    }
+
+    #[test]
+    fn test_byte_hashers() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        for hasher in [&Sha256Hasher as &dyn Hasher, &Blake3Hasher as &dyn Hasher] {
+            let hash = hasher.hash(finite_field.element(15));
+            assert!(hash.value() < 97);
+            assert_eq!(hash, hasher.hash(finite_field.element(15)));
+        }
+    }
+
+    #[test]
+    fn test_hash_elements() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let inputs: Vec<_> = finite_field.elements([1, 2, 3]).collect();
+        for hasher in [
+            &Sha256Hasher as &dyn Hasher,
+            &Blake3Hasher as &dyn Hasher,
+            &PoseidonHash::default() as &dyn Hasher,
+            &RescueHash::default() as &dyn Hasher,
+        ] {
+            let digest = hasher.hash_elements(&inputs, 4);
+            assert_eq!(digest.len(), 4);
+            assert_eq!(digest, hasher.hash_elements(&inputs, 4));
+            // shorter digests are prefixes
+            assert_eq!(hasher.hash_elements(&inputs, 2), digest[..2]);
+        }
+    }
 }
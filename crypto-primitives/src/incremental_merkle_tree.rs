@@ -0,0 +1,139 @@
+//! Append-only Merkle tree for leafs that arrive one at a time, like trace rows streamed by the prover.
+//!
+//! Only the roots of the complete subtrees on the left edge (the frontier) are kept, one per set bit of
+//! the number of leafs. The running root pads the leafs with zero hashes up to the next power of two,
+//! so it is the root [`MerkleTree::new`](crate::merkle_tree::MerkleTree::new) commits to for the same
+//! leafs.
+use crate::hash::Hasher;
+use crate::merkle_tree::{hash_leaf, hash_pair, MerkleNode};
+use algebra::field::Field;
+use algebra::finite_field::FieldElement;
+
+pub struct IncrementalMerkleTree<H: Hasher<F>, F: Field = FieldElement> {
+    hasher: H,
+    len: usize,
+    /// root of the complete subtree of `2^level` leafs at every set bit of `len`
+    frontier: Vec<Option<MerkleNode<F>>>,
+    /// root of a subtree of `2^level` zero hashes, grown with the tree
+    zeros: Vec<MerkleNode<F>>,
+}
+
+impl<H: Hasher<F>, F: Field> IncrementalMerkleTree<H, F> {
+    pub fn new(hasher: H) -> Self {
+        IncrementalMerkleTree {
+            hasher,
+            len: 0,
+            frontier: vec![],
+            zeros: vec![],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Hashes `leaf` in and merges the complete subtrees it finishes, one per trailing set bit of the
+    /// number of leafs, so `O(1)` hashes amortized and `O(log n)` at most.
+    pub fn push(&mut self, leaf: F) {
+        let mut node = hash_leaf(&self.hasher, leaf, None);
+        if self.zeros.is_empty() {
+            self.zeros.push(node.iter().map(F::zero).collect());
+        }
+
+        let mut level = 0;
+        while (self.len >> level) & 1 == 1 {
+            let left = self.frontier[level]
+                .take()
+                .expect("a subtree at every set bit");
+            node = hash_pair(&self.hasher, &left, &node);
+            level += 1;
+        }
+        if level == self.frontier.len() {
+            self.frontier.push(None);
+            let zero = self.zeros[level].clone();
+            self.zeros.push(hash_pair(&self.hasher, &zero, &zero));
+        }
+        self.frontier[level] = Some(node);
+        self.len += 1;
+    }
+
+    /// The root of the leafs pushed so far, padded with zero hashes to a power of two, in `O(log n)`
+    /// hashes. `None` before the first leaf.
+    pub fn root(&self) -> Option<MerkleNode<F>> {
+        if self.len.is_power_of_two() {
+            return self.frontier[self.len.ilog2() as usize].clone();
+        }
+
+        // the node holding the last leafs, right of the frontier and padded with zeros, from the
+        // leafs up; `None` while it only covers padding
+        let mut node: Option<MerkleNode<F>> = None;
+        for level in 0..self.len.next_power_of_two().ilog2() as usize {
+            let right = node.take();
+            node = match &self.frontier[level] {
+                Some(left) => Some(hash_pair(
+                    &self.hasher,
+                    left,
+                    right.as_ref().unwrap_or(&self.zeros[level]),
+                )),
+                None => right.map(|left| hash_pair(&self.hasher, &left, &self.zeros[level])),
+            };
+        }
+        node
+    }
+}
+
+impl<H: Hasher<F>, F: Field> Extend<F> for IncrementalMerkleTree<H, F> {
+    fn extend<I: IntoIterator<Item = F>>(&mut self, leafs: I) {
+        for leaf in leafs {
+            self.push(leaf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::hash::{Blake3Hasher, RescueHash};
+    use crate::incremental_merkle_tree::IncrementalMerkleTree;
+    use crate::merkle_tree::MerkleTree;
+    use algebra::finite_field::FiniteField;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_running_root() {
+        let finite_field = Arc::new(FiniteField::new(3221225473, 5));
+        let mut tree = IncrementalMerkleTree::new(Blake3Hasher);
+        assert!(tree.is_empty());
+        assert_eq!(tree.root(), None);
+
+        let leafs: Vec<_> = finite_field.elements(100..133).collect();
+        for (len, leaf) in (1..).zip(&leafs) {
+            tree.push(leaf.clone());
+            assert_eq!(tree.len(), len);
+
+            let mut full = MerkleTree::new(Blake3Hasher, leafs[..len].to_vec()).unwrap();
+            assert_eq!(tree.root(), Some(full.commit()), "{len} leafs");
+        }
+        // one subtree per set bit of 33
+        assert_eq!(tree.frontier.iter().flatten().count(), 2);
+    }
+
+    #[test]
+    fn test_extend() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let leafs: Vec<_> = finite_field.elements(0..6).collect();
+        let hasher = RescueHash::default();
+
+        let mut pushed = IncrementalMerkleTree::new(hasher.clone());
+        for leaf in &leafs {
+            pushed.push(leaf.clone());
+        }
+        let mut extended = IncrementalMerkleTree::new(hasher);
+        extended.extend(leafs);
+        assert_eq!(extended.len(), 6);
+        assert_eq!(pushed.root(), extended.root());
+    }
+}
@@ -1,5 +1,14 @@
-#[allow(dead_code)]
+pub mod error;
 pub mod hash;
 
-#[allow(dead_code)]
+pub mod incremental_merkle_tree;
+pub mod merkle_mountain_range;
 pub mod merkle_tree;
+pub mod proof_options;
+pub mod queries;
+pub mod round_log;
+pub mod sparse_merkle_tree;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
+pub mod transcript;
+pub mod wire;
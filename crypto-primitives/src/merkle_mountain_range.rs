@@ -0,0 +1,288 @@
+//! Merkle Mountain Range, an append-only accumulator for logs and recursive proof accumulation.
+//!
+//! The leafs form one perfect tree (a mountain) per set bit of their number, largest first, and the
+//! root bags the mountain tops (the peaks) from right to left. Appending hashes the new leaf and merges
+//! the mountains of equal height it completes, so nothing is rebuilt and every node ever computed is
+//! kept for openings. With a power of two leafs there is a single peak, the root of
+//! [`MerkleTree`](crate::merkle_tree::MerkleTree) over the same leafs.
+//!
+//! The root doesn't bind the number of leafs, the verifier has to know it.
+use crate::error::CommitmentError;
+use crate::hash::Hasher;
+use crate::merkle_tree::{hash_leaf, hash_pair, is_digest, MerkleNode};
+use algebra::field::Field;
+use algebra::finite_field::FieldElement;
+
+pub struct MerkleMountainRange<H: Hasher<F>, F: Field = FieldElement> {
+    hasher: H,
+    len: usize,
+    /// every node at each height, in order: the one at position `j` of height `h` covers the leafs
+    /// `j * 2^h..(j + 1) * 2^h`
+    levels: Vec<Vec<MerkleNode<F>>>,
+}
+
+/// Path of the leaf at `index` up to the peak of its mountain, siblings from the leafs up, and all the
+/// peaks of the range, to bag them into the root.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MountainRangeProof<F: Field = FieldElement> {
+    pub index: usize,
+    pub leaf: MerkleNode<F>,
+    pub siblings: Vec<MerkleNode<F>>,
+    pub peaks: Vec<MerkleNode<F>>,
+}
+
+/// the root of `peaks`, largest mountain first, hashed in from the right
+fn bag_peaks<F: Field, H: Hasher<F>>(hasher: &H, peaks: &[MerkleNode<F>]) -> Option<MerkleNode<F>> {
+    let (last, rest) = peaks.split_last()?;
+    Some(rest.iter().rev().fold(last.clone(), |bagged, peak| {
+        hash_pair(hasher, peak, &bagged)
+    }))
+}
+
+/// (height, position among the peaks) of the mountain holding leaf `index` of `len`
+fn mountain_of(len: usize, index: usize) -> Option<(usize, usize)> {
+    let mut start = 0;
+    for (peak, height) in (0..usize::BITS as usize)
+        .rev()
+        .filter(|height| (len >> height) & 1 == 1)
+        .enumerate()
+    {
+        if index < start + (1 << height) {
+            return Some((height, peak));
+        }
+        start += 1 << height;
+    }
+    None
+}
+
+impl<H: Hasher<F>, F: Field> MerkleMountainRange<H, F> {
+    pub fn new(hasher: H) -> Self {
+        MerkleMountainRange {
+            hasher,
+            len: 0,
+            levels: vec![],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Hashes `leaf` in and merges the mountains it completes, `O(1)` hashes amortized.
+    pub fn append(&mut self, leaf: F) {
+        let mut node = hash_leaf(&self.hasher, leaf, None);
+        let mut height = 0;
+        loop {
+            if height == self.levels.len() {
+                self.levels.push(vec![]);
+            }
+            let level = &mut self.levels[height];
+            level.push(node);
+            if level.len() % 2 == 1 {
+                break;
+            }
+            node = hash_pair(
+                &self.hasher,
+                &level[level.len() - 2],
+                &level[level.len() - 1],
+            );
+            height += 1;
+        }
+        self.len += 1;
+    }
+
+    /// the tops of the mountains, largest first
+    pub fn peaks(&self) -> Vec<MerkleNode<F>> {
+        (0..self.levels.len())
+            .rev()
+            .filter(|height| (self.len >> height) & 1 == 1)
+            .map(|height| self.levels[height][(self.len >> height) - 1].clone())
+            .collect()
+    }
+
+    /// The bagged peaks, `None` before the first leaf.
+    pub fn root(&self) -> Option<MerkleNode<F>> {
+        bag_peaks(&self.hasher, &self.peaks())
+    }
+
+    /// Opening of the leaf at `index` against the current root. Later appends change the peaks, so it
+    /// has to be opened again for a newer root.
+    pub fn open(&self, index: usize) -> Result<MountainRangeProof<F>, CommitmentError> {
+        let (height, _) = mountain_of(self.len, index).ok_or(CommitmentError::IndexOutOfRange {
+            index,
+            len: self.len,
+        })?;
+        let siblings = (0..height)
+            .map(|level| self.levels[level][(index >> level) ^ 1].clone())
+            .collect();
+        Ok(MountainRangeProof {
+            index,
+            leaf: self.levels[0][index].clone(),
+            siblings,
+            peaks: self.peaks(),
+        })
+    }
+
+    /// Verifies knowing only the root and the number of leafs `len` that `leaf` was appended at
+    /// `index`. `leaf` is the appended value, it's hashed here.
+    pub fn verify_proof(
+        hasher: &H,
+        root: &[F],
+        len: usize,
+        index: usize,
+        leaf: &F,
+        proof: &MountainRangeProof<F>,
+    ) -> bool {
+        let Some((height, peak)) = mountain_of(len, index) else {
+            return false;
+        };
+        if proof.index != index
+            || proof.siblings.len() != height
+            || proof.peaks.len() != len.count_ones() as usize
+            || !proof
+                .siblings
+                .iter()
+                .chain(&proof.peaks)
+                .all(|node| is_digest(hasher, node))
+            || proof.leaf != hash_leaf(hasher, leaf.clone(), None)
+        {
+            return false;
+        }
+
+        // mountains start at multiples of their size, so the low bits of the index are the sides
+        let top =
+            proof
+                .siblings
+                .iter()
+                .enumerate()
+                .fold(proof.leaf.clone(), |node, (level, sibling)| {
+                    if (index >> level) & 1 == 1 {
+                        hash_pair(hasher, sibling, &node)
+                    } else {
+                        hash_pair(hasher, &node, sibling)
+                    }
+                });
+        top == proof.peaks[peak] && bag_peaks(hasher, &proof.peaks).as_deref() == Some(root)
+    }
+}
+
+impl<H: Hasher<F>, F: Field> Extend<F> for MerkleMountainRange<H, F> {
+    fn extend<I: IntoIterator<Item = F>>(&mut self, leafs: I) {
+        for leaf in leafs {
+            self.append(leaf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::CommitmentError;
+    use crate::hash::Blake3Hasher;
+    use crate::merkle_mountain_range::{bag_peaks, MerkleMountainRange};
+    use crate::merkle_tree::{hash_leaf, hash_pair, MerkleTree};
+    use algebra::finite_field::FiniteField;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_append() {
+        let finite_field = Arc::new(FiniteField::new(3221225473, 5));
+        let hasher = Blake3Hasher;
+        let mut range = MerkleMountainRange::new(Blake3Hasher);
+        assert!(range.is_empty());
+        assert_eq!(range.root(), None);
+
+        let leafs: Vec<_> = finite_field.elements(40..53).collect();
+        range.extend(leafs[..8].iter().cloned());
+        let mut tree = MerkleTree::new(Blake3Hasher, leafs[..8].to_vec()).unwrap();
+        assert_eq!(range.peaks(), vec![tree.commit()]);
+        assert_eq!(range.root(), Some(tree.commit()));
+
+        // 13 leafs: mountains of 8, 4 and 1
+        range.extend(leafs[8..].iter().cloned());
+        assert_eq!(range.len(), 13);
+        let mountain = |leafs: &[_]| {
+            let mut tree = MerkleTree::new(Blake3Hasher, leafs.to_vec()).unwrap();
+            tree.commit()
+        };
+        let peaks = vec![
+            mountain(&leafs[..8]),
+            mountain(&leafs[8..12]),
+            hash_leaf(&hasher, leafs[12].clone(), None),
+        ];
+        assert_eq!(range.peaks(), peaks);
+        let bagged = hash_pair(
+            &hasher,
+            &peaks[0],
+            &hash_pair(&hasher, &peaks[1], &peaks[2]),
+        );
+        assert_eq!(range.root(), Some(bagged));
+        assert_eq!(bag_peaks(&hasher, &peaks[2..]), Some(peaks[2].clone()));
+    }
+
+    #[test]
+    fn test_open() {
+        let finite_field = Arc::new(FiniteField::new(3221225473, 5));
+        let hasher = Blake3Hasher;
+        let leafs: Vec<_> = finite_field.elements(0..11).collect();
+        let mut range = MerkleMountainRange::new(Blake3Hasher);
+
+        for (len, leaf) in (1..).zip(&leafs) {
+            range.append(leaf.clone());
+            let root = range.root().unwrap();
+            for (index, leaf) in leafs[..len].iter().enumerate() {
+                let proof = range.open(index).unwrap();
+                assert!(
+                    MerkleMountainRange::verify_proof(&hasher, &root, len, index, leaf, &proof),
+                    "leaf {index} of {len}"
+                );
+                // the wrong value, position or peaks
+                let other = leaf + &finite_field.one();
+                assert!(!MerkleMountainRange::verify_proof(
+                    &hasher, &root, len, index, &other, &proof
+                ));
+                let mut tampered = proof.clone();
+                let last = tampered.peaks.len() - 1;
+                tampered.peaks[last][0] = &tampered.peaks[last][0] + &finite_field.one();
+                assert!(!MerkleMountainRange::verify_proof(
+                    &hasher, &root, len, index, leaf, &tampered
+                ));
+                if index > 0 {
+                    let mut moved = proof.clone();
+                    moved.index -= 1;
+                    assert!(!MerkleMountainRange::verify_proof(
+                        &hasher,
+                        &root,
+                        len,
+                        index - 1,
+                        leaf,
+                        &moved
+                    ));
+                }
+            }
+        }
+
+        // an opening is for the root it was made against
+        let root = range.root().unwrap();
+        let proof = range.open(3).unwrap();
+        range.append(finite_field.element(100));
+        assert!(!MerkleMountainRange::verify_proof(
+            &hasher,
+            &range.root().unwrap(),
+            12,
+            3,
+            &leafs[3],
+            &proof
+        ));
+        assert!(MerkleMountainRange::verify_proof(
+            &hasher, &root, 11, 3, &leafs[3], &proof
+        ));
+        assert_eq!(
+            range.open(12),
+            Err(CommitmentError::IndexOutOfRange { index: 12, len: 12 })
+        );
+    }
+}
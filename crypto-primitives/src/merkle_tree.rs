@@ -1,58 +1,268 @@
+use crate::error::CommitmentError;
 use crate::hash::Hasher;
-use algebra::finite_field::{FieldElement, FiniteField};
+use algebra::field::Field;
+use algebra::finite_field::FieldElement;
+use rand::Rng;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::collections::{BTreeMap, HashMap};
 use std::ops::Index;
-use std::rc::Rc;
 
-struct MerkleTree<H: Hasher + Clone> {
-    finite_field: Rc<FiniteField>,
+/// A node of a Merkle tree, a digest of [`Hasher::digest_len`] elements.
+pub type MerkleNode<F = FieldElement> = Vec<F>;
+
+/// How `MerkleTree::new` fills the leaf level up to the next power of two.
+///
+/// The padding nodes are leaf hashes, they can't be opened, and the number of committed leafs is not
+/// bound by the root: the verifier has to know it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MerklePadding {
+    /// the zero digest in place of the missing leaf hashes
+    #[default]
+    ZeroHash,
+    /// the hash of the last leaf, repeated
+    DuplicateLast,
+}
+
+pub struct MerkleTree<H: Hasher<F> + Clone, F: Field = FieldElement> {
     hasher: H,
-    leafs: Vec<FieldElement>,
-    levels: Vec<Vec<FieldElement>>,
-    root: Option<FieldElement>,
+    leafs: Vec<MerkleNode<F>>,
+    salts: Option<Vec<F>>,
+    levels: Vec<Vec<MerkleNode<F>>>,
+    root: Option<MerkleNode<F>>,
 }
 
-impl<H: Hasher + Clone> MerkleTree<H> {
-    /// computes the Merkle root of a given array.
-    pub fn new(finite_field: Rc<FiniteField>, hasher: H, leafs: Vec<FieldElement>) -> Self {
-        let leafs_len = leafs.len();
-        assert_ne!(leafs_len, 0, "The list doesn't contains any elements");
-        assert_eq!(leafs_len & (leafs_len - 1), 0, "The list is not power of 2");
+/// Authentication path of the leaf at `index`: its hash and the sibling of every node on the way to
+/// the root, from the leafs up.
+///
+/// Bit `i` of the index is the side of the node at level `i`, 0 for a left child, so the verifier
+/// knows on which side to put each sibling and a path can't be replayed for another position.
+/// Openings of a salted tree carry the salt of the leaf, needed to hash the opened value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MerkleProof<F: Field = FieldElement> {
+    pub index: usize,
+    pub leaf: MerkleNode<F>,
+    pub siblings: Vec<MerkleNode<F>>,
+    pub salt: Option<F>,
+}
 
-        let leafs = leafs
+impl<F: Field> MerkleProof<F> {
+    /// whether the node at `level` is a right child, so its sibling goes on the left
+    pub fn is_right_child(&self, level: usize) -> bool {
+        (self.index >> level) & 1 == 1
+    }
+
+    /// The root the path leads to, hashing `(left, right)` in order at every level. `None` when a
+    /// node is not a digest of `hasher`.
+    pub fn compute_root<H: Hasher<F>>(&self, hasher: &H) -> Option<MerkleNode<F>> {
+        if !is_digest(hasher, &self.leaf) || !self.siblings.iter().all(|s| is_digest(hasher, s)) {
+            return None;
+        }
+        Some(
+            self.siblings
+                .iter()
+                .enumerate()
+                .fold(self.leaf.clone(), |node, (level, sibling)| {
+                    if self.is_right_child(level) {
+                        hash_pair(hasher, sibling, &node)
+                    } else {
+                        hash_pair(hasher, &node, sibling)
+                    }
+                }),
+        )
+    }
+}
+
+/// Openings of several leafs sharing one authentication structure.
+///
+/// `indices` are sorted without repetitions, `leafs` holds their hashes and `salts` the salts of a
+/// salted tree in the same order. `nodes` are the siblings the verifier can't compute from the opened leafs, level by level from
+/// the leafs up and by position within a level, so a node shared by several paths is sent once.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MerkleMultiProof<F: Field = FieldElement> {
+    pub depth: usize,
+    pub indices: Vec<usize>,
+    pub leafs: Vec<MerkleNode<F>>,
+    pub nodes: Vec<MerkleNode<F>>,
+    pub salts: Option<Vec<F>>,
+}
+
+impl<F: Field> MerkleMultiProof<F> {
+    /// Hashes the opened leafs up to the root, consuming `nodes` for the missing siblings. `None`
+    /// when the proof doesn't describe a tree of its depth: indices out of order or out of range,
+    /// too few or too many nodes, or nodes that are not digests of `hasher`.
+    pub fn compute_root<H: Hasher<F>>(&self, hasher: &H) -> Option<MerkleNode<F>> {
+        if self.indices.is_empty()
+            || self.indices.len() != self.leafs.len()
+            || !self.indices.windows(2).all(|pair| pair[0] < pair[1])
+            || !self
+                .leafs
+                .iter()
+                .chain(&self.nodes)
+                .all(|node| is_digest(hasher, node))
+        {
+            return None;
+        }
+        let mut nodes = self.nodes.iter();
+        let mut layer: Vec<(usize, MerkleNode<F>)> = self
+            .indices
             .iter()
-            .map(|leaf| hasher.hash(leaf.clone()))
-            .collect::<Vec<FieldElement>>();
+            .copied()
+            .zip(self.leafs.iter().cloned())
+            .collect();
+
+        for _ in 0..self.depth {
+            let mut parents = Vec::with_capacity(layer.len());
+            let mut known = layer.into_iter().peekable();
+            while let Some((position, node)) = known.next() {
+                let parent = if position & 1 == 1 {
+                    hash_pair(hasher, nodes.next()?, &node)
+                } else if let Some((_, right)) = known.next_if(|(next, _)| *next == position ^ 1) {
+                    hash_pair(hasher, &node, &right)
+                } else {
+                    hash_pair(hasher, &node, nodes.next()?)
+                };
+                parents.push((position >> 1, parent));
+            }
+            layer = parents;
+        }
+
+        match layer.as_slice() {
+            [(0, root)] if nodes.next().is_none() => Some(root.clone()),
+            _ => None,
+        }
+    }
+}
 
-        MerkleTree {
-            finite_field,
+/// a single opening is a multi-proof whose nodes are its siblings
+impl<F: Field> From<MerkleProof<F>> for MerkleMultiProof<F> {
+    fn from(proof: MerkleProof<F>) -> Self {
+        MerkleMultiProof {
+            depth: proof.siblings.len(),
+            indices: vec![proof.index],
+            leafs: vec![proof.leaf],
+            nodes: proof.siblings,
+            salts: proof.salt.map(|salt| vec![salt]),
+        }
+    }
+}
+
+/// whether `node` has the length of the digests of `hasher`
+pub(crate) fn is_digest<F: Field, H: Hasher<F>>(hasher: &H, node: &[F]) -> bool {
+    node.len() == hasher.digest_len()
+}
+
+/// The parent of two digests of `hasher`, the order of the children matters.
+///
+/// Inner nodes end their message with one and leaf nodes with zero, so a value can't be opened as a
+/// leaf whose node is an inner node of the tree.
+pub(crate) fn hash_pair<F: Field, H: Hasher<F>>(
+    hasher: &H,
+    left: &[F],
+    right: &[F],
+) -> MerkleNode<F> {
+    let mut message = Vec::with_capacity(left.len() + right.len() + 1);
+    message.extend_from_slice(left);
+    message.extend_from_slice(right);
+    message.push(left[0].one());
+    hasher.hash_elements(&message, hasher.digest_len())
+}
+
+/// the leaf node of a committed value, blinded by the salt in a salted tree
+pub(crate) fn hash_leaf<F: Field, H: Hasher<F>>(
+    hasher: &H,
+    leaf: F,
+    salt: Option<&F>,
+) -> MerkleNode<F> {
+    let tag = leaf.zero();
+    let message = match salt {
+        Some(salt) => vec![leaf, salt.clone(), tag],
+        None => vec![leaf, tag],
+    };
+    hasher.hash_elements(&message, hasher.digest_len())
+}
+
+/// number of levels above the leafs of a tree of `len` leafs padded to a power of two
+pub(crate) fn tree_depth(len: usize) -> usize {
+    match len {
+        0 | 1 => 0,
+        len => (usize::BITS - (len - 1).leading_zeros()) as usize,
+    }
+}
+
+impl<H: Hasher<F> + Clone, F: Field> MerkleTree<H, F> {
+    /// computes the Merkle root of a given array, padded with [`MerklePadding::ZeroHash`] when its
+    /// length is not a power of two.
+    pub fn new(hasher: H, leafs: Vec<F>) -> Result<Self, CommitmentError> {
+        Self::with_padding(hasher, leafs, MerklePadding::default())
+    }
+
+    /// like `new`, filling the leaf level up to a power of two with `padding`
+    pub fn with_padding(
+        hasher: H,
+        leafs: Vec<F>,
+        padding: MerklePadding,
+    ) -> Result<Self, CommitmentError> {
+        Self::build(hasher, leafs, padding, None)
+    }
+
+    /// Like `with_padding`, blinding every leaf with a random salt drawn from `rng`.
+    ///
+    /// A leaf node is the hash of the value and its salt, and openings reveal the salt, so the
+    /// verifier learns the opened values but nothing about the others from their nodes.
+    pub fn with_salt<R: Rng + ?Sized>(
+        hasher: H,
+        leafs: Vec<F>,
+        padding: MerklePadding,
+        rng: &mut R,
+    ) -> Result<Self, CommitmentError> {
+        let salts = leafs.iter().map(|leaf| leaf.sample(rng)).collect();
+        Self::build(hasher, leafs, padding, Some(salts))
+    }
+
+    fn build(
+        hasher: H,
+        leafs: Vec<F>,
+        padding: MerklePadding,
+        salts: Option<Vec<F>>,
+    ) -> Result<Self, CommitmentError> {
+        if leafs.is_empty() {
+            return Err(CommitmentError::EmptyLeafs);
+        }
+
+        let leafs = leafs
+            .into_iter()
+            .enumerate()
+            .map(|(index, leaf)| hash_leaf(&hasher, leaf, salts.as_ref().map(|s| &s[index])))
+            .collect::<Vec<MerkleNode<F>>>();
+
+        let last = leafs.last().unwrap();
+        let filler = match padding {
+            MerklePadding::ZeroHash => last.iter().map(F::zero).collect(),
+            MerklePadding::DuplicateLast => last.clone(),
+        };
+        let mut padded = leafs.clone();
+        padded.resize(leafs.len().next_power_of_two(), filler);
+
+        Ok(MerkleTree {
             hasher: hasher.clone(),
-            leafs: leafs.clone(),
-            levels: vec![leafs],
+            leafs,
+            salts,
+            levels: vec![padded],
             root: None,
-        }
+        })
     }
 
-    pub fn commit(&mut self) -> FieldElement {
-        let mut curr_level = self.leafs.clone();
+    /// Hashes every level up to the root. The levels are kept, so openings only read the stored nodes.
+    pub fn commit(&mut self) -> MerkleNode<F> {
+        self.levels.truncate(1);
+        let mut curr_level = self.levels[0].clone();
 
         while curr_level.len() > 1 {
-            let odd_leafs = curr_level
-                .clone()
-                .into_iter()
-                .step_by(2)
-                .collect::<Vec<FieldElement>>();
-            let even_leafs = curr_level
-                .clone()
-                .into_iter()
-                .skip(1)
-                .step_by(2)
-                .collect::<Vec<FieldElement>>();
-
-            let parents = odd_leafs
-                .iter()
-                .zip(even_leafs.iter())
-                .map(|(left, right)| self.hasher.hash(left + right))
-                .collect::<Vec<FieldElement>>();
+            let parents = curr_level
+                .chunks(2)
+                .map(|pair| hash_pair(&self.hasher, &pair[0], &pair[1]))
+                .collect::<Vec<MerkleNode<F>>>();
             self.levels.push(parents.clone());
             curr_level = parents;
         }
@@ -62,60 +272,290 @@ impl<H: Hasher + Clone> MerkleTree<H> {
     }
 
     /// computes the authentication path of an indicated leaf in the Merkle tree.
-    pub fn prove(&self, element: FieldElement) -> Option<Vec<FieldElement>> {
-        let mut current_level_index = 0usize;
+    pub fn prove(&self, element: MerkleNode<F>) -> Result<MerkleProof<F>, CommitmentError> {
+        if self.root.is_none() {
+            return Err(CommitmentError::NotCommitted);
+        }
+        let index = self
+            .leafs
+            .iter()
+            .position(|x| *x == element)
+            .ok_or(CommitmentError::LeafNotFound)?;
+        self.open(index)
+    }
 
-        let mut result: Vec<FieldElement> = vec![element.clone()];
-        let mut element = element;
+    /// Authentication path of the leaf at `index`, like `prove`.
+    ///
+    /// Siblings are read from the levels stored by `commit`, so no node is hashed again however many
+    /// leafs are opened.
+    pub fn open(&self, index: usize) -> Result<MerkleProof<F>, CommitmentError> {
+        if self.root.is_none() {
+            return Err(CommitmentError::NotCommitted);
+        }
+        let len = self.leafs.len();
+        if index >= len {
+            return Err(CommitmentError::IndexOutOfRange { index, len });
+        }
 
-        let mut current_level = &self.levels[current_level_index];
+        let mut siblings = Vec::with_capacity(self.levels.len() - 1);
+        let mut position = index;
+        for level in &self.levels[..self.levels.len() - 1] {
+            siblings.push(level.index(position ^ 1).clone());
+            position >>= 1;
+        }
+        Ok(MerkleProof {
+            index,
+            leaf: self.levels[0].index(index).clone(),
+            siblings,
+            salt: self.salt(index),
+        })
+    }
 
-        while current_level_index < self.levels.len() - 1 {
-            match current_level.iter().position(|x| *x == element) {
-                Some(element_index) => {
-                    let sibling = if element_index % 2 == 0 {
-                        current_level.index(element_index + 1)
-                    } else {
-                        current_level.index(element_index - 1)
-                    };
-                    result.push(sibling.clone());
-                    element = self.hasher.hash(sibling.clone() + element);
-                    current_level_index += 1;
-                    current_level = &self.levels[current_level_index];
+    /// Opens all the leafs at `indices` at once, in any order and possibly repeated.
+    ///
+    /// Siblings that are themselves on the path of another opened leaf are left out of the proof, so
+    /// nearby openings, like the queries of a FRI layer, share most of their nodes.
+    pub fn open_batch(&self, indices: &[usize]) -> Result<MerkleMultiProof<F>, CommitmentError> {
+        if self.root.is_none() {
+            return Err(CommitmentError::NotCommitted);
+        }
+        if indices.is_empty() {
+            return Err(CommitmentError::EmptyProof);
+        }
+        let len = self.leafs.len();
+        if let Some(&index) = indices.iter().find(|&&index| index >= len) {
+            return Err(CommitmentError::IndexOutOfRange { index, len });
+        }
+
+        let mut positions = indices.to_vec();
+        positions.sort_unstable();
+        positions.dedup();
+        let leafs = positions
+            .iter()
+            .map(|&index| self.levels[0][index].clone())
+            .collect();
+        let salts = self.salts.as_ref().map(|salts| {
+            positions
+                .iter()
+                .map(|&index| salts[index].clone())
+                .collect()
+        });
+
+        let depth = self.levels.len() - 1;
+        let mut nodes = Vec::new();
+        let mut layer = positions.clone();
+        for level in &self.levels[..depth] {
+            for (i, &position) in layer.iter().enumerate() {
+                let sibling = position ^ 1;
+                let known = if position & 1 == 1 {
+                    i > 0 && layer[i - 1] == sibling
+                } else {
+                    layer.get(i + 1) == Some(&sibling)
+                };
+                if !known {
+                    nodes.push(level[sibling].clone());
                 }
-                None => return None,
             }
+            layer = layer.iter().map(|position| position >> 1).collect();
+            layer.dedup();
+        }
+
+        Ok(MerkleMultiProof {
+            depth,
+            indices: positions,
+            leafs,
+            nodes,
+            salts,
+        })
+    }
+
+    /// the salt of the leaf at `index`, `None` when the tree is not salted
+    fn salt(&self, index: usize) -> Option<F> {
+        self.salts.as_ref().map(|salts| salts[index].clone())
+    }
+
+    /// Verifies that the leaf is committed at the index of the proof. An index outside the committed
+    /// leafs or a path of the wrong length is an error, a path that doesn't lead to the root is
+    /// `Ok(false)`.
+    pub fn verify(&self, proof: &MerkleProof<F>) -> Result<bool, CommitmentError> {
+        let root = self.root.as_ref().ok_or(CommitmentError::NotCommitted)?;
+        let len = self.leafs.len();
+        if proof.index >= len {
+            return Err(CommitmentError::IndexOutOfRange {
+                index: proof.index,
+                len,
+            });
+        }
+        let depth = self.levels.len() - 1;
+        if proof.siblings.len() != depth {
+            return Err(CommitmentError::PathLengthMismatch {
+                expected: depth,
+                found: proof.siblings.len(),
+            });
         }
+        Ok(proof.compute_root(&self.hasher).as_ref() == Some(root))
+    }
 
-        Some(result)
+    /// Verifies the opening of `leaf` at `index` knowing only the root and the number of committed
+    /// leafs `len`, like a STARK verifier does.
+    ///
+    /// `leaf` is the committed value, it's hashed here with the salt of the proof if any, and the
+    /// proof must be for the same index and leaf. `len` fixes the depth of the path, so a proof can't
+    /// stop at an inner node, and the index must be a committed leaf, not the padding.
+    pub fn verify_proof(
+        hasher: &H,
+        root: &[F],
+        len: usize,
+        index: usize,
+        leaf: &F,
+        proof: &MerkleProof<F>,
+    ) -> bool {
+        index < len
+            && proof.index == index
+            && proof.siblings.len() == tree_depth(len)
+            && proof.leaf == hash_leaf(hasher, leaf.clone(), proof.salt.as_ref())
+            && proof.compute_root(hasher).as_deref() == Some(root)
     }
 
-    ///  verifies that a given leaf is an element of the committed vector at the given index
-    pub fn verify(&self, proof: Vec<FieldElement>) -> bool {
-        let mut current_element = proof[0].clone();
-        let mut index = 1;
-        while index < proof.len() {
-            current_element = self
-                .hasher
-                .hash(current_element.clone() + proof[index].clone());
-            index += 1;
+    /// Verifies a multi-proof knowing only the root and the number of committed leafs `len`, like
+    /// [`MerkleTree::verify_proof`].
+    ///
+    /// `indices` and `leafs` are the opened positions and their committed values, in any order; they
+    /// must be exactly the leafs of the proof. The depth of the proof must be the one of a tree of
+    /// `len` leafs, so inner nodes can't be opened as leafs of a shallower tree.
+    pub fn verify_multi_proof(
+        hasher: &H,
+        root: &[F],
+        len: usize,
+        indices: &[usize],
+        leafs: &[F],
+        proof: &MerkleMultiProof<F>,
+    ) -> bool {
+        if proof.depth != tree_depth(len)
+            || indices.iter().any(|&index| index >= len)
+            || indices.len() != leafs.len()
+            || proof
+                .salts
+                .as_ref()
+                .is_some_and(|salts| salts.len() != proof.indices.len())
+        {
+            return false;
         }
+        let mut opened = BTreeMap::new();
+        for (&index, leaf) in indices.iter().zip(leafs) {
+            let Ok(position) = proof.indices.binary_search(&index) else {
+                return false;
+            };
+            let salt = proof.salts.as_ref().map(|salts| &salts[position]);
+            let leaf = hash_leaf(hasher, leaf.clone(), salt);
+            if opened
+                .insert(index, leaf.clone())
+                .is_some_and(|other| other != leaf)
+            {
+                return false;
+            }
+        }
+        opened.keys().eq(proof.indices.iter())
+            && opened.values().eq(proof.leafs.iter())
+            && proof.compute_root(hasher).as_deref() == Some(root)
+    }
+
+    /// Verifies several openings `(index, leaf, proof)` against the same root, each like
+    /// [`MerkleTree::verify_proof`] for a tree of `len` leafs.
+    ///
+    /// Inner nodes computed for one opening are remembered by position, so the next opening stops
+    /// hashing as soon as it reaches an already verified node. Returns false on the first
+    /// inconsistent opening.
+    pub fn verify_batch(
+        hasher: &H,
+        root: &[F],
+        len: usize,
+        openings: &[(usize, F, MerkleProof<F>)],
+    ) -> bool {
+        let depth = tree_depth(len);
+        // (level, position) -> node value, only holds nodes whose path to the root was checked
+        let mut verified: HashMap<(usize, usize), MerkleNode<F>> = HashMap::new();
+
+        for (index, leaf, proof) in openings {
+            if *index >= len
+                || proof.index != *index
+                || proof.siblings.len() != depth
+                || !proof
+                    .siblings
+                    .iter()
+                    .all(|sibling| is_digest(hasher, sibling))
+                || proof.leaf != hash_leaf(hasher, leaf.clone(), proof.salt.as_ref())
+            {
+                return false;
+            }
+
+            let mut current_element = proof.leaf.clone();
+            let mut shared = false;
+            for (level, sibling) in proof.siblings.iter().enumerate() {
+                current_element = if proof.is_right_child(level) {
+                    hash_pair(hasher, sibling, &current_element)
+                } else {
+                    hash_pair(hasher, &current_element, sibling)
+                };
+
+                let position = (level + 1, proof.index >> (level + 1));
+                if let Some(node) = verified.get(&position) {
+                    if *node != current_element {
+                        return false;
+                    }
+                    shared = true;
+                    break;
+                }
+                verified.insert(position, current_element.clone());
+            }
+
+            if !shared && current_element != root {
+                return false;
+            }
+        }
+        true
+    }
 
-        current_element == self.root.clone().unwrap()
+    /// [`MerkleTree::verify_batch`] with the openings split in one chunk per thread of the rayon
+    /// pool, every chunk sharing the nodes it verified. Returns false as soon as a chunk fails.
+    #[cfg(feature = "parallel")]
+    pub fn verify_batch_parallel(
+        hasher: &H,
+        root: &[F],
+        len: usize,
+        openings: &[(usize, F, MerkleProof<F>)],
+    ) -> bool
+    where
+        H: Sync,
+        F: Send + Sync,
+    {
+        let chunk_len = openings.len().div_ceil(rayon::current_num_threads()).max(1);
+        openings
+            .par_chunks(chunk_len)
+            .all(|chunk| Self::verify_batch(hasher, root, len, chunk))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::hash::{Hasher, RescueHash};
-    use crate::merkle_tree::MerkleTree;
+    use crate::error::CommitmentError;
+    use crate::hash::{Blake3Hasher, Hasher, RescueHash, WideDigest};
+    use crate::merkle_tree::{
+        hash_leaf, hash_pair, MerkleMultiProof, MerklePadding, MerkleProof, MerkleTree,
+    };
+    use crate::test_utils::{merkle_leafs_with_index, merkle_proof_mutations};
+    use algebra::big_field::{BigPrimeField, U256};
     use algebra::finite_field::FiniteField;
+    use algebra::matrix;
+    use proptest::prelude::*;
     use rand::random;
-    use std::rc::Rc;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::sync::Arc;
 
     #[test]
     fn test_create_merkle_tree() {
-        let finite_field = Rc::new(FiniteField::new(97, 1));
+        let finite_field = Arc::new(FiniteField::new(97, 5));
         let hasher = RescueHash::default();
 
         let element = finite_field.random_element();
@@ -131,16 +571,709 @@ mod tests {
         ];
         let random_index = random::<usize>() % leafs.len();
         leafs[random_index] = element.clone();
-        let mut tree = MerkleTree::new(Rc::clone(&finite_field), hasher.clone(), leafs);
+        let mut tree = MerkleTree::new(hasher.clone(), leafs).unwrap();
         let root = tree.commit();
         assert_eq!(tree.levels.len(), tree.leafs.len().ilog2() as usize + 1);
-        println!("Root: {}", root);
+        println!("Root: {:?}", root);
 
-        let element_hash = hasher.hash(element);
+        let element_hash = hash_leaf(&hasher, element, None);
         let proof = tree.prove(element_hash);
         println!("Proof: {:?}", proof);
-        assert!(proof.is_some());
+        assert!(proof.is_ok());
+
+        assert_eq!(tree.verify(&proof.unwrap()), Ok(true));
+    }
+
+    #[test]
+    fn test_big_field_merkle_tree() {
+        let finite_field = Arc::new(BigPrimeField::stark_252());
+        let leafs: Vec<_> = (0..8u64)
+            .map(|i| finite_field.element(U256::from(i)))
+            .collect();
+        let mut tree = MerkleTree::new(Blake3Hasher, leafs).unwrap();
+        tree.commit();
 
-        assert!(tree.verify(proof.unwrap()));
+        let leaf = finite_field.element(U256::from(5u64));
+        let proof = tree.prove(hash_leaf(&Blake3Hasher, leaf, None));
+        assert_eq!(tree.verify(&proof.unwrap()), Ok(true));
+    }
+
+    #[test]
+    fn test_wide_digests() {
+        let finite_field = Arc::new(FiniteField::new(3221225473, 5));
+        let hasher = WideDigest::new(Blake3Hasher, 4);
+        let leafs: Vec<_> = finite_field.elements(0..6).collect();
+        let mut tree = MerkleTree::new(hasher.clone(), leafs.clone()).unwrap();
+        let root = tree.commit();
+        assert_eq!(root.len(), 4);
+        assert!(tree.levels.iter().flatten().all(|node| node.len() == 4));
+        assert_eq!(tree.levels[0][7], vec![finite_field.zero(); 4]);
+
+        let openings: Vec<_> = (0..6)
+            .map(|index| (index, leafs[index].clone(), tree.open(index).unwrap()))
+            .collect();
+        for (index, leaf, proof) in &openings {
+            assert_eq!(tree.verify(proof), Ok(true));
+            assert!(MerkleTree::verify_proof(
+                &hasher, &root, 6, *index, leaf, proof
+            ));
+        }
+        assert!(MerkleTree::verify_batch(&hasher, &root, 6, &openings));
+        let proof = tree.open_batch(&[1, 4]).unwrap();
+        let opened = [leafs[1].clone(), leafs[4].clone()];
+        assert!(MerkleTree::verify_multi_proof(
+            &hasher,
+            &root,
+            6,
+            &[1, 4],
+            &opened,
+            &proof
+        ));
+
+        // a node of another length is not a digest of the hasher
+        let (index, leaf, proof) = &openings[2];
+        let mut short = proof.clone();
+        short.siblings[1].pop();
+        assert_eq!(short.compute_root(&hasher), None);
+        assert_eq!(tree.verify(&short), Ok(false));
+        assert!(!MerkleTree::verify_proof(
+            &hasher, &root, 6, *index, leaf, &short
+        ));
+        let short_opening = (*index, leaf.clone(), short);
+        assert!(!MerkleTree::verify_batch(
+            &hasher,
+            &root,
+            6,
+            &[short_opening]
+        ));
+        let mut long: MerkleMultiProof = proof.clone().into();
+        long.nodes[0].push(finite_field.zero());
+        assert!(!MerkleTree::verify_multi_proof(
+            &hasher,
+            &root,
+            6,
+            &[*index],
+            std::slice::from_ref(leaf),
+            &long
+        ));
+        assert!(!MerkleTree::verify_proof(
+            &hasher,
+            &root[..1],
+            6,
+            *index,
+            leaf,
+            proof
+        ));
+
+        // the first element of a wide digest is not the narrow digest
+        let mut narrow = MerkleTree::new(Blake3Hasher, leafs).unwrap();
+        assert_ne!(narrow.commit()[0], root[0]);
+    }
+
+    /// fixed parameters over F_97 so every run hashes the same way, the leafs of the tests don't
+    /// collide under it
+    fn fixed_hasher(finite_field: &Arc<FiniteField>) -> RescueHash {
+        RescueHash::builder(Arc::clone(finite_field))
+            .alpha(5)
+            .mds_matrix(matrix!(finite_field, [[2, 3], [5, 7]]))
+            .round_constants(vec![finite_field.element(11); 108])
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_verify_batch() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let hasher = fixed_hasher(&finite_field);
+        let leafs: Vec<_> = finite_field.elements(0..8).collect();
+        let mut tree = MerkleTree::new(hasher.clone(), leafs.clone()).unwrap();
+        let root = tree.commit();
+
+        let openings: Vec<_> = [0, 1, 5, 6]
+            .into_iter()
+            .map(|index| (index, leafs[index].clone(), tree.open(index).unwrap()))
+            .collect();
+        assert!(MerkleTree::verify_batch(&hasher, &root, 8, &openings));
+        assert!(MerkleTree::verify_batch(&hasher, &root, 8, &[]));
+
+        let mut tampered = openings.clone();
+        tampered[2].2.siblings[1][0] = &tampered[2].2.siblings[1][0] + &finite_field.one();
+        assert!(!MerkleTree::verify_batch(&hasher, &root, 8, &tampered));
+
+        // another value, or a valid path claimed for the wrong position
+        let mut wrong_leaf = openings.clone();
+        wrong_leaf[1].1 = leafs[2].clone();
+        assert!(!MerkleTree::verify_batch(&hasher, &root, 8, &wrong_leaf));
+        let mut wrong_index = openings.clone();
+        wrong_index[3].0 = 2;
+        wrong_index[3].2.index = 2;
+        assert!(!MerkleTree::verify_batch(&hasher, &root, 8, &wrong_index));
+
+        let wrong_root = vec![&root[0] + &finite_field.one()];
+        assert!(!MerkleTree::verify_batch(
+            &hasher,
+            &wrong_root,
+            8,
+            &openings
+        ));
+
+        // a path one level short, stopping at the parent of leafs 0 and 1, even after a full opening
+        // of the same subtree filled the cache
+        let shortened = MerkleProof {
+            index: 0,
+            leaf: tree.levels[1][0].clone(),
+            siblings: openings[0].2.siblings[1..].to_vec(),
+            salt: None,
+        };
+        assert_eq!(shortened.compute_root(&hasher), Some(root.clone()));
+        let forged =
+            hasher.hash(openings[0].2.leaf[0].clone()) + openings[0].2.siblings[0][0].clone();
+        let mut with_shortened = openings.clone();
+        with_shortened.push((0, forged, shortened));
+        assert!(!MerkleTree::verify_batch(
+            &hasher,
+            &root,
+            8,
+            &with_shortened
+        ));
+
+        // the duplicated padding is not a committed leaf
+        let mut tree = MerkleTree::with_padding(
+            hasher.clone(),
+            leafs[..3].to_vec(),
+            MerklePadding::DuplicateLast,
+        )
+        .unwrap();
+        let root = tree.commit();
+        let mut padding = tree.open(2).unwrap();
+        padding.index = 3;
+        assert!(!MerkleTree::verify_batch(
+            &hasher,
+            &root,
+            3,
+            &[(3, leafs[2].clone(), padding)]
+        ));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_verify_batch_parallel() {
+        let finite_field = Arc::new(FiniteField::new(3221225473, 5));
+        let leafs: Vec<_> = finite_field.elements(0..64).collect();
+        let mut tree = MerkleTree::new(Blake3Hasher, leafs.clone()).unwrap();
+        let root = tree.commit();
+
+        let mut openings: Vec<_> = (0..64)
+            .step_by(3)
+            .map(|index| (index, leafs[index].clone(), tree.open(index).unwrap()))
+            .collect();
+        assert!(MerkleTree::verify_batch_parallel(
+            &Blake3Hasher,
+            &root,
+            64,
+            &openings
+        ));
+        let last = openings.len() - 1;
+        openings[last].1 = finite_field.zero();
+        assert!(!MerkleTree::verify_batch_parallel(
+            &Blake3Hasher,
+            &root,
+            64,
+            &openings
+        ));
+    }
+
+    #[test]
+    fn test_verify_proof() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let hasher = fixed_hasher(&finite_field);
+        let leafs: Vec<_> = finite_field.elements(10..18).collect();
+        let mut tree = MerkleTree::new(hasher.clone(), leafs.clone()).unwrap();
+        let root = tree.commit();
+        let proofs: Vec<_> = (0..8).map(|index| tree.open(index).unwrap()).collect();
+        drop(tree);
+
+        for (index, (leaf, proof)) in leafs.iter().zip(&proofs).enumerate() {
+            assert!(MerkleTree::verify_proof(
+                &hasher, &root, 8, index, leaf, proof
+            ));
+        }
+        // another leaf, another index or another root
+        assert!(!MerkleTree::verify_proof(
+            &hasher, &root, 8, 3, &leafs[4], &proofs[3]
+        ));
+        assert!(!MerkleTree::verify_proof(
+            &hasher, &root, 8, 4, &leafs[3], &proofs[3]
+        ));
+        let wrong_root = vec![&root[0] + &finite_field.one()];
+        assert!(!MerkleTree::verify_proof(
+            &hasher,
+            &wrong_root,
+            8,
+            3,
+            &leafs[3],
+            &proofs[3]
+        ));
+
+        // an index beyond the depth of the path
+        let mut beyond = proofs[3].clone();
+        beyond.index = 3 + 8;
+        assert!(!MerkleTree::verify_proof(
+            &hasher, &root, 16, 11, &leafs[3], &beyond
+        ));
+        // a valid path for a tree of another size
+        assert!(!MerkleTree::verify_proof(
+            &hasher, &root, 16, 3, &leafs[3], &proofs[3]
+        ));
+        assert!(!MerkleTree::verify_proof(
+            &hasher, &root, 4, 3, &leafs[3], &proofs[3]
+        ));
+    }
+
+    #[test]
+    fn test_rejects_inner_nodes_as_leafs() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let hasher = fixed_hasher(&finite_field);
+        let leafs: Vec<_> = finite_field.elements(10..14).collect();
+        let mut tree = MerkleTree::new(hasher.clone(), leafs.clone()).unwrap();
+        let root = tree.commit();
+
+        // the path of the parent of leafs 0 and 1, one level short, and a value built to hash to that
+        // parent if leaf and inner nodes were hashed alike
+        let proof = tree.open(0).unwrap();
+        let shortened = MerkleProof {
+            index: 0,
+            leaf: tree.levels[1][0].clone(),
+            siblings: proof.siblings[1..].to_vec(),
+            salt: None,
+        };
+        assert_eq!(shortened.compute_root(&hasher), Some(root.clone()));
+        let forged = hasher.hash(proof.leaf[0].clone()) + proof.siblings[0][0].clone();
+        for len in [2, 4] {
+            assert!(!MerkleTree::verify_proof(
+                &hasher, &root, len, 0, &forged, &shortened
+            ));
+        }
+        assert_ne!(
+            hash_leaf(&hasher, forged, None),
+            hash_pair(&hasher, &proof.leaf, &proof.siblings[0])
+        );
+
+        // the duplicated padding has the same path as the last leaf but is not committed
+        let mut tree = MerkleTree::with_padding(
+            hasher.clone(),
+            leafs[..3].to_vec(),
+            MerklePadding::DuplicateLast,
+        )
+        .unwrap();
+        let root = tree.commit();
+        let mut padding = tree.open(2).unwrap();
+        padding.index = 3;
+        assert_eq!(padding.compute_root(&hasher), Some(root.clone()));
+        assert!(!MerkleTree::verify_proof(
+            &hasher, &root, 3, 3, &leafs[2], &padding
+        ));
+    }
+
+    #[test]
+    fn test_open_batch() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let hasher = fixed_hasher(&finite_field);
+        let leafs: Vec<_> = finite_field.elements(20..36).collect();
+        let mut tree = MerkleTree::new(hasher.clone(), leafs.clone()).unwrap();
+        assert!(matches!(
+            tree.open_batch(&[0]),
+            Err(CommitmentError::NotCommitted)
+        ));
+        let root = tree.commit();
+
+        let indices = [9, 1, 0, 5, 9, 14];
+        let opened: Vec<_> = indices.iter().map(|&i| leafs[i].clone()).collect();
+        let proof = tree.open_batch(&indices).unwrap();
+        assert_eq!(proof.indices, vec![0, 1, 5, 9, 14]);
+        assert_eq!(proof.depth, 4);
+        assert!(MerkleTree::verify_multi_proof(
+            &hasher, &root, 16, &indices, &opened, &proof
+        ));
+
+        // smaller than the individual paths, which repeat the nodes shared near the root
+        let individual: usize = proof
+            .indices
+            .iter()
+            .map(|&index| tree.open(index).unwrap().siblings.len())
+            .sum();
+        assert!(proof.nodes.len() < individual);
+
+        // every leaf of the tree needs no sibling at all
+        let all: Vec<_> = (0..16).collect();
+        let full = tree.open_batch(&all).unwrap();
+        assert!(full.nodes.is_empty());
+        assert!(MerkleTree::verify_multi_proof(
+            &hasher, &root, 16, &all, &leafs, &full
+        ));
+
+        // the nodes of level 1 opened as the leafs of a tree of depth 3: they hash to the same root
+        // but are not committed values
+        let shallow = MerkleMultiProof {
+            depth: 3,
+            indices: vec![0, 1],
+            leafs: tree.levels[1][..2].to_vec(),
+            nodes: vec![tree.levels[2][1].clone(), tree.levels[3][1].clone()],
+            salts: None,
+        };
+        assert_eq!(shallow.compute_root(&hasher), Some(root.clone()));
+        assert!(!MerkleTree::verify_multi_proof(
+            &hasher,
+            &root,
+            16,
+            &[0, 1],
+            &leafs[..2],
+            &shallow
+        ));
+        // and indices past the committed leafs
+        assert!(!MerkleTree::verify_multi_proof(
+            &hasher, &root, 14, &all, &leafs, &full
+        ));
+
+        // another value, a missing index, a tampered or missing node, another root
+        let mut wrong_leafs = opened.clone();
+        wrong_leafs[3] = finite_field.element(3);
+        assert!(!MerkleTree::verify_multi_proof(
+            &hasher,
+            &root,
+            16,
+            &indices,
+            &wrong_leafs,
+            &proof
+        ));
+        assert!(!MerkleTree::verify_multi_proof(
+            &hasher,
+            &root,
+            16,
+            &indices[..5],
+            &opened[..5],
+            &proof
+        ));
+        let mut tampered = proof.clone();
+        tampered.nodes[2][0] = &tampered.nodes[2][0] + &finite_field.one();
+        assert!(!MerkleTree::verify_multi_proof(
+            &hasher, &root, 16, &indices, &opened, &tampered
+        ));
+        let mut truncated = proof.clone();
+        truncated.nodes.pop();
+        assert_eq!(truncated.compute_root(&hasher), None);
+        let mut extended = proof.clone();
+        extended.nodes.push(vec![finite_field.zero()]);
+        assert_eq!(extended.compute_root(&hasher), None);
+        let wrong_root = vec![&root[0] + &finite_field.one()];
+        assert!(!MerkleTree::verify_multi_proof(
+            &hasher,
+            &wrong_root,
+            16,
+            &indices,
+            &opened,
+            &proof
+        ));
+
+        assert!(matches!(
+            tree.open_batch(&[]),
+            Err(CommitmentError::EmptyProof)
+        ));
+        assert!(matches!(
+            tree.open_batch(&[3, 16]),
+            Err(CommitmentError::IndexOutOfRange { index: 16, len: 16 })
+        ));
+        let empty = MerkleMultiProof {
+            depth: 0,
+            indices: vec![],
+            leafs: vec![],
+            nodes: vec![],
+            salts: None,
+        };
+        assert!(!MerkleTree::verify_multi_proof(
+            &hasher,
+            &root,
+            16,
+            &[],
+            &[],
+            &empty
+        ));
+    }
+
+    #[test]
+    fn test_open() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let hasher = fixed_hasher(&finite_field);
+        let leafs: Vec<_> = finite_field.elements(0..8).collect();
+        let mut tree = MerkleTree::new(hasher.clone(), leafs).unwrap();
+        assert!(matches!(tree.open(0), Err(CommitmentError::NotCommitted)));
+
+        let root = tree.commit();
+        // committing again doesn't add levels
+        assert_eq!(tree.commit(), root);
+
+        for (index, leaf) in finite_field.elements(0..8).enumerate() {
+            let proof = tree.open(index).unwrap();
+            assert_eq!(proof.index, index);
+            assert_eq!(proof.siblings.len(), 3);
+            assert_eq!(proof, tree.prove(hash_leaf(&hasher, leaf, None)).unwrap());
+            assert_eq!(tree.verify(&proof), Ok(true));
+        }
+
+        // the path of leaf 2 is a valid path to the root from position 3 with the children swapped,
+        // hashing the siblings in order rejects it
+        let mut moved = tree.open(2).unwrap();
+        moved.index = 3;
+        assert_eq!(tree.verify(&moved), Ok(false));
+        moved.index = 10;
+        assert_eq!(
+            tree.verify(&moved),
+            Err(CommitmentError::IndexOutOfRange { index: 10, len: 8 })
+        );
+        assert!(matches!(
+            tree.open(8),
+            Err(CommitmentError::IndexOutOfRange { index: 8, len: 8 })
+        ));
+    }
+
+    #[test]
+    fn test_rejects_mutated_proofs() {
+        // in F_97 any hash collides too often to tell every swapped pair of siblings apart
+        let finite_field = Arc::new(FiniteField::new(3221225473, 5));
+        let hasher = Blake3Hasher;
+        let leafs: Vec<_> = finite_field.elements(0..5).collect();
+        let mut tree = MerkleTree::new(hasher.clone(), leafs.clone()).unwrap();
+        let root = tree.commit();
+
+        for (index, leaf) in leafs.iter().enumerate() {
+            let proof = tree.open(index).unwrap();
+            assert!(MerkleTree::verify_multi_proof(
+                &hasher,
+                &root,
+                5,
+                &[index],
+                std::slice::from_ref(leaf),
+                &proof.clone().into()
+            ));
+            for mutation in merkle_proof_mutations(&proof, 5) {
+                let name = &mutation.name;
+                assert_eq!(tree.verify(&mutation.proof), mutation.expected, "{name}");
+
+                // the verifiers holding only the root tell valid from invalid
+                let index = mutation.proof.index;
+                assert!(
+                    !MerkleTree::verify_proof(&hasher, &root, 5, index, leaf, &mutation.proof),
+                    "{name}"
+                );
+                let opening = (index, leaf.clone(), mutation.proof.clone());
+                assert!(
+                    !MerkleTree::verify_batch(&hasher, &root, 5, &[opening]),
+                    "{name}"
+                );
+                assert!(
+                    !MerkleTree::verify_multi_proof(
+                        &hasher,
+                        &root,
+                        5,
+                        &[index],
+                        std::slice::from_ref(leaf),
+                        &mutation.proof.into()
+                    ),
+                    "{name}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_padding() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let hasher = fixed_hasher(&finite_field);
+        let leafs: Vec<_> = finite_field.elements(1..6).collect();
+        let hashes: Vec<_> = leafs
+            .iter()
+            .map(|leaf| hash_leaf(&hasher, leaf.clone(), None))
+            .collect();
+
+        for (padding, filler) in [
+            (MerklePadding::ZeroHash, vec![finite_field.zero()]),
+            (MerklePadding::DuplicateLast, hashes[4].clone()),
+        ] {
+            let mut tree =
+                MerkleTree::with_padding(hasher.clone(), leafs.clone(), padding).unwrap();
+            let root = tree.commit();
+            assert_eq!(tree.levels[0].len(), 8);
+            assert_eq!(tree.levels.len(), 4);
+
+            // the same root as the leaf level padded by hand
+            let mut padded = hashes.clone();
+            padded.resize(8, filler);
+            let mut nodes = padded;
+            while nodes.len() > 1 {
+                nodes = nodes
+                    .chunks(2)
+                    .map(|pair| hash_pair(&hasher, &pair[0], &pair[1]))
+                    .collect();
+            }
+            assert_eq!(root, nodes[0]);
+
+            for (index, leaf) in leafs.iter().enumerate() {
+                let proof = tree.open(index).unwrap();
+                assert_eq!(tree.verify(&proof), Ok(true));
+                assert!(MerkleTree::verify_proof(
+                    &hasher, &root, 5, index, leaf, &proof
+                ));
+            }
+            // the padding is not a leaf
+            assert!(matches!(
+                tree.open(5),
+                Err(CommitmentError::IndexOutOfRange { index: 5, len: 5 })
+            ));
+            let mut padding_proof = tree.open(4).unwrap();
+            padding_proof.index = 5;
+            padding_proof.leaf = tree.levels[0][5].clone();
+            assert_eq!(
+                tree.verify(&padding_proof),
+                Err(CommitmentError::IndexOutOfRange { index: 5, len: 5 })
+            );
+        }
+
+        // `new` pads with zeros, a single leaf is its own root
+        let tree = MerkleTree::new(hasher.clone(), leafs.clone()).unwrap();
+        assert_eq!(tree.levels[0][7], vec![finite_field.zero()]);
+        let mut single = MerkleTree::new(hasher.clone(), leafs[..1].to_vec()).unwrap();
+        assert_eq!(single.commit(), hashes[0]);
+    }
+
+    #[test]
+    fn test_salted_leafs() {
+        let finite_field = Arc::new(FiniteField::new(3221225473, 5));
+        let hasher = Blake3Hasher;
+        // repeated values, only the salts tell their nodes apart
+        let leafs = vec![finite_field.element(7); 5];
+        let salted = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            MerkleTree::with_salt(
+                Blake3Hasher,
+                leafs.clone(),
+                MerklePadding::ZeroHash,
+                &mut rng,
+            )
+            .unwrap()
+        };
+
+        let mut tree = salted(1);
+        let root = tree.commit();
+        assert_eq!(salted(1).commit(), root);
+        assert_ne!(salted(2).commit(), root);
+        assert_ne!(tree.levels[0][0], tree.levels[0][1]);
+        assert_ne!(
+            tree.levels[0][0],
+            hash_leaf(&hasher, leafs[0].clone(), None)
+        );
+
+        for (index, leaf) in leafs.iter().enumerate() {
+            let proof = tree.open(index).unwrap();
+            assert!(proof.salt.is_some());
+            assert_eq!(tree.verify(&proof), Ok(true));
+            assert!(MerkleTree::verify_proof(
+                &hasher, &root, 5, index, leaf, &proof
+            ));
+
+            // the value can't be checked without its salt
+            let unsalted = MerkleProof {
+                salt: None,
+                ..proof.clone()
+            };
+            assert!(!MerkleTree::verify_proof(
+                &hasher, &root, 5, index, leaf, &unsalted
+            ));
+        }
+
+        let indices = [4, 0, 1];
+        let opened = vec![leafs[0].clone(); 3];
+        let proof = tree.open_batch(&indices).unwrap();
+        assert_eq!(
+            proof.salts,
+            Some(
+                [0, 1, 4]
+                    .map(|index| tree.open(index).unwrap().salt.unwrap())
+                    .to_vec()
+            )
+        );
+        assert!(MerkleTree::verify_multi_proof(
+            &hasher, &root, 5, &indices, &opened, &proof
+        ));
+        let mut swapped = proof.clone();
+        swapped.salts.as_mut().unwrap().swap(0, 1);
+        assert!(!MerkleTree::verify_multi_proof(
+            &hasher, &root, 5, &indices, &opened, &swapped
+        ));
+        let mut missing = proof.clone();
+        missing.salts.as_mut().unwrap().pop();
+        assert!(!MerkleTree::verify_multi_proof(
+            &hasher, &root, 5, &indices, &opened, &missing
+        ));
+
+        // unsalted trees open without salts
+        let mut plain = MerkleTree::new(Blake3Hasher, leafs.clone()).unwrap();
+        plain.commit();
+        assert_eq!(plain.open(0).unwrap().salt, None);
+        assert_eq!(plain.open_batch(&[0, 3]).unwrap().salts, None);
+    }
+
+    #[test]
+    fn test_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<MerkleTree<RescueHash>>();
+    }
+
+    #[test]
+    fn test_merkle_tree_errors() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let hasher = RescueHash::default();
+
+        assert!(matches!(
+            MerkleTree::new(hasher.clone(), vec![]),
+            Err(CommitmentError::EmptyLeafs)
+        ));
+        let leafs = vec![finite_field.element(1), finite_field.element(2)];
+        let mut tree = MerkleTree::new(hasher, leafs).unwrap();
+        assert_eq!(
+            tree.prove(vec![finite_field.element(1)]),
+            Err(CommitmentError::NotCommitted)
+        );
+        let proof = MerkleProof {
+            index: 0,
+            leaf: vec![finite_field.element(1)],
+            siblings: vec![],
+            salt: None,
+        };
+        assert_eq!(tree.verify(&proof), Err(CommitmentError::NotCommitted));
+
+        tree.commit();
+        assert_eq!(
+            tree.verify(&proof),
+            Err(CommitmentError::PathLengthMismatch {
+                expected: 1,
+                found: 0
+            })
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_prove_verify_any_leaf(
+            (leafs, index) in merkle_leafs_with_index(Arc::new(FiniteField::new(97, 5)), 5)
+        ) {
+            let hasher = RescueHash::default();
+            let leaf_hash = hash_leaf(&hasher, leafs[index].clone(), None);
+
+            let mut tree = MerkleTree::new(hasher, leafs).unwrap();
+            tree.commit();
+            let proof = tree.prove(leaf_hash);
+            prop_assert!(proof.is_ok());
+            prop_assert_eq!(tree.verify(&proof.unwrap()), Ok(true));
+        }
     }
 }
@@ -0,0 +1,393 @@
+use crate::error::ProofOptionsError;
+use algebra::finite_field::{FieldElement, FieldSize, FiniteField};
+use std::sync::Arc;
+
+/// The hasher of the Merkle commitments, see [`crate::hash`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashKind {
+    /// [`RescueHash`](crate::hash::RescueHash)
+    Rescue,
+    /// [`Sha256Hasher`](crate::hash::Sha256Hasher)
+    Sha256,
+    /// [`Blake3Hasher`](crate::hash::Blake3Hasher)
+    Blake3,
+}
+
+impl HashKind {
+    pub fn id(&self) -> u8 {
+        match self {
+            HashKind::Rescue => 0,
+            HashKind::Sha256 => 1,
+            HashKind::Blake3 => 2,
+        }
+    }
+
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(HashKind::Rescue),
+            1 => Some(HashKind::Sha256),
+            2 => Some(HashKind::Blake3),
+            _ => None,
+        }
+    }
+
+    /// Birthday bound of a digest over `field`, half its bits. Every kind digests to a single element,
+    /// the state element of Rescue or 128 bits of the byte digest reduced modulo `p`, so a 256 bit
+    /// hash is no stronger than the field.
+    pub fn collision_bits(&self, field: &FiniteField) -> u32 {
+        match self {
+            HashKind::Rescue | HashKind::Sha256 | HashKind::Blake3 => field.prime.ilog2() / 2,
+        }
+    }
+}
+
+/// How trace and composition columns are combined into the DEEP composition polynomial.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeepBatching {
+    /// a fresh random coefficient per column
+    Independent,
+    /// the powers 1, α, α^2, ... of a single challenge α
+    Powers,
+}
+
+impl DeepBatching {
+    pub fn id(&self) -> u8 {
+        match self {
+            DeepBatching::Independent => 0,
+            DeepBatching::Powers => 1,
+        }
+    }
+
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(DeepBatching::Independent),
+            1 => Some(DeepBatching::Powers),
+            _ => None,
+        }
+    }
+
+    /// `num_columns` coefficients, `draw` returns the next transcript challenge
+    pub fn coefficients(
+        &self,
+        num_columns: usize,
+        mut draw: impl FnMut() -> FieldElement,
+    ) -> Vec<FieldElement> {
+        match self {
+            DeepBatching::Independent => (0..num_columns).map(|_| draw()).collect(),
+            DeepBatching::Powers => {
+                let alpha = draw();
+                let mut coefficients = Vec::with_capacity(num_columns);
+                let mut power = alpha.finite_field().one();
+                for _ in 0..num_columns {
+                    coefficients.push(power.clone());
+                    power = &power * &alpha;
+                }
+                coefficients
+            }
+        }
+    }
+}
+
+/// Parameters shared by the prover and the verifier.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProofOptions {
+    /// ratio between the evaluation domain and the trace length
+    pub blowup: usize,
+    pub num_queries: usize,
+    /// proof of work bits required before the query phase
+    pub grinding_bits: u8,
+    /// how many FRI layer elements are folded into one
+    pub folding_factor: usize,
+    /// FRI stops folding once the layer fits a polynomial of this degree and sends its coefficients
+    pub fri_remainder_max_degree: usize,
+    pub hash_kind: HashKind,
+    pub deep_batching: DeepBatching,
+    pub field: FiniteField,
+}
+
+pub const MAX_GRINDING_BITS: u8 = 32;
+pub const FOLDING_FACTORS: [usize; 4] = [2, 4, 8, 16];
+pub const MAX_REMAINDER_DEGREE: usize = 255;
+
+/// 3 * 2^30 + 1, generator 5
+const DEFAULT_PRIME: FieldSize = 3221225473;
+const DEFAULT_GENERATOR: FieldSize = 5;
+
+/// 2^61 + 20 * 2^32 + 1, the ethSTARK field, generator 3
+pub const ETHSTARK_PRIME: FieldSize = 2305843095113039873;
+const ETHSTARK_GENERATOR: FieldSize = 3;
+
+impl ProofOptions {
+    pub fn new(
+        blowup: usize,
+        num_queries: usize,
+        grinding_bits: u8,
+        folding_factor: usize,
+        fri_remainder_max_degree: usize,
+        hash_kind: HashKind,
+        field: FiniteField,
+    ) -> Result<Self, ProofOptionsError> {
+        let options = Self {
+            blowup,
+            num_queries,
+            grinding_bits,
+            folding_factor,
+            fri_remainder_max_degree,
+            hash_kind,
+            deep_batching: DeepBatching::Independent,
+            field,
+        };
+        options.validate()?;
+        Ok(options)
+    }
+
+    pub fn with_deep_batching(mut self, deep_batching: DeepBatching) -> Self {
+        self.deep_batching = deep_batching;
+        self
+    }
+
+    /// 27 queries at blowup 8 plus 16 grinding bits, 96 bits for the query phase alone. The challenges
+    /// of the default 31-bit field cap the whole proof far below, see [`ProofOptions::security_bits`].
+    pub fn default_96_bit() -> Self {
+        Self::new(
+            8,
+            27,
+            16,
+            2,
+            7,
+            HashKind::Rescue,
+            FiniteField::new(DEFAULT_PRIME, DEFAULT_GENERATOR),
+        )
+        .expect("valid preset")
+    }
+
+    /// 28 queries at blowup 16 plus 16 grinding bits, 128 bits for the query phase alone, over the same
+    /// field as [`ProofOptions::default_96_bit`]
+    pub fn default_128_bit() -> Self {
+        Self::new(
+            16,
+            28,
+            16,
+            2,
+            7,
+            HashKind::Rescue,
+            FiniteField::new(DEFAULT_PRIME, DEFAULT_GENERATOR),
+        )
+        .expect("valid preset")
+    }
+
+    /// ethSTARK/Stone style profile: blowup 16, 18 queries, 24 grinding bits, FRI steps of 4 layers
+    /// and a last layer below degree 64, over the ethSTARK field and committed with a byte hash
+    pub fn ethstark() -> Self {
+        Self::new(
+            16,
+            18,
+            24,
+            16,
+            63,
+            HashKind::Blake3,
+            FiniteField::new(ETHSTARK_PRIME, ETHSTARK_GENERATOR),
+        )
+        .expect("valid preset")
+    }
+
+    pub fn validate(&self) -> Result<(), ProofOptionsError> {
+        if self.blowup < 2 || !self.blowup.is_power_of_two() {
+            return Err(ProofOptionsError::InvalidBlowup(self.blowup));
+        }
+        if self.num_queries == 0 {
+            return Err(ProofOptionsError::NoQueries);
+        }
+        if self.grinding_bits > MAX_GRINDING_BITS {
+            return Err(ProofOptionsError::TooManyGrindingBits(self.grinding_bits));
+        }
+        if !FOLDING_FACTORS.contains(&self.folding_factor) {
+            return Err(ProofOptionsError::InvalidFoldingFactor(self.folding_factor));
+        }
+        if self.fri_remainder_max_degree > MAX_REMAINDER_DEGREE
+            || !(self.fri_remainder_max_degree + 1).is_power_of_two()
+        {
+            return Err(ProofOptionsError::InvalidRemainderDegree(
+                self.fri_remainder_max_degree,
+            ));
+        }
+        if self.field.prime < 3 {
+            return Err(ProofOptionsError::InvalidField(self.field.prime));
+        }
+        Ok(())
+    }
+
+    /// conjectured soundness of the query phase: log2(blowup) bits per query plus grinding
+    pub fn query_security_bits(&self) -> u32 {
+        self.num_queries as u32 * self.blowup.ilog2() + self.grinding_bits as u32
+    }
+
+    /// Conjectured soundness of the proof: the query phase, capped by `log2(p)` since the DEEP and
+    /// FRI challenges are drawn from the field, and by the collision resistance of the Merkle digests.
+    pub fn security_bits(&self) -> u32 {
+        self.query_security_bits()
+            .min(self.field.prime.ilog2())
+            .min(self.hash_kind.collision_bits(&self.field))
+    }
+
+    /// number of FRI folding rounds for an evaluation domain of `domain_size` points,
+    /// the last layer has at most `(fri_remainder_max_degree + 1) * blowup` points
+    pub fn fri_num_layers(&self, domain_size: usize) -> usize {
+        let remainder_size = (self.fri_remainder_max_degree + 1) * self.blowup;
+        let mut layer_size = domain_size;
+        let mut layers = 0;
+        while layer_size > remainder_size {
+            layer_size /= self.folding_factor;
+            layers += 1;
+        }
+        layers
+    }
+
+    pub fn finite_field(&self) -> Arc<FiniteField> {
+        Arc::new(self.field.clone())
+    }
+}
+
+impl Default for ProofOptions {
+    fn default() -> Self {
+        Self::default_96_bit()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::ProofOptionsError;
+    use crate::proof_options::{DeepBatching, HashKind, ProofOptions};
+    use algebra::finite_field::FiniteField;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_presets() {
+        assert!(ProofOptions::default_96_bit().query_security_bits() >= 96);
+        assert!(ProofOptions::default_128_bit().query_security_bits() >= 128);
+        assert_eq!(ProofOptions::default(), ProofOptions::default_96_bit());
+        // digests of a single element of 3 * 2^30 + 1
+        assert_eq!(ProofOptions::default_96_bit().security_bits(), 15);
+        assert_eq!(ProofOptions::default_128_bit().security_bits(), 15);
+
+        let ethstark = ProofOptions::ethstark();
+        assert_eq!(ethstark.hash_kind, HashKind::Blake3);
+        assert_eq!(ethstark.query_security_bits(), 96);
+        assert_eq!(ethstark.security_bits(), 30);
+        // below the field, the query phase is the estimate
+        let mut few_queries = ethstark.clone();
+        few_queries.num_queries = 1;
+        assert_eq!(few_queries.security_bits(), 4 + 24);
+        // 2^20 trace rows at blowup 16, folded by 16 down to 64 * 16 points
+        assert_eq!(ethstark.fri_num_layers(1 << 24), 4);
+    }
+
+    #[test]
+    fn test_hash_kinds() {
+        for hash_kind in [HashKind::Rescue, HashKind::Sha256, HashKind::Blake3] {
+            assert_eq!(HashKind::from_id(hash_kind.id()), Some(hash_kind));
+        }
+        assert_eq!(HashKind::from_id(3), None);
+
+        let small = FiniteField::new(3221225473, 5);
+        let large = FiniteField::new((1 << 107) - 1, 3);
+        assert_eq!(HashKind::Rescue.collision_bits(&small), 15);
+        assert_eq!(HashKind::Blake3.collision_bits(&small), 15);
+        assert_eq!(HashKind::Rescue.collision_bits(&large), 53);
+        assert_eq!(HashKind::Sha256.collision_bits(&large), 53);
+    }
+
+    #[test]
+    fn test_validation() {
+        let field = FiniteField::new(97, 5);
+        let options = |blowup, num_queries, grinding_bits, folding_factor| {
+            ProofOptions::new(
+                blowup,
+                num_queries,
+                grinding_bits,
+                folding_factor,
+                7,
+                HashKind::Rescue,
+                field.clone(),
+            )
+        };
+        assert!(options(4, 10, 0, 2).is_ok());
+        assert_eq!(
+            options(6, 10, 0, 2),
+            Err(ProofOptionsError::InvalidBlowup(6))
+        );
+        assert_eq!(
+            options(1, 10, 0, 2),
+            Err(ProofOptionsError::InvalidBlowup(1))
+        );
+        assert_eq!(options(4, 0, 0, 2), Err(ProofOptionsError::NoQueries));
+        assert_eq!(
+            options(4, 10, 33, 2),
+            Err(ProofOptionsError::TooManyGrindingBits(33))
+        );
+        assert_eq!(
+            options(4, 10, 0, 3),
+            Err(ProofOptionsError::InvalidFoldingFactor(3))
+        );
+
+        let remainder = |fri_remainder_max_degree| {
+            ProofOptions::new(
+                4,
+                10,
+                0,
+                2,
+                fri_remainder_max_degree,
+                HashKind::Rescue,
+                field.clone(),
+            )
+        };
+        assert!(remainder(0).is_ok());
+        assert!(remainder(255).is_ok());
+        assert_eq!(
+            remainder(6),
+            Err(ProofOptionsError::InvalidRemainderDegree(6))
+        );
+        assert_eq!(
+            remainder(511),
+            Err(ProofOptionsError::InvalidRemainderDegree(511))
+        );
+    }
+
+    #[test]
+    fn test_fri_num_layers() {
+        let mut options = ProofOptions::default_96_bit();
+        // remainder layer of 8 * 8 = 64 points
+        assert_eq!(options.fri_num_layers(64), 0);
+        assert_eq!(options.fri_num_layers(1 << 10), 4);
+
+        options.fri_remainder_max_degree = 0;
+        assert_eq!(options.fri_num_layers(1 << 10), 7);
+
+        options.folding_factor = 4;
+        assert_eq!(options.fri_num_layers(1 << 10), 4);
+    }
+
+    #[test]
+    fn test_deep_batching() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let mut challenges = finite_field.elements([3, 10, 20]);
+
+        let independent = DeepBatching::Independent.coefficients(3, || challenges.next().unwrap());
+        assert_eq!(
+            independent,
+            finite_field.elements([3, 10, 20]).collect::<Vec<_>>()
+        );
+
+        let mut challenges = finite_field.elements([3]);
+        let powers = DeepBatching::Powers.coefficients(4, || challenges.next().unwrap());
+        assert_eq!(
+            powers,
+            finite_field.elements([1, 3, 9, 27]).collect::<Vec<_>>()
+        );
+
+        for batching in [DeepBatching::Independent, DeepBatching::Powers] {
+            assert_eq!(DeepBatching::from_id(batching.id()), Some(batching));
+        }
+        assert_eq!(DeepBatching::from_id(2), None);
+    }
+}
@@ -0,0 +1,79 @@
+//! Query index derivation, shared by the prover and the verifier.
+//!
+//! Indices are expanded from a seed, the hash of the final transcript state, and the optional
+//! proof of work nonce:
+//!
+//! ```text
+//! block_i = SHA-256(seed | nonce as u64 LE, if any | i as u64 LE)
+//! ```
+//!
+//! Every block gives four indices, its 8 byte little-endian words masked to `domain_size - 1`.
+//! The domain size is a power of two, so the mask keeps the indices uniform. Indices may repeat.
+use sha2::{Digest, Sha256};
+
+pub fn derive_query_indices(
+    seed: &[u8],
+    nonce: Option<u64>,
+    domain_size: usize,
+    num_queries: usize,
+) -> Vec<usize> {
+    assert!(
+        domain_size.is_power_of_two(),
+        "The evaluation domain size must be a power of 2"
+    );
+    let mask = (domain_size - 1) as u64;
+
+    let mut indices = Vec::with_capacity(num_queries);
+    let mut counter: u64 = 0;
+    while indices.len() < num_queries {
+        let mut hasher = Sha256::new();
+        hasher.update(seed);
+        if let Some(nonce) = nonce {
+            hasher.update(nonce.to_le_bytes());
+        }
+        hasher.update(counter.to_le_bytes());
+        let block = hasher.finalize();
+
+        for word in block.chunks_exact(8) {
+            if indices.len() == num_queries {
+                break;
+            }
+            let word = u64::from_le_bytes(word.try_into().expect("8 byte chunk"));
+            indices.push((word & mask) as usize);
+        }
+        counter += 1;
+    }
+    indices
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::queries::derive_query_indices;
+
+    #[test]
+    fn test_derive_query_indices() {
+        let indices = derive_query_indices(b"transcript state", None, 1 << 10, 27);
+        assert_eq!(indices.len(), 27);
+        assert!(indices.iter().all(|index| *index < 1 << 10));
+        assert_eq!(
+            indices,
+            derive_query_indices(b"transcript state", None, 1 << 10, 27)
+        );
+
+        // the first indices don't depend on how many are requested
+        assert_eq!(
+            derive_query_indices(b"transcript state", None, 1 << 10, 5),
+            indices[..5]
+        );
+
+        assert_ne!(
+            derive_query_indices(b"transcript state", Some(7), 1 << 10, 27),
+            indices
+        );
+        assert_ne!(
+            derive_query_indices(b"other state", None, 1 << 10, 27),
+            indices
+        );
+        assert_eq!(derive_query_indices(b"seed", None, 1, 3), vec![0, 0, 0]);
+    }
+}
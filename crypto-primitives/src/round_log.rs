@@ -0,0 +1,181 @@
+//! Optional JSON-lines log of the data exchanged in every protocol round.
+//!
+//! Prover and verifier write the same events in the same order, so the first line where their logs differ
+//! points at the transcript step where they stopped agreeing (see [`first_divergence`]).
+use algebra::finite_field::FieldElement;
+use std::io::Write;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RoundEvent {
+    Absorb { label: String, data: Vec<u8> },
+    Squeeze { label: String, data: Vec<u8> },
+    Commitment { label: String, root: FieldElement },
+    Challenge { label: String, value: FieldElement },
+    QueryIndex { index: usize },
+}
+
+pub trait RoundLogger {
+    fn log(&mut self, event: &RoundEvent) -> std::io::Result<()>;
+}
+
+/// logger used when no log was requested
+pub struct NoopLogger;
+
+impl RoundLogger for NoopLogger {
+    fn log(&mut self, _event: &RoundEvent) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// writes one JSON object per event, numbered by step
+pub struct JsonLinesLogger<W: Write> {
+    writer: W,
+    step: usize,
+}
+
+impl<W: Write> JsonLinesLogger<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer, step: 0 }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> RoundLogger for JsonLinesLogger<W> {
+    fn log(&mut self, event: &RoundEvent) -> std::io::Result<()> {
+        let body = match event {
+            RoundEvent::Absorb { label, data } => format!(
+                r#""event":"absorb","label":"{}","data":"{}""#,
+                escape(label),
+                hex(data)
+            ),
+            RoundEvent::Squeeze { label, data } => format!(
+                r#""event":"squeeze","label":"{}","data":"{}""#,
+                escape(label),
+                hex(data)
+            ),
+            RoundEvent::Commitment { label, root } => format!(
+                r#""event":"commitment","label":"{}","root":"{}""#,
+                escape(label),
+                root.value()
+            ),
+            RoundEvent::Challenge { label, value } => format!(
+                r#""event":"challenge","label":"{}","value":"{}""#,
+                escape(label),
+                value.value()
+            ),
+            RoundEvent::QueryIndex { index } => {
+                format!(r#""event":"query_index","index":{}"#, index)
+            }
+        };
+        writeln!(self.writer, r#"{{"step":{},{}}}"#, self.step, body)?;
+        self.step += 1;
+        Ok(())
+    }
+}
+
+fn hex(data: &[u8]) -> String {
+    data.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn escape(value: &str) -> String {
+    let mut output = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            c if c.is_control() => output.push_str(&format!("\\u{:04x}", c as u32)),
+            c => output.push(c),
+        }
+    }
+    output
+}
+
+/// first line (0-based) where the two logs differ, together with both lines
+pub fn first_divergence(prover_log: &str, verifier_log: &str) -> Option<(usize, String, String)> {
+    let mut prover_lines = prover_log.lines();
+    let mut verifier_lines = verifier_log.lines();
+    let mut line = 0;
+    loop {
+        match (prover_lines.next(), verifier_lines.next()) {
+            (None, None) => return None,
+            (Some(p), Some(v)) if p == v => line += 1,
+            (p, v) => {
+                return Some((
+                    line,
+                    p.unwrap_or_default().to_string(),
+                    v.unwrap_or_default().to_string(),
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::round_log::{first_divergence, JsonLinesLogger, RoundEvent, RoundLogger};
+    use algebra::finite_field::FiniteField;
+    use std::sync::Arc;
+
+    fn events(challenge: i128) -> Vec<RoundEvent> {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        vec![
+            RoundEvent::Absorb {
+                label: "seed".to_string(),
+                data: vec![0x0a, 0xff],
+            },
+            RoundEvent::Commitment {
+                label: "trace".to_string(),
+                root: finite_field.element(42),
+            },
+            RoundEvent::Challenge {
+                label: "alpha".to_string(),
+                value: finite_field.element(challenge),
+            },
+            RoundEvent::QueryIndex { index: 3 },
+        ]
+    }
+
+    fn write_log(events: &[RoundEvent]) -> String {
+        let mut logger = JsonLinesLogger::new(Vec::new());
+        for event in events {
+            logger.log(event).unwrap();
+        }
+        String::from_utf8(logger.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn test_json_lines() {
+        let log = write_log(&events(7));
+        let lines: Vec<&str> = log.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                r#"{"step":0,"event":"absorb","label":"seed","data":"0aff"}"#,
+                r#"{"step":1,"event":"commitment","label":"trace","root":"42"}"#,
+                r#"{"step":2,"event":"challenge","label":"alpha","value":"7"}"#,
+                r#"{"step":3,"event":"query_index","index":3}"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_first_divergence() {
+        let prover_log = write_log(&events(7));
+        assert_eq!(first_divergence(&prover_log, &prover_log), None);
+
+        let verifier_log = write_log(&events(8));
+        let (line, _, verifier_line) = first_divergence(&prover_log, &verifier_log).unwrap();
+        assert_eq!(line, 2);
+        assert!(verifier_line.contains(r#""value":"8""#));
+
+        let truncated: String = prover_log
+            .lines()
+            .take(2)
+            .map(|l| format!("{}\n", l))
+            .collect();
+        assert_eq!(first_divergence(&prover_log, &truncated).unwrap().0, 2);
+    }
+}
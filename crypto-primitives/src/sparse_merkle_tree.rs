@@ -0,0 +1,346 @@
+//! Sparse Merkle tree committing to a key-value map, for state commitments.
+//!
+//! Every key of `depth` bits has its own leaf, at the position given by the key, so a proof for an
+//! absent key (its leaf is empty) is a proof of non-membership. A present value is stored as its hash
+//! and an empty leaf is zero, like the padding of [`MerkleTree`](crate::merkle_tree::MerkleTree).
+//! Only nodes above present values are stored, the others are the default hashes of empty subtrees.
+//!
+//! Keys are fixed-width integers; a tree keyed by field elements ([`SparseMerkleTree::with_field_keys`])
+//! uses their canonical value, `key.value() as u128`.
+use crate::error::CommitmentError;
+use crate::hash::Hasher;
+use crate::merkle_tree::{hash_leaf, hash_pair, is_digest, MerkleNode};
+use algebra::field::Field;
+use algebra::finite_field::{FieldElement, FieldSize, FiniteField};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// the widest keys, `u128`
+pub const MAX_SPARSE_DEPTH: usize = 128;
+
+pub struct SparseMerkleTree<H: Hasher<F>, F: Field = FieldElement> {
+    hasher: H,
+    depth: usize,
+    values: HashMap<u128, F>,
+    /// (level, position) -> node, only for subtrees holding a value
+    nodes: HashMap<(usize, u128), MerkleNode<F>>,
+    /// root of an empty subtree at every level, from the empty leaf up to the empty tree
+    defaults: Vec<MerkleNode<F>>,
+}
+
+/// The siblings on the path from the leaf of `key` to the root, from the leafs up. Bit `i` of the key
+/// is the side of the node at level `i`, 0 for a left child, as for
+/// [`MerkleProof`](crate::merkle_tree::MerkleProof).
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseMerkleProof<F: Field = FieldElement> {
+    pub key: u128,
+    pub siblings: Vec<MerkleNode<F>>,
+}
+
+impl<H: Hasher<F>, F: Field> SparseMerkleTree<H, F> {
+    /// Empty tree of `2^depth` leafs, `zero` being any element of the field of the nodes.
+    pub fn new(hasher: H, depth: usize, zero: F) -> Result<Self, CommitmentError> {
+        if depth == 0 || depth > MAX_SPARSE_DEPTH {
+            return Err(CommitmentError::InvalidDepth(depth));
+        }
+        let mut defaults = Vec::with_capacity(depth + 1);
+        defaults.push(vec![zero.zero(); hasher.digest_len()]);
+        for level in 0..depth {
+            let empty = defaults[level].clone();
+            defaults.push(hash_pair(&hasher, &empty, &empty));
+        }
+        Ok(SparseMerkleTree {
+            hasher,
+            depth,
+            values: HashMap::new(),
+            nodes: HashMap::new(),
+            defaults,
+        })
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn root(&self) -> MerkleNode<F> {
+        self.node(self.depth, 0)
+    }
+
+    pub fn get(&self, key: u128) -> Option<&F> {
+        self.values.get(&key)
+    }
+
+    /// Sets the value of `key` and rehashes its path, `depth` hashes. Returns the previous value.
+    pub fn insert(&mut self, key: u128, value: F) -> Result<Option<F>, CommitmentError> {
+        self.check_key(key)?;
+        let leaf = hash_leaf(&self.hasher, value.clone(), None);
+        self.update(key, leaf);
+        Ok(self.values.insert(key, value))
+    }
+
+    /// Empties the leaf of `key`, the nodes left without values are dropped. Returns the removed value.
+    pub fn remove(&mut self, key: u128) -> Result<Option<F>, CommitmentError> {
+        self.check_key(key)?;
+        let previous = self.values.remove(&key);
+        if previous.is_some() {
+            self.update(key, self.defaults[0].clone());
+        }
+        Ok(previous)
+    }
+
+    /// Path of the leaf of `key`, proving its value if present and its absence otherwise.
+    pub fn prove(&self, key: u128) -> Result<SparseMerkleProof<F>, CommitmentError> {
+        self.check_key(key)?;
+        let siblings = (0..self.depth)
+            .map(|level| self.node(level, (key >> level) ^ 1))
+            .collect();
+        Ok(SparseMerkleProof { key, siblings })
+    }
+
+    /// Verifies knowing only the root that `key` holds `value`, or that it is absent when `value` is
+    /// `None`. The depth of the tree is the length of the path.
+    pub fn verify_proof(
+        hasher: &H,
+        root: &[F],
+        key: u128,
+        value: Option<&F>,
+        proof: &SparseMerkleProof<F>,
+    ) -> bool {
+        if proof.key != key
+            || proof.siblings.is_empty()
+            || proof.siblings.len() > MAX_SPARSE_DEPTH
+            || key.checked_shr(proof.siblings.len() as u32).unwrap_or(0) != 0
+            || !is_digest(hasher, root)
+            || !proof
+                .siblings
+                .iter()
+                .all(|sibling| is_digest(hasher, sibling))
+        {
+            return false;
+        }
+        let leaf = match value {
+            Some(value) => hash_leaf(hasher, value.clone(), None),
+            None => root.iter().map(F::zero).collect(),
+        };
+        let computed = proof
+            .siblings
+            .iter()
+            .enumerate()
+            .fold(leaf, |node, (level, sibling)| {
+                if (key >> level) & 1 == 1 {
+                    hash_pair(hasher, sibling, &node)
+                } else {
+                    hash_pair(hasher, &node, sibling)
+                }
+            });
+        computed == root
+    }
+
+    fn check_key(&self, key: u128) -> Result<(), CommitmentError> {
+        match key.checked_shr(self.depth as u32).unwrap_or(0) {
+            0 => Ok(()),
+            _ => Err(CommitmentError::KeyOutOfRange {
+                key,
+                depth: self.depth,
+            }),
+        }
+    }
+
+    fn node(&self, level: usize, position: u128) -> MerkleNode<F> {
+        self.nodes
+            .get(&(level, position))
+            .cloned()
+            .unwrap_or_else(|| self.defaults[level].clone())
+    }
+
+    /// writes `leaf` and every node above it, only keeping the ones different from the defaults
+    fn update(&mut self, key: u128, leaf: MerkleNode<F>) {
+        let mut node = leaf;
+        for level in 0..=self.depth {
+            // the root is at level `depth`, where a 128-bit key shifts out completely
+            let position = key.checked_shr(level as u32).unwrap_or(0);
+            if node == self.defaults[level] {
+                self.nodes.remove(&(level, position));
+            } else {
+                self.nodes.insert((level, position), node.clone());
+            }
+            if level == self.depth {
+                break;
+            }
+            let sibling = self.node(level, position ^ 1);
+            node = if position & 1 == 1 {
+                hash_pair(&self.hasher, &sibling, &node)
+            } else {
+                hash_pair(&self.hasher, &node, &sibling)
+            };
+        }
+    }
+}
+
+impl<H: Hasher> SparseMerkleTree<H> {
+    /// Tree with a leaf for every element of `finite_field`, as many levels as the prime has bits.
+    pub fn with_field_keys(
+        hasher: H,
+        finite_field: &Arc<FiniteField>,
+    ) -> Result<Self, CommitmentError> {
+        let depth = (FieldSize::BITS - finite_field.prime.leading_zeros()) as usize;
+        Self::new(hasher, depth, finite_field.zero())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::CommitmentError;
+    use crate::hash::Blake3Hasher;
+    use crate::merkle_tree::{hash_leaf, hash_pair};
+    use crate::sparse_merkle_tree::{SparseMerkleProof, SparseMerkleTree};
+    use algebra::finite_field::FiniteField;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_membership() {
+        let finite_field = Arc::new(FiniteField::new(3221225473, 5));
+        let hasher = Blake3Hasher;
+        let mut tree = SparseMerkleTree::with_field_keys(Blake3Hasher, &finite_field).unwrap();
+        assert_eq!(tree.depth(), 32);
+        let empty_root = tree.root();
+
+        let entries = [(5, 50), (6, 60), (3221225472, 1), (1 << 20, 7)];
+        for (key, value) in entries {
+            let key = finite_field.element(key).value() as u128;
+            assert_eq!(tree.insert(key, finite_field.element(value)), Ok(None));
+        }
+        assert_eq!(tree.len(), 4);
+        let root = tree.root();
+        assert_ne!(root, empty_root);
+
+        for (key, value) in entries {
+            let key = key as u128;
+            let value = finite_field.element(value);
+            assert_eq!(tree.get(key), Some(&value));
+            let proof = tree.prove(key).unwrap();
+            assert_eq!(proof.siblings.len(), 32);
+            assert!(SparseMerkleTree::verify_proof(
+                &hasher,
+                &root,
+                key,
+                Some(&value),
+                &proof
+            ));
+            // neither absent nor holding another value
+            assert!(!SparseMerkleTree::verify_proof(
+                &hasher, &root, key, None, &proof
+            ));
+            let other = &value + &finite_field.one();
+            assert!(!SparseMerkleTree::verify_proof(
+                &hasher,
+                &root,
+                key,
+                Some(&other),
+                &proof
+            ));
+        }
+
+        // non-membership, and the proof doesn't move to another key
+        let proof = tree.prove(7).unwrap();
+        assert!(SparseMerkleTree::verify_proof(
+            &hasher, &root, 7, None, &proof
+        ));
+        assert!(!SparseMerkleTree::verify_proof(
+            &hasher,
+            &root,
+            7,
+            Some(&finite_field.zero()),
+            &proof
+        ));
+        let moved = SparseMerkleProof { key: 8, ..proof };
+        assert!(!SparseMerkleTree::verify_proof(
+            &hasher, &root, 8, None, &moved
+        ));
+    }
+
+    #[test]
+    fn test_full_width_keys() {
+        let finite_field = Arc::new(FiniteField::new(3221225473, 5));
+        let hasher = Blake3Hasher;
+        let mut tree = SparseMerkleTree::new(Blake3Hasher, 128, finite_field.zero()).unwrap();
+        let empty_root = tree.root();
+
+        for key in [1, u128::MAX, 1 << 127] {
+            tree.insert(key, finite_field.one()).unwrap();
+        }
+        let root = tree.root();
+        assert_ne!(root, empty_root);
+        for key in [1, u128::MAX, 1 << 127] {
+            let proof = tree.prove(key).unwrap();
+            assert_eq!(proof.siblings.len(), 128);
+            assert!(SparseMerkleTree::verify_proof(
+                &hasher,
+                &root,
+                key,
+                Some(&finite_field.one()),
+                &proof
+            ));
+        }
+        let proof = tree.prove(2).unwrap();
+        assert!(SparseMerkleTree::verify_proof(
+            &hasher, &root, 2, None, &proof
+        ));
+
+        for key in [1, u128::MAX, 1 << 127] {
+            tree.remove(key).unwrap();
+        }
+        assert_eq!(tree.root(), empty_root);
+        assert!(tree.nodes.is_empty());
+    }
+
+    #[test]
+    fn test_update_and_remove() {
+        let finite_field = Arc::new(FiniteField::new(3221225473, 5));
+        let mut tree = SparseMerkleTree::new(Blake3Hasher, 4, finite_field.zero()).unwrap();
+        let empty_root = tree.root();
+
+        // the root of a dense tree with the empty leafs as zero padding
+        let mut leafs = vec![vec![finite_field.zero()]; 16];
+        for (key, value) in [(2, 20), (9, 90), (15, 150)] {
+            tree.insert(key, finite_field.element(value)).unwrap();
+            leafs[key as usize] = hash_leaf(&Blake3Hasher, finite_field.element(value), None);
+        }
+        while leafs.len() > 1 {
+            leafs = leafs
+                .chunks(2)
+                .map(|pair| hash_pair(&Blake3Hasher, &pair[0], &pair[1]))
+                .collect();
+        }
+        assert_eq!(tree.root(), leafs[0]);
+
+        assert_eq!(
+            tree.insert(9, finite_field.element(91)),
+            Ok(Some(finite_field.element(90)))
+        );
+        assert_eq!(tree.remove(9), Ok(Some(finite_field.element(91))));
+        assert_eq!(tree.remove(9), Ok(None));
+        tree.remove(2).unwrap();
+        tree.remove(15).unwrap();
+        assert!(tree.is_empty());
+        assert_eq!(tree.root(), empty_root);
+        assert!(tree.nodes.is_empty());
+
+        assert_eq!(
+            tree.insert(16, finite_field.one()),
+            Err(CommitmentError::KeyOutOfRange { key: 16, depth: 4 })
+        );
+        assert!(matches!(
+            SparseMerkleTree::new(Blake3Hasher, 129, finite_field.zero()),
+            Err(CommitmentError::InvalidDepth(129))
+        ));
+    }
+}
@@ -0,0 +1,105 @@
+//! `proptest` strategies and proof mutations for the commitment types, enabled with the `test-utils` feature.
+use crate::error::CommitmentError;
+use crate::merkle_tree::MerkleProof;
+use algebra::finite_field::{FieldElement, FiniteField};
+use algebra::test_utils::field_element;
+use proptest::prelude::*;
+use std::sync::Arc;
+
+/// leaf set of at least 2 and at most 2^max_log_len leafs, padded by `MerkleTree::new` when its
+/// length is not a power of two
+pub fn merkle_leafs(
+    finite_field: Arc<FiniteField>,
+    max_log_len: u32,
+) -> impl Strategy<Value = Vec<FieldElement>> {
+    prop::collection::vec(field_element(finite_field), 2..=1usize << max_log_len)
+}
+
+/// leaf set together with the index of one of its leafs
+pub fn merkle_leafs_with_index(
+    finite_field: Arc<FiniteField>,
+    max_log_len: u32,
+) -> impl Strategy<Value = (Vec<FieldElement>, usize)> {
+    merkle_leafs(finite_field, max_log_len).prop_flat_map(|leafs| {
+        let len = leafs.len();
+        (Just(leafs), 0..len)
+    })
+}
+
+/// A corrupted copy of a valid proof and what the verifier must answer for it.
+#[derive(Debug, Clone)]
+pub struct ProofMutation {
+    pub name: String,
+    pub proof: MerkleProof,
+    pub expected: Result<bool, CommitmentError>,
+}
+
+/// Every single-step corruption of a valid `MerkleTree::open` output for a tree of `len` leafs: the
+/// leaf and each sibling bumped by one, the path truncated or extended, adjacent siblings swapped and
+/// the index moved to the neighbouring leaf or out of the tree. The expected answers are the ones of
+/// `MerkleTree::verify`.
+pub fn merkle_proof_mutations(proof: &MerkleProof, len: usize) -> Vec<ProofMutation> {
+    let depth = proof.siblings.len();
+    let mut mutations = Vec::new();
+    let mut add = |name: String,
+                   expected: Result<bool, CommitmentError>,
+                   mutate: &dyn Fn(&mut MerkleProof)| {
+        let mut mutated = proof.clone();
+        mutate(&mut mutated);
+        mutations.push(ProofMutation {
+            name,
+            proof: mutated,
+            expected,
+        });
+    };
+
+    add("bump leaf".to_string(), Ok(false), &|p| {
+        p.leaf[0] = &p.leaf[0] + &p.leaf[0].finite_field().one()
+    });
+    for index in 0..depth {
+        add(format!("bump sibling {index}"), Ok(false), &|p| {
+            let sibling = &mut p.siblings[index][0];
+            *sibling = &*sibling + &sibling.finite_field().one()
+        });
+    }
+    if depth > 0 {
+        let expected = Err(CommitmentError::PathLengthMismatch {
+            expected: depth,
+            found: depth - 1,
+        });
+        add("truncate path".to_string(), expected, &|p| {
+            p.siblings.pop();
+        });
+    }
+    let expected = Err(CommitmentError::PathLengthMismatch {
+        expected: depth,
+        found: depth + 1,
+    });
+    add("extend path".to_string(), expected, &|p| {
+        p.siblings.push(p.leaf.clone())
+    });
+    for index in 0..depth.saturating_sub(1) {
+        if proof.siblings[index] != proof.siblings[index + 1] {
+            add(format!("swap siblings {index}"), Ok(false), &|p| {
+                p.siblings.swap(index, index + 1)
+            });
+        }
+    }
+    let out_of_range = |index| Err(CommitmentError::IndexOutOfRange { index, len });
+    let neighbour = proof.index ^ 1;
+    let expected = if neighbour < len {
+        Ok(false)
+    } else {
+        out_of_range(neighbour)
+    };
+    add("neighbouring index".to_string(), expected, &|p| {
+        p.index ^= 1
+    });
+    let outside = proof.index + (1 << depth);
+    add(
+        "index out of the tree".to_string(),
+        out_of_range(outside),
+        &|p| p.index = outside,
+    );
+    mutations
+}
@@ -0,0 +1,347 @@
+//! Fiat-Shamir transcript with labelled absorption.
+//!
+//! Every absorbed message and every challenge is bound to a label, and the transcript starts from a
+//! protocol label, so the same bytes absorbed in another phase or another protocol give other challenges.
+//! A [`LabelSchedule`] fixes the order of the labels of a protocol; a transcript created with one rejects
+//! any step that doesn't match the next expected label.
+//!
+//! A transcript given a [`RoundLogger`] logs every absorbed message, squeezed state and drawn challenge,
+//! so the logs of a prover and a verifier show where their transcripts diverge.
+use crate::error::TranscriptError;
+use crate::round_log::{RoundEvent, RoundLogger};
+use algebra::finite_field::{FieldElement, FieldSize, FiniteField};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::sync::Arc;
+
+const ABSORB_DOMAIN: u8 = 0x00;
+const SQUEEZE_DOMAIN: u8 = 0x01;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    Absorb(&'static [u8]),
+    Squeeze(&'static [u8]),
+}
+
+/// canonical sequence of labelled steps of a protocol
+pub type LabelSchedule = &'static [Step];
+
+pub struct Transcript<'a> {
+    state: [u8; 32],
+    schedule: Option<LabelSchedule>,
+    position: usize,
+    logger: Option<&'a mut dyn RoundLogger>,
+}
+
+impl fmt::Debug for Transcript<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Transcript")
+            .field("state", &self.state)
+            .field("schedule", &self.schedule)
+            .field("position", &self.position)
+            .field("logged", &self.logger.is_some())
+            .finish()
+    }
+}
+
+impl<'a> Transcript<'a> {
+    pub fn new(protocol: &[u8]) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update((protocol.len() as u64).to_le_bytes());
+        hasher.update(protocol);
+        Self {
+            state: hasher.finalize().into(),
+            schedule: None,
+            position: 0,
+            logger: None,
+        }
+    }
+
+    pub fn with_schedule(protocol: &[u8], schedule: LabelSchedule) -> Self {
+        Self {
+            schedule: Some(schedule),
+            ..Self::new(protocol)
+        }
+    }
+
+    /// logs every following step to `logger`
+    pub fn with_logger(mut self, logger: &'a mut dyn RoundLogger) -> Self {
+        self.logger = Some(logger);
+        self
+    }
+
+    pub fn absorb(&mut self, label: &'static [u8], data: &[u8]) -> Result<(), TranscriptError> {
+        self.check_step(Step::Absorb(label))?;
+        self.state = self.hash(ABSORB_DOMAIN, label, data);
+        self.log(|| RoundEvent::Absorb {
+            label: label_string(label),
+            data: data.to_vec(),
+        })
+    }
+
+    /// absorbs a multi-element digest, every element as 16 little-endian bytes
+    pub fn absorb_elements(
+        &mut self,
+        label: &'static [u8],
+        elements: &[FieldElement],
+    ) -> Result<(), TranscriptError> {
+        let data: Vec<u8> = elements
+            .iter()
+            .flat_map(|element| element.value().to_le_bytes())
+            .collect();
+        self.absorb(label, &data)
+    }
+
+    pub fn challenge_bytes(&mut self, label: &'static [u8]) -> Result<[u8; 32], TranscriptError> {
+        self.check_step(Step::Squeeze(label))?;
+        let state = self.hash(SQUEEZE_DOMAIN, label, &[]);
+        self.state = state;
+        self.log(|| RoundEvent::Squeeze {
+            label: label_string(label),
+            data: state.to_vec(),
+        })?;
+        Ok(state)
+    }
+
+    /// challenge reduced modulo the field prime from the first 16 bytes
+    pub fn challenge_element(
+        &mut self,
+        label: &'static [u8],
+        finite_field: &Arc<FiniteField>,
+    ) -> Result<FieldElement, TranscriptError> {
+        let bytes = self.challenge_bytes(label)?;
+        let value = reduce(&bytes, finite_field);
+        self.log_challenges(label, std::slice::from_ref(&value))?;
+        Ok(value)
+    }
+
+    /// `count` challenges drawn in a single labelled step, element `i` comes from SHA-256(challenge | i)
+    pub fn challenge_elements(
+        &mut self,
+        label: &'static [u8],
+        finite_field: &Arc<FiniteField>,
+        count: usize,
+    ) -> Result<Vec<FieldElement>, TranscriptError> {
+        let bytes = self.challenge_bytes(label)?;
+        let values: Vec<_> = (0..count as u64)
+            .map(|i| {
+                let mut hasher = Sha256::new();
+                hasher.update(bytes);
+                hasher.update(i.to_le_bytes());
+                reduce(&hasher.finalize(), finite_field)
+            })
+            .collect();
+        self.log_challenges(label, &values)?;
+        Ok(values)
+    }
+
+    /// fails if a schedule was given and some of its steps were not performed
+    pub fn finish(&self) -> Result<(), TranscriptError> {
+        match self.schedule {
+            Some(schedule) if self.position < schedule.len() => Err(
+                TranscriptError::IncompleteSchedule(label_name(schedule[self.position])),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    fn check_step(&mut self, step: Step) -> Result<(), TranscriptError> {
+        let Some(schedule) = self.schedule else {
+            return Ok(());
+        };
+        match schedule.get(self.position) {
+            Some(expected) if *expected == step => {
+                self.position += 1;
+                Ok(())
+            }
+            Some(expected) => Err(TranscriptError::UnexpectedLabel {
+                expected: label_name(*expected),
+                found: label_name(step),
+            }),
+            None => Err(TranscriptError::ScheduleExhausted(label_name(step))),
+        }
+    }
+
+    /// builds the event only when a logger was given
+    fn log(&mut self, event: impl FnOnce() -> RoundEvent) -> Result<(), TranscriptError> {
+        match self.logger.as_mut() {
+            Some(logger) => logger
+                .log(&event())
+                .map_err(|error| TranscriptError::Log(error.to_string())),
+            None => Ok(()),
+        }
+    }
+
+    fn log_challenges(
+        &mut self,
+        label: &'static [u8],
+        values: &[FieldElement],
+    ) -> Result<(), TranscriptError> {
+        for value in values {
+            self.log(|| RoundEvent::Challenge {
+                label: label_string(label),
+                value: value.clone(),
+            })?;
+        }
+        Ok(())
+    }
+
+    fn hash(&self, domain: u8, label: &[u8], data: &[u8]) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(self.state);
+        hasher.update([domain]);
+        hasher.update((label.len() as u64).to_le_bytes());
+        hasher.update(label);
+        hasher.update((data.len() as u64).to_le_bytes());
+        hasher.update(data);
+        hasher.finalize().into()
+    }
+}
+
+/// first 16 bytes as a little-endian integer reduced modulo the prime
+fn reduce(bytes: &[u8], finite_field: &Arc<FiniteField>) -> FieldElement {
+    let value = u128::from_le_bytes(bytes[..16].try_into().expect("16 bytes"));
+    finite_field.element((value % finite_field.prime as u128) as FieldSize)
+}
+
+fn label_string(label: &[u8]) -> String {
+    String::from_utf8_lossy(label).into_owned()
+}
+
+fn label_name(step: Step) -> String {
+    match step {
+        Step::Absorb(label) => format!("absorb {}", String::from_utf8_lossy(label)),
+        Step::Squeeze(label) => format!("squeeze {}", String::from_utf8_lossy(label)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::TranscriptError;
+    use crate::round_log::{first_divergence, JsonLinesLogger, RoundEvent, RoundLogger};
+    use crate::transcript::{LabelSchedule, Step, Transcript};
+    use algebra::finite_field::FiniteField;
+    use std::sync::Arc;
+
+    const SCHEDULE: LabelSchedule = &[
+        Step::Absorb(b"trace_root"),
+        Step::Squeeze(b"composition_coefficients"),
+        Step::Absorb(b"composition_root"),
+    ];
+
+    #[test]
+    fn test_labels_separate_challenges() {
+        let mut first = Transcript::new(b"stark");
+        first.absorb(b"trace_root", b"root").unwrap();
+        let mut second = Transcript::new(b"stark");
+        second.absorb(b"composition_root", b"root").unwrap();
+        let mut other_protocol = Transcript::new(b"fri");
+        other_protocol.absorb(b"trace_root", b"root").unwrap();
+
+        let challenge = first.challenge_bytes(b"alpha").unwrap();
+        assert_ne!(challenge, second.challenge_bytes(b"alpha").unwrap());
+        assert_ne!(challenge, other_protocol.challenge_bytes(b"alpha").unwrap());
+
+        let mut replay = Transcript::new(b"stark");
+        replay.absorb(b"trace_root", b"root").unwrap();
+        assert_eq!(challenge, replay.challenge_bytes(b"alpha").unwrap());
+    }
+
+    #[test]
+    fn test_schedule() {
+        let mut transcript = Transcript::with_schedule(b"stark", SCHEDULE);
+        transcript.absorb(b"trace_root", b"root").unwrap();
+        assert_eq!(
+            transcript.finish(),
+            Err(TranscriptError::IncompleteSchedule(
+                "squeeze composition_coefficients".to_string()
+            ))
+        );
+        assert_eq!(
+            transcript.absorb(b"trace_root", b"root"),
+            Err(TranscriptError::UnexpectedLabel {
+                expected: "squeeze composition_coefficients".to_string(),
+                found: "absorb trace_root".to_string(),
+            })
+        );
+        transcript
+            .challenge_bytes(b"composition_coefficients")
+            .unwrap();
+        transcript.absorb(b"composition_root", b"root").unwrap();
+        assert_eq!(transcript.finish(), Ok(()));
+        assert_eq!(
+            transcript.challenge_bytes(b"ood_point"),
+            Err(TranscriptError::ScheduleExhausted(
+                "squeeze ood_point".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_multi_element_digests() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let digest: Vec<_> = finite_field.elements([4, 8, 15, 16]).collect();
+        let mut transcript = Transcript::new(b"stark");
+        transcript.absorb_elements(b"trace_root", &digest).unwrap();
+        let challenges = transcript
+            .challenge_elements(b"alpha", &finite_field, 3)
+            .unwrap();
+        assert_eq!(challenges.len(), 3);
+
+        let mut other = Transcript::new(b"stark");
+        other.absorb_elements(b"trace_root", &digest[..3]).unwrap();
+        assert_ne!(
+            other
+                .challenge_elements(b"alpha", &finite_field, 3)
+                .unwrap(),
+            challenges
+        );
+    }
+
+    /// the prover or verifier side of a short protocol, logged to a string
+    fn logged_run(root: &[u8]) -> String {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let mut logger = JsonLinesLogger::new(Vec::new());
+        let mut transcript = Transcript::new(b"stark").with_logger(&mut logger);
+        transcript.absorb(b"trace_root", root).unwrap();
+        transcript
+            .challenge_elements(b"alpha", &finite_field, 2)
+            .unwrap();
+        String::from_utf8(logger.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn test_round_log() {
+        let log = logged_run(b"root");
+        let lines: Vec<&str> = log.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[0],
+            r#"{"step":0,"event":"absorb","label":"trace_root","data":"726f6f74"}"#
+        );
+        assert!(lines[1].starts_with(r#"{"step":1,"event":"squeeze","label":"alpha","data":""#));
+        assert!(lines[2].starts_with(r#"{"step":2,"event":"challenge","label":"alpha""#));
+        assert!(lines[3].starts_with(r#"{"step":3,"event":"challenge","label":"alpha""#));
+
+        // the same steps log the same lines, another root diverges where it was absorbed
+        assert_eq!(first_divergence(&log, &logged_run(b"root")), None);
+        assert_eq!(first_divergence(&log, &logged_run(b"toor")).unwrap().0, 0);
+    }
+
+    #[test]
+    fn test_round_log_errors() {
+        struct FailingLogger;
+        impl RoundLogger for FailingLogger {
+            fn log(&mut self, _event: &RoundEvent) -> std::io::Result<()> {
+                Err(std::io::Error::other("disk full"))
+            }
+        }
+
+        let mut logger = FailingLogger;
+        let mut transcript = Transcript::new(b"stark").with_logger(&mut logger);
+        assert_eq!(
+            transcript.absorb(b"trace_root", b"root"),
+            Err(TranscriptError::Log("disk full".to_string()))
+        );
+    }
+}
@@ -0,0 +1,715 @@
+//! Canonical binary encoding of commitments and transcript seeds.
+//!
+//! Every message starts with the format version and a tag identifying its kind:
+//!
+//! ```text
+//! commitment      : version | 0x01 | varint(p) | varint(d) | d * element
+//! merkle proof    : version | 0x02 | varint(p) | varint(index) | varint(d) | leaf | varint(n)
+//!                   | n * sibling | 0x00 or 0x01 salt
+//! transcript seed : version | 0x03 | varint(n) | n * byte
+//! proof options   : version | 0x04 | varint(blowup) | varint(queries) | grinding bits | varint(folding)
+//!                   | varint(remainder degree) | hash id | deep batching id | varint(p) | varint(generator)
+//! ```
+//!
+//! Merkle nodes are digests of `d` elements, `d` being at least one, so the leaf and every sibling of
+//! a proof take `d` elements.
+//!
+//! Varints are unsigned LEB128 and must be minimally encoded. Elements are written little-endian on
+//! exactly `ceil(log2(p) / 8)` bytes and must be reduced modulo `p`, so every value has exactly one encoding.
+use crate::error::{ProofOptionsError, WireError};
+use crate::merkle_tree::MerkleProof;
+use crate::proof_options::{DeepBatching, HashKind, ProofOptions};
+use algebra::finite_field::{FieldElement, FieldSize, FiniteField};
+use std::sync::Arc;
+
+pub const WIRE_VERSION: u8 = 1;
+
+pub const COMMITMENT_TAG: u8 = 0x01;
+pub const MERKLE_PROOF_TAG: u8 = 0x02;
+pub const TRANSCRIPT_SEED_TAG: u8 = 0x03;
+pub const PROOF_OPTIONS_TAG: u8 = 0x04;
+
+/// number of bytes used to encode an element of the field
+pub fn element_width(finite_field: &FiniteField) -> usize {
+    finite_field.byte_len()
+}
+
+/// number of bits needed for the largest element, p - 1
+pub fn element_bit_width(finite_field: &FiniteField) -> usize {
+    (FieldSize::BITS - (finite_field.prime - 1).leading_zeros()) as usize
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Packing {
+    /// every element takes `element_width` bytes
+    Bytes,
+    /// elements are concatenated on `element_bit_width` bits, least significant bit first,
+    /// the last byte is padded with zeros
+    Bits,
+}
+
+/// packs the elements without any header, the caller is responsible for storing the length and field
+pub fn encode_slice(elements: &[FieldElement], packing: Packing) -> Vec<u8> {
+    let Some(first) = elements.first() else {
+        return Vec::new();
+    };
+    match packing {
+        Packing::Bytes => {
+            let mut buffer =
+                Vec::with_capacity(elements.len() * element_width(first.finite_field()));
+            for element in elements {
+                write_element(&mut buffer, element);
+            }
+            buffer
+        }
+        Packing::Bits => {
+            let bit_width = element_bit_width(first.finite_field());
+            let mut buffer = vec![0u8; (elements.len() * bit_width).div_ceil(8)];
+            for (i, element) in elements.iter().enumerate() {
+                let value = element.value();
+                for bit in 0..bit_width {
+                    if (value >> bit) & 1 == 1 {
+                        let position = i * bit_width + bit;
+                        buffer[position / 8] |= 1 << (position % 8);
+                    }
+                }
+            }
+            buffer
+        }
+    }
+}
+
+pub fn decode_slice(
+    bytes: &[u8],
+    len: usize,
+    packing: Packing,
+    finite_field: &Arc<FiniteField>,
+) -> Result<Vec<FieldElement>, WireError> {
+    match packing {
+        Packing::Bytes => {
+            let width = element_width(finite_field);
+            let expected = len.checked_mul(width).ok_or(WireError::Truncated)?;
+            check_slice_length(bytes, expected)?;
+            let mut reader = Reader::new(bytes);
+            (0..len)
+                .map(|_| reader.read_element(finite_field))
+                .collect()
+        }
+        Packing::Bits => {
+            let bit_width = element_bit_width(finite_field);
+            let total_bits = len.checked_mul(bit_width).ok_or(WireError::Truncated)?;
+            check_slice_length(bytes, total_bits.div_ceil(8))?;
+            let bit = |position: usize| (bytes[position / 8] >> (position % 8)) & 1;
+
+            let elements = (0..len)
+                .map(|i| {
+                    let value = (0..bit_width).fold(0 as FieldSize, |acc, j| {
+                        acc | (bit(i * bit_width + j) as FieldSize) << j
+                    });
+                    if value >= finite_field.prime {
+                        return Err(WireError::NonCanonicalElement);
+                    }
+                    Ok(finite_field.element(value))
+                })
+                .collect::<Result<Vec<FieldElement>, _>>()?;
+            if (total_bits..bytes.len() * 8).any(|position| bit(position) != 0) {
+                return Err(WireError::NonZeroPadding);
+            }
+            Ok(elements)
+        }
+    }
+}
+
+fn check_slice_length(bytes: &[u8], expected: usize) -> Result<(), WireError> {
+    if bytes.len() < expected {
+        return Err(WireError::Truncated);
+    }
+    if bytes.len() > expected {
+        return Err(WireError::TrailingBytes(bytes.len() - expected));
+    }
+    Ok(())
+}
+
+pub fn write_varint(buffer: &mut Vec<u8>, mut value: u128) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            buffer.push(byte);
+            return;
+        }
+        buffer.push(byte | 0x80);
+    }
+}
+
+pub fn write_element(buffer: &mut Vec<u8>, element: &FieldElement) {
+    buffer.extend_from_slice(&element.to_bytes_le());
+}
+
+fn write_header(buffer: &mut Vec<u8>, tag: u8) {
+    buffer.push(WIRE_VERSION);
+    buffer.push(tag);
+}
+
+/// writes the elements of a digest, its length is written once per message
+fn write_digest(buffer: &mut Vec<u8>, digest: &[FieldElement]) {
+    for element in digest {
+        write_element(buffer, element);
+    }
+}
+
+pub fn encode_commitment(root: &[FieldElement]) -> Vec<u8> {
+    assert!(!root.is_empty(), "empty digest");
+    let mut buffer = Vec::new();
+    write_header(&mut buffer, COMMITMENT_TAG);
+    write_varint(&mut buffer, root[0].finite_field().prime as u128);
+    write_varint(&mut buffer, root.len() as u128);
+    write_digest(&mut buffer, root);
+    buffer
+}
+
+pub fn encode_merkle_proof(proof: &MerkleProof) -> Vec<u8> {
+    let digest_len = proof.leaf.len();
+    assert!(digest_len > 0, "empty digest");
+    assert!(
+        proof
+            .siblings
+            .iter()
+            .all(|sibling| sibling.len() == digest_len),
+        "siblings and leaf have different digest lengths"
+    );
+    let mut buffer = Vec::new();
+    write_header(&mut buffer, MERKLE_PROOF_TAG);
+    write_varint(&mut buffer, proof.leaf[0].finite_field().prime as u128);
+    write_varint(&mut buffer, proof.index as u128);
+    write_varint(&mut buffer, digest_len as u128);
+    write_digest(&mut buffer, &proof.leaf);
+    write_varint(&mut buffer, proof.siblings.len() as u128);
+    for sibling in &proof.siblings {
+        write_digest(&mut buffer, sibling);
+    }
+    match &proof.salt {
+        None => buffer.push(0),
+        Some(salt) => {
+            buffer.push(1);
+            write_element(&mut buffer, salt);
+        }
+    }
+    buffer
+}
+
+pub fn encode_transcript_seed(seed: &[u8]) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    write_header(&mut buffer, TRANSCRIPT_SEED_TAG);
+    write_varint(&mut buffer, seed.len() as u128);
+    buffer.extend_from_slice(seed);
+    buffer
+}
+
+pub fn encode_proof_options(options: &ProofOptions) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    write_header(&mut buffer, PROOF_OPTIONS_TAG);
+    write_varint(&mut buffer, options.blowup as u128);
+    write_varint(&mut buffer, options.num_queries as u128);
+    buffer.push(options.grinding_bits);
+    write_varint(&mut buffer, options.folding_factor as u128);
+    write_varint(&mut buffer, options.fri_remainder_max_degree as u128);
+    buffer.push(options.hash_kind.id());
+    buffer.push(options.deep_batching.id());
+    write_varint(&mut buffer, options.field.prime as u128);
+    write_varint(&mut buffer, options.field.generator as u128);
+    buffer
+}
+
+pub fn decode_commitment(
+    bytes: &[u8],
+    finite_field: &Arc<FiniteField>,
+) -> Result<Vec<FieldElement>, WireError> {
+    let mut reader = Reader::new(bytes);
+    reader.read_header(COMMITMENT_TAG)?;
+    reader.read_modulus(finite_field)?;
+    let digest_len = reader.read_digest_len()?;
+    let root = reader.read_digest(digest_len, finite_field)?;
+    reader.finish()?;
+    Ok(root)
+}
+
+pub fn decode_merkle_proof(
+    bytes: &[u8],
+    finite_field: &Arc<FiniteField>,
+) -> Result<MerkleProof, WireError> {
+    let mut reader = Reader::new(bytes);
+    reader.read_header(MERKLE_PROOF_TAG)?;
+    reader.read_modulus(finite_field)?;
+    let index = reader.read_usize()?;
+    let digest_len = reader.read_digest_len()?;
+    let leaf = reader.read_digest(digest_len, finite_field)?;
+    let len = reader.read_length()?;
+    let siblings = (0..len)
+        .map(|_| reader.read_digest(digest_len, finite_field))
+        .collect::<Result<Vec<Vec<FieldElement>>, _>>()?;
+    let salt = match reader.read_u8()? {
+        0 => None,
+        1 => Some(reader.read_element(finite_field)?),
+        flag => return Err(WireError::InvalidFlag(flag)),
+    };
+    reader.finish()?;
+    Ok(MerkleProof {
+        index,
+        leaf,
+        siblings,
+        salt,
+    })
+}
+
+pub fn decode_transcript_seed(bytes: &[u8]) -> Result<Vec<u8>, WireError> {
+    let mut reader = Reader::new(bytes);
+    reader.read_header(TRANSCRIPT_SEED_TAG)?;
+    let len = reader.read_length()?;
+    let seed = reader.read_bytes(len)?.to_vec();
+    reader.finish()?;
+    Ok(seed)
+}
+
+pub fn decode_proof_options(bytes: &[u8]) -> Result<ProofOptions, WireError> {
+    let mut reader = Reader::new(bytes);
+    reader.read_header(PROOF_OPTIONS_TAG)?;
+    let blowup = reader.read_usize()?;
+    let num_queries = reader.read_usize()?;
+    let grinding_bits = reader.read_u8()?;
+    let folding_factor = reader.read_usize()?;
+    let fri_remainder_max_degree = reader.read_usize()?;
+    let hash_id = reader.read_u8()?;
+    let hash_kind =
+        HashKind::from_id(hash_id).ok_or(ProofOptionsError::UnknownHashKind(hash_id))?;
+    let deep_batching_id = reader.read_u8()?;
+    let deep_batching = DeepBatching::from_id(deep_batching_id)
+        .ok_or(ProofOptionsError::UnknownDeepBatching(deep_batching_id))?;
+    let prime = reader.read_field_size()?;
+    let generator = reader.read_field_size()?;
+    reader.finish()?;
+
+    let field = FiniteField::try_new(prime, generator)
+        .map_err(|_| ProofOptionsError::InvalidField(prime))?;
+    Ok(ProofOptions::new(
+        blowup,
+        num_queries,
+        grinding_bits,
+        folding_factor,
+        fri_remainder_max_degree,
+        hash_kind,
+        field,
+    )?
+    .with_deep_batching(deep_batching))
+}
+
+/// cursor over an encoded message, every read checks canonicality
+pub struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, position: 0 }
+    }
+
+    pub fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], WireError> {
+        let end = self
+            .position
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or(WireError::Truncated)?;
+        let bytes = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, WireError> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    pub fn read_varint(&mut self) -> Result<u128, WireError> {
+        let mut value = 0u128;
+        let mut shift = 0;
+        loop {
+            let byte = self.read_u8()?;
+            let payload = (byte & 0x7f) as u128;
+            if shift >= u128::BITS || (payload << shift) >> shift != payload {
+                return Err(WireError::NonCanonicalVarint);
+            }
+            value |= payload << shift;
+            if byte & 0x80 == 0 {
+                // a zero final byte means a shorter encoding existed
+                if byte == 0 && shift > 0 {
+                    return Err(WireError::NonCanonicalVarint);
+                }
+                return Ok(value);
+            }
+            shift += 7;
+        }
+    }
+
+    pub fn read_usize(&mut self) -> Result<usize, WireError> {
+        usize::try_from(self.read_varint()?).map_err(|_| WireError::NonCanonicalVarint)
+    }
+
+    pub fn read_field_size(&mut self) -> Result<FieldSize, WireError> {
+        FieldSize::try_from(self.read_varint()?).map_err(|_| WireError::NonCanonicalVarint)
+    }
+
+    pub fn read_length(&mut self) -> Result<usize, WireError> {
+        let len = self.read_varint()?;
+        // every encoded item takes at least one byte
+        if len > (self.bytes.len() - self.position) as u128 {
+            return Err(WireError::Truncated);
+        }
+        Ok(len as usize)
+    }
+
+    pub fn read_header(&mut self, tag: u8) -> Result<(), WireError> {
+        let version = self.read_u8()?;
+        if version != WIRE_VERSION {
+            return Err(WireError::UnsupportedVersion(version));
+        }
+        let found = self.read_u8()?;
+        if found != tag {
+            return Err(WireError::UnexpectedTag {
+                expected: tag,
+                found,
+            });
+        }
+        Ok(())
+    }
+
+    pub fn read_modulus(&mut self, finite_field: &FiniteField) -> Result<(), WireError> {
+        let prime = self.read_varint()?;
+        if prime != finite_field.prime as u128 {
+            return Err(WireError::ModulusMismatch(prime));
+        }
+        Ok(())
+    }
+
+    pub fn read_element(
+        &mut self,
+        finite_field: &Arc<FiniteField>,
+    ) -> Result<FieldElement, WireError> {
+        let bytes = self.read_bytes(element_width(finite_field))?;
+        FieldElement::from_bytes(bytes, finite_field).map_err(|_| WireError::NonCanonicalElement)
+    }
+
+    /// the length of the digests of a message, at least one element
+    pub fn read_digest_len(&mut self) -> Result<usize, WireError> {
+        match self.read_length()? {
+            0 => Err(WireError::EmptyDigest),
+            len => Ok(len),
+        }
+    }
+
+    pub fn read_digest(
+        &mut self,
+        len: usize,
+        finite_field: &Arc<FiniteField>,
+    ) -> Result<Vec<FieldElement>, WireError> {
+        (0..len).map(|_| self.read_element(finite_field)).collect()
+    }
+
+    pub fn finish(self) -> Result<(), WireError> {
+        match self.bytes.len() - self.position {
+            0 => Ok(()),
+            trailing => Err(WireError::TrailingBytes(trailing)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::{ProofOptionsError, WireError};
+    use crate::hash::{Blake3Hasher, WideDigest};
+    use crate::merkle_tree::{MerkleProof, MerkleTree};
+    use crate::proof_options::{DeepBatching, ProofOptions};
+    use crate::wire::{
+        decode_commitment, decode_merkle_proof, decode_proof_options, decode_slice,
+        decode_transcript_seed, encode_commitment, encode_merkle_proof, encode_proof_options,
+        encode_slice, encode_transcript_seed, Packing, Reader,
+    };
+    use algebra::finite_field::FiniteField;
+    use std::sync::Arc;
+
+    const COMMITMENT_F97: &[u8] = include_bytes!("../testdata/wire/commitment_f97_v1.bin");
+    const MERKLE_PROOF_F97: &[u8] = include_bytes!("../testdata/wire/merkle_proof_f97_v1.bin");
+    const MERKLE_PROOF_F3221225473: &[u8] =
+        include_bytes!("../testdata/wire/merkle_proof_f3221225473_v1.bin");
+    const TRANSCRIPT_SEED: &[u8] = include_bytes!("../testdata/wire/transcript_seed_v1.bin");
+    const PROOF_OPTIONS_96_BIT: &[u8] =
+        include_bytes!("../testdata/wire/proof_options_96_bit_v1.bin");
+
+    #[test]
+    fn test_golden_commitment() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let root = vec![finite_field.element(42)];
+        assert_eq!(encode_commitment(&root), COMMITMENT_F97);
+        assert_eq!(decode_commitment(COMMITMENT_F97, &finite_field), Ok(root));
+
+        // a wide root, and no digest at all
+        let root: Vec<_> = finite_field.elements([1, 2, 96]).collect();
+        let bytes = encode_commitment(&root);
+        assert_eq!(bytes, [1, 1, 97, 3, 1, 2, 96]);
+        assert_eq!(decode_commitment(&bytes, &finite_field), Ok(root));
+        assert_eq!(
+            decode_commitment(&[1, 1, 97, 0], &finite_field),
+            Err(WireError::EmptyDigest)
+        );
+    }
+
+    #[test]
+    fn test_golden_merkle_proof() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let proof = MerkleProof {
+            index: 5,
+            leaf: vec![finite_field.element(42)],
+            siblings: finite_field
+                .elements([5, 17, 96])
+                .map(|x| vec![x])
+                .collect(),
+            salt: None,
+        };
+        assert_eq!(encode_merkle_proof(&proof), MERKLE_PROOF_F97);
+        assert_eq!(
+            decode_merkle_proof(MERKLE_PROOF_F97, &finite_field),
+            Ok(proof)
+        );
+
+        let finite_field = Arc::new(FiniteField::new(3221225473, 5));
+        let proof = MerkleProof {
+            index: 300,
+            leaf: vec![finite_field.element(7)],
+            siblings: finite_field
+                .elements([123456789, 3221225472])
+                .map(|x| vec![x])
+                .collect(),
+            salt: Some(finite_field.element(99)),
+        };
+        assert_eq!(encode_merkle_proof(&proof), MERKLE_PROOF_F3221225473);
+        assert_eq!(
+            decode_merkle_proof(MERKLE_PROOF_F3221225473, &finite_field),
+            Ok(proof)
+        );
+    }
+
+    #[test]
+    fn test_merkle_proof_round_trip() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let leafs: Vec<_> = finite_field.elements(0..5).collect();
+        let mut tree = MerkleTree::new(Blake3Hasher, leafs.clone()).unwrap();
+        let root = tree.commit();
+        let proof = tree.open(3).unwrap();
+        let decoded = decode_merkle_proof(&encode_merkle_proof(&proof), &finite_field).unwrap();
+        assert_eq!(decoded, proof);
+        // the index travels with the path, so the decoded proof is checked with the root alone
+        assert!(MerkleTree::verify_proof(
+            &Blake3Hasher,
+            &root,
+            5,
+            decoded.index,
+            &leafs[3],
+            &decoded
+        ));
+
+        // a proof without siblings still carries the modulus of its leaf
+        let proof = MerkleProof {
+            index: 0,
+            leaf: vec![finite_field.element(1)],
+            siblings: vec![],
+            salt: None,
+        };
+        let bytes = encode_merkle_proof(&proof);
+        assert_eq!(bytes, [1, 2, 97, 0, 1, 1, 0, 0]);
+        let other_field = Arc::new(FiniteField::new(13, 2));
+        assert_eq!(
+            decode_merkle_proof(&bytes, &other_field),
+            Err(WireError::ModulusMismatch(97))
+        );
+        let mut bytes = bytes;
+        bytes[2] = 13;
+        assert_eq!(
+            decode_merkle_proof(&bytes, &finite_field),
+            Err(WireError::ModulusMismatch(13))
+        );
+
+        let mut bytes = MERKLE_PROOF_F97.to_vec();
+        let flag = bytes.len() - 1;
+        bytes[flag] = 2;
+        assert_eq!(
+            decode_merkle_proof(&bytes, &finite_field),
+            Err(WireError::InvalidFlag(2))
+        );
+        let mut bytes = MERKLE_PROOF_F97.to_vec();
+        bytes[4] = 0;
+        assert_eq!(
+            decode_merkle_proof(&bytes, &finite_field),
+            Err(WireError::EmptyDigest)
+        );
+    }
+
+    #[test]
+    fn test_wide_digest_round_trip() {
+        let finite_field = Arc::new(FiniteField::new(3221225473, 5));
+        let hasher = WideDigest::new(Blake3Hasher, 4);
+        let leafs: Vec<_> = finite_field.elements(0..6).collect();
+        let mut tree = MerkleTree::new(hasher.clone(), leafs.clone()).unwrap();
+        let root = tree.commit();
+
+        let decoded = decode_commitment(&encode_commitment(&root), &finite_field).unwrap();
+        assert_eq!(decoded, root);
+        let proof = tree.open(4).unwrap();
+        let bytes = encode_merkle_proof(&proof);
+        // header, modulus, index and digest length, then 4 elements for the leaf and each sibling
+        assert_eq!(bytes.len(), 2 + 5 + 1 + 1 + 16 + 1 + 3 * 16 + 1);
+        let decoded = decode_merkle_proof(&bytes, &finite_field).unwrap();
+        assert_eq!(decoded, proof);
+        assert!(MerkleTree::verify_proof(
+            &hasher, &root, 6, 4, &leafs[4], &decoded
+        ));
+    }
+
+    #[test]
+    fn test_golden_transcript_seed() {
+        assert_eq!(encode_transcript_seed(b"stark"), TRANSCRIPT_SEED);
+        assert_eq!(
+            decode_transcript_seed(TRANSCRIPT_SEED),
+            Ok(b"stark".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_golden_proof_options() {
+        let options = ProofOptions::default_96_bit();
+        assert_eq!(encode_proof_options(&options), PROOF_OPTIONS_96_BIT);
+        assert_eq!(decode_proof_options(PROOF_OPTIONS_96_BIT), Ok(options));
+
+        // blowup 6
+        let mut bytes = PROOF_OPTIONS_96_BIT.to_vec();
+        bytes[2] = 6;
+        assert_eq!(
+            decode_proof_options(&bytes),
+            Err(WireError::InvalidOptions(ProofOptionsError::InvalidBlowup(
+                6
+            )))
+        );
+
+        let options = ProofOptions::default_96_bit().with_deep_batching(DeepBatching::Powers);
+        assert_eq!(
+            decode_proof_options(&encode_proof_options(&options)),
+            Ok(options)
+        );
+    }
+
+    #[test]
+    fn test_slice_round_trip() {
+        for (prime, bytes_len, bits_len) in [(97, 10, 9), (3221225473, 40, 40), (257, 20, 12)] {
+            let finite_field = Arc::new(FiniteField::from_prime(prime).unwrap());
+            let elements: Vec<_> = (0..10)
+                .map(|i| finite_field.element(prime - 1 - i * 7))
+                .collect();
+
+            let bytes = encode_slice(&elements, Packing::Bytes);
+            assert_eq!(bytes.len(), bytes_len);
+            assert_eq!(
+                decode_slice(&bytes, 10, Packing::Bytes, &finite_field),
+                Ok(elements.clone())
+            );
+
+            let bits = encode_slice(&elements, Packing::Bits);
+            assert_eq!(bits.len(), bits_len);
+            assert_eq!(
+                decode_slice(&bits, 10, Packing::Bits, &finite_field),
+                Ok(elements)
+            );
+        }
+    }
+
+    #[test]
+    fn test_slice_reject_non_canonical() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let elements = vec![finite_field.element(96), finite_field.element(5)];
+        let bits = encode_slice(&elements, Packing::Bits);
+        assert_eq!(bits, vec![0xe0, 0x02]);
+
+        assert_eq!(
+            decode_slice(&bits, 3, Packing::Bits, &finite_field),
+            Err(WireError::Truncated)
+        );
+        assert_eq!(
+            decode_slice(&[0xe0, 0x02, 0x00], 2, Packing::Bits, &finite_field),
+            Err(WireError::TrailingBytes(1))
+        );
+        assert_eq!(
+            decode_slice(&[0xe0, 0x82], 2, Packing::Bits, &finite_field),
+            Err(WireError::NonZeroPadding)
+        );
+        // 127 on 7 bits
+        assert_eq!(
+            decode_slice(&[0x7f], 1, Packing::Bits, &finite_field),
+            Err(WireError::NonCanonicalElement)
+        );
+        assert_eq!(
+            decode_slice(&[0x61], 1, Packing::Bytes, &finite_field),
+            Err(WireError::NonCanonicalElement)
+        );
+    }
+
+    #[test]
+    fn test_reject_non_canonical() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+
+        let mut bytes = COMMITMENT_F97.to_vec();
+        bytes[0] = 2;
+        assert_eq!(
+            decode_commitment(&bytes, &finite_field),
+            Err(WireError::UnsupportedVersion(2))
+        );
+
+        assert_eq!(
+            decode_commitment(MERKLE_PROOF_F97, &finite_field),
+            Err(WireError::UnexpectedTag {
+                expected: 1,
+                found: 2
+            })
+        );
+
+        // 97 is not reduced
+        let bytes = [1, 1, 0x61, 1, 0x61];
+        assert_eq!(
+            decode_commitment(&bytes, &finite_field),
+            Err(WireError::NonCanonicalElement)
+        );
+
+        let mut bytes = COMMITMENT_F97.to_vec();
+        bytes.push(0);
+        assert_eq!(
+            decode_commitment(&bytes, &finite_field),
+            Err(WireError::TrailingBytes(1))
+        );
+        assert_eq!(
+            decode_commitment(&COMMITMENT_F97[..4], &finite_field),
+            Err(WireError::Truncated)
+        );
+
+        let other_field = Arc::new(FiniteField::new(13, 2));
+        assert_eq!(
+            decode_commitment(COMMITMENT_F97, &other_field),
+            Err(WireError::ModulusMismatch(97))
+        );
+
+        // 5 encoded on two bytes
+        assert_eq!(
+            Reader::new(&[0x85, 0x00]).read_varint(),
+            Err(WireError::NonCanonicalVarint)
+        );
+        assert_eq!(
+            Reader::new(&[0xff; 20]).read_varint(),
+            Err(WireError::NonCanonicalVarint)
+        );
+    }
+}
@@ -0,0 +1,23 @@
+#![no_main]
+
+use algebra::finite_field::{FieldSize, FiniteField};
+use libfuzzer_sys::fuzz_target;
+use stark_fuzz::{pick_prime, reference};
+use std::sync::Arc;
+
+fuzz_target!(|input: (u8, u64)| {
+    let (selector, value) = input;
+    let prime = pick_prime(selector);
+    let finite_field = Arc::new(FiniteField::from_prime(prime).unwrap());
+
+    let value = value as FieldSize % prime;
+    if value == 0 {
+        return;
+    }
+    let element = finite_field.element(value);
+    let inverse = element.inverse();
+
+    assert_eq!(&element * &inverse, finite_field.one());
+    assert_eq!(inverse.inverse(), element);
+    assert_eq!(inverse.value(), reference::inverse(value, prime));
+});
@@ -0,0 +1,81 @@
+#![no_main]
+
+use algebra::finite_field::{FieldSize, FiniteField};
+use crypto_primitives::error::CommitmentError;
+use crypto_primitives::hash::Blake3Hasher;
+use crypto_primitives::merkle_tree::{MerkleProof, MerkleTree};
+use libfuzzer_sys::fuzz_target;
+use std::sync::Arc;
+
+const MAX_LEAFS: usize = 64;
+
+fuzz_target!(|input: (Vec<u64>, u8, u8, u64)| {
+    let (leafs, leaf_index, tamper_index, delta) = input;
+    // a field large enough that tampered nodes don't collide with the honest ones
+    let finite_field = Arc::new(FiniteField::new(3221225473, 5));
+    let hasher = Blake3Hasher;
+
+    let leafs_len = leafs.len().min(MAX_LEAFS);
+    if leafs_len < 2 {
+        return;
+    }
+    let leafs: Vec<_> = leafs
+        .iter()
+        .take(leafs_len)
+        .map(|x| finite_field.element(*x as FieldSize % finite_field.prime))
+        .collect();
+
+    let leaf_index = leaf_index as usize % leafs_len;
+    let leaf = leafs[leaf_index].clone();
+    let mut tree = MerkleTree::new(hasher.clone(), leafs).expect("at least two leafs");
+    let root = tree.commit();
+
+    let proof = tree
+        .open(leaf_index)
+        .expect("a committed leaf must have a proof");
+    assert_eq!(tree.verify(&proof), Ok(true));
+    assert!(MerkleTree::verify_proof(
+        &hasher, &root, leafs_len, leaf_index, &leaf, &proof
+    ));
+
+    // changing a single element of the authentication path must invalidate it
+    let delta = delta as FieldSize % finite_field.prime;
+    if delta == 0 {
+        return;
+    }
+    let mut malleated = proof.clone();
+    let tamper_index = tamper_index as usize % (malleated.siblings.len() + 1);
+    match tamper_index.checked_sub(1) {
+        None => malleated.leaf[0] = &malleated.leaf[0] + &finite_field.element(delta),
+        Some(level) => {
+            let sibling = &mut malleated.siblings[level][0];
+            *sibling = &*sibling + &finite_field.element(delta)
+        }
+    }
+    assert_eq!(tree.verify(&malleated), Ok(false));
+    assert!(!MerkleTree::verify_proof(
+        &hasher, &root, leafs_len, leaf_index, &leaf, &malleated
+    ));
+
+    // so must claiming the path for another position, unless the swapped nodes are equal, and the
+    // padding is out of range
+    let level = tamper_index % proof.siblings.len();
+    let node = MerkleProof {
+        index: proof.index,
+        leaf: proof.leaf.clone(),
+        siblings: proof.siblings[..level].to_vec(),
+        salt: None,
+    }
+    .compute_root(&hasher);
+    let mut moved = proof.clone();
+    moved.index ^= 1 << level;
+    let expected = if moved.index < leafs_len {
+        Ok(node.as_ref() == Some(&proof.siblings[level]))
+    } else {
+        Err(CommitmentError::IndexOutOfRange {
+            index: moved.index,
+            len: leafs_len,
+        })
+    };
+    assert_eq!(tree.verify(&moved), expected);
+});
@@ -0,0 +1,53 @@
+#![no_main]
+
+use algebra::finite_field::{FieldSize, FiniteField};
+use algebra::polynomial::Polynomial;
+use libfuzzer_sys::fuzz_target;
+use stark_fuzz::{pick_prime, reference};
+use std::sync::Arc;
+
+/// Keeps the schoolbook paths fast enough for the fuzzer.
+const MAX_COEFFICIENTS: usize = 32;
+
+fn coefficients(raw: &[u64], prime: FieldSize) -> Option<Vec<FieldSize>> {
+    let coefficients: Vec<FieldSize> = raw
+        .iter()
+        .take(MAX_COEFFICIENTS)
+        .map(|c| *c as FieldSize % prime)
+        .collect();
+    match coefficients.last() {
+        Some(leading) if *leading != 0 => Some(coefficients),
+        _ => None,
+    }
+}
+
+fuzz_target!(|input: (u8, Vec<u64>, Vec<u64>, u64)| {
+    let (selector, a, b, x) = input;
+    let prime = pick_prime(selector);
+    let finite_field = Arc::new(FiniteField::from_prime(prime).unwrap());
+
+    let (Some(a), Some(b)) = (coefficients(&a, prime), coefficients(&b, prime)) else {
+        return;
+    };
+    let poly_a = Polynomial::from_slice(&a, Arc::clone(&finite_field));
+    let poly_b = Polynomial::from_slice(&b, Arc::clone(&finite_field));
+
+    // differential check against the reference implementation
+    let product = &poly_a * &poly_b;
+    let expected = Polynomial::from_slice(
+        &reference::poly_mul(&a, &b, prime),
+        Arc::clone(&finite_field),
+    );
+    assert_eq!(product, expected);
+
+    let x = x as FieldSize % prime;
+    assert_eq!(
+        poly_a.evaluate(finite_field.element(x)).value(),
+        reference::poly_evaluate(&a, x, prime)
+    );
+
+    // (a * b) / b == a
+    let (quotient, remainder) = product / poly_b;
+    assert_eq!(quotient, poly_a);
+    assert!(remainder.coefficients.is_empty());
+});
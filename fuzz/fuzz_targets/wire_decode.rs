@@ -0,0 +1,32 @@
+#![no_main]
+
+use algebra::finite_field::FiniteField;
+use crypto_primitives::wire::{
+    decode_commitment, decode_merkle_proof, decode_proof_options, decode_slice, encode_commitment,
+    encode_merkle_proof, encode_proof_options, encode_slice, Packing,
+};
+use libfuzzer_sys::fuzz_target;
+use stark_fuzz::pick_prime;
+use std::sync::Arc;
+
+fuzz_target!(|input: (u8, u16, &[u8])| {
+    let (selector, len, bytes) = input;
+    let finite_field = Arc::new(FiniteField::from_prime(pick_prime(selector)).unwrap());
+
+    // every value has a single encoding, so whatever decodes encodes back to the same bytes
+    if let Ok(root) = decode_commitment(bytes, &finite_field) {
+        assert_eq!(encode_commitment(&root), bytes);
+    }
+    if let Ok(proof) = decode_merkle_proof(bytes, &finite_field) {
+        assert_eq!(encode_merkle_proof(&proof), bytes);
+    }
+    if let Ok(options) = decode_proof_options(bytes) {
+        assert_eq!(encode_proof_options(&options), bytes);
+    }
+    for packing in [Packing::Bytes, Packing::Bits] {
+        if let Ok(elements) = decode_slice(bytes, len as usize, packing, &finite_field) {
+            assert_eq!(elements.len(), len as usize);
+            assert_eq!(encode_slice(&elements, packing), bytes);
+        }
+    }
+});
@@ -0,0 +1,58 @@
+use algebra::finite_field::FieldSize;
+
+/// Small primes the targets pick from, so both the toy field used in tests and a larger one get covered.
+pub const PRIMES: [FieldSize; 3] = [97, 3221225473, 2305843009213693951];
+
+pub fn pick_prime(selector: u8) -> FieldSize {
+    PRIMES[selector as usize % PRIMES.len()]
+}
+
+/// Slow reference implementation over plain integers, used to cross-check the library.
+pub mod reference {
+    use algebra::finite_field::FieldSize;
+
+    pub fn reduce(x: FieldSize, p: FieldSize) -> FieldSize {
+        x.rem_euclid(p)
+    }
+
+    pub fn mul(a: FieldSize, b: FieldSize, p: FieldSize) -> FieldSize {
+        ((reduce(a, p) as u128 * reduce(b, p) as u128) % p as u128) as FieldSize
+    }
+
+    /// square-and-multiply
+    pub fn pow(base: FieldSize, mut exp: u128, p: FieldSize) -> FieldSize {
+        let mut result = 1;
+        let mut base = reduce(base, p);
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = mul(result, base, p);
+            }
+            base = mul(base, base, p);
+            exp >>= 1;
+        }
+        result
+    }
+
+    /// Fermat's little theorem: a^(p-2) = a^-1
+    pub fn inverse(a: FieldSize, p: FieldSize) -> FieldSize {
+        pow(a, (p - 2) as u128, p)
+    }
+
+    /// schoolbook multiplication, coefficients in ascending order
+    pub fn poly_mul(a: &[FieldSize], b: &[FieldSize], p: FieldSize) -> Vec<FieldSize> {
+        let mut result = vec![0; a.len() + b.len() - 1];
+        for (i, x) in a.iter().enumerate() {
+            for (j, y) in b.iter().enumerate() {
+                result[i + j] = reduce(result[i + j] + mul(*x, *y, p), p);
+            }
+        }
+        result
+    }
+
+    pub fn poly_evaluate(coefficients: &[FieldSize], x: FieldSize, p: FieldSize) -> FieldSize {
+        coefficients
+            .iter()
+            .rev()
+            .fold(0, |acc, c| reduce(mul(acc, x, p) + reduce(*c, p), p))
+    }
+}
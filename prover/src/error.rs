@@ -0,0 +1,18 @@
+use algebra::error::AlgebraError;
+use algebra::finite_field::FieldSize;
+use thiserror::Error;
+
+/// Reasons a trace cannot be turned into a proof.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ProverError {
+    #[error(transparent)]
+    Algebra(#[from] AlgebraError),
+    #[error("value {value} at row {row} does not fit in {bits} bits")]
+    ValueOutOfRange {
+        row: usize,
+        value: FieldSize,
+        bits: u32,
+    },
+    #[error("a range of {bits} bits does not fit in a table smaller than the field")]
+    RangeTooLarge { bits: u32 },
+}
@@ -1,3 +1,7 @@
+pub mod error;
+pub mod permutation;
+pub mod range_check;
+
 pub fn add(left: usize, right: usize) -> usize {
     left + right
 }
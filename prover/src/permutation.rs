@@ -0,0 +1,186 @@
+use algebra::error::AlgebraError;
+use algebra::finite_field::FieldElement;
+
+/// Columns whose rows are sorted for a permutation argument.
+///
+/// Row `i` of `columns` is row `permutation[i]` of the original columns.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SortedColumns {
+    pub columns: Vec<Vec<FieldElement>>,
+    pub permutation: Vec<usize>,
+}
+
+impl SortedColumns {
+    /// Sorts the rows by their first `key_columns` columns, compared lexicographically by value.
+    /// The sort is stable, rows with equal keys keep their original order.
+    pub fn sort_rows(columns: &[Vec<FieldElement>], key_columns: usize) -> Self {
+        assert!(key_columns <= columns.len());
+        let height = columns.first().map_or(0, Vec::len);
+        assert!(
+            columns.iter().all(|column| column.len() == height),
+            "All columns must have the same height"
+        );
+
+        let key = |row: usize| -> Vec<_> {
+            columns[..key_columns]
+                .iter()
+                .map(|column| column[row].value())
+                .collect()
+        };
+        let mut permutation: Vec<usize> = (0..height).collect();
+        permutation.sort_by_key(|row| key(*row));
+
+        let sorted = columns
+            .iter()
+            .map(|column| permutation.iter().map(|row| column[*row].clone()).collect())
+            .collect();
+        Self {
+            columns: sorted,
+            permutation,
+        }
+    }
+
+    pub fn sort_by_value(column: &[FieldElement]) -> Self {
+        Self::sort_rows(&[column.to_vec()], 1)
+    }
+
+    /// memory access rows sorted by address, then by timestamp
+    pub fn sort_by_address(
+        address: &[FieldElement],
+        timestamp: &[FieldElement],
+        value: &[FieldElement],
+    ) -> Self {
+        Self::sort_rows(&[address.to_vec(), timestamp.to_vec(), value.to_vec()], 2)
+    }
+
+    /// Checks that the permutation is a bijection mapping the original rows onto the sorted ones
+    /// and that the first `key_columns` columns are sorted.
+    pub fn validate(&self, original: &[Vec<FieldElement>], key_columns: usize) -> bool {
+        let height = self.permutation.len();
+        let mut seen = vec![false; height];
+        for row in &self.permutation {
+            if *row >= height || seen[*row] {
+                return false;
+            }
+            seen[*row] = true;
+        }
+
+        if original.len() != self.columns.len() || key_columns > self.columns.len() {
+            return false;
+        }
+        for (sorted, original) in self.columns.iter().zip(original) {
+            if sorted.len() != height || original.len() != height {
+                return false;
+            }
+            if self
+                .permutation
+                .iter()
+                .enumerate()
+                .any(|(i, row)| sorted[i] != original[*row])
+            {
+                return false;
+            }
+        }
+
+        let key = |row: usize| -> Vec<_> {
+            self.columns[..key_columns]
+                .iter()
+                .map(|column| column[row].value())
+                .collect::<Vec<_>>()
+        };
+        (1..height).all(|row| key(row - 1) <= key(row))
+    }
+}
+
+/// Running product column of the grand-product argument.
+///
+/// Rows are compressed as `c = Σ alpha^j * column_j`, then `z_0 = 1` and
+/// `z_{i+1} = z_i * (gamma - c_original_i) / (gamma - c_sorted_i)`. The last entry is one when the
+/// sorted rows are a permutation of the original ones, and otherwise only for a negligible set of alpha, gamma.
+pub fn grand_product(
+    original: &[Vec<FieldElement>],
+    sorted: &[Vec<FieldElement>],
+    alpha: &FieldElement,
+    gamma: &FieldElement,
+) -> Result<Vec<FieldElement>, AlgebraError> {
+    let finite_field = alpha.finite_field();
+    let height = original.first().map_or(0, Vec::len);
+
+    let compress = |columns: &[Vec<FieldElement>], row: usize| {
+        let mut result = finite_field.zero();
+        let mut power = finite_field.one();
+        for column in columns {
+            result += &power * &column[row];
+            power = &power * alpha;
+        }
+        result
+    };
+
+    let mut products = Vec::with_capacity(height + 1);
+    let mut current = finite_field.one();
+    products.push(current.clone());
+    for row in 0..height {
+        let numerator = gamma - &compress(original, row);
+        let denominator = gamma - &compress(sorted, row);
+        current = &(&current * &numerator) * &denominator.try_inverse()?;
+        products.push(current.clone());
+    }
+    Ok(products)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::permutation::{grand_product, SortedColumns};
+    use algebra::finite_field::FiniteField;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_sort_by_address() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let address: Vec<_> = finite_field.elements([5, 2, 5, 2, 9]).collect();
+        let timestamp: Vec<_> = finite_field.elements([0, 1, 2, 3, 4]).collect();
+        let value: Vec<_> = finite_field.elements([10, 20, 11, 20, 30]).collect();
+        let original = vec![address.clone(), timestamp.clone(), value.clone()];
+
+        let sorted = SortedColumns::sort_by_address(&address, &timestamp, &value);
+        assert_eq!(sorted.permutation, vec![1, 3, 0, 2, 4]);
+        assert_eq!(
+            sorted.columns[2],
+            finite_field
+                .elements([20, 20, 10, 11, 30])
+                .collect::<Vec<_>>()
+        );
+        assert!(sorted.validate(&original, 2));
+
+        let alpha = finite_field.element(13);
+        let gamma = finite_field.element(41);
+        let products = grand_product(&original, &sorted.columns, &alpha, &gamma).unwrap();
+        assert_eq!(products.len(), 6);
+        assert_eq!(products[5], finite_field.one());
+
+        let mut tampered = sorted.clone();
+        tampered.columns[2][0] = finite_field.element(21);
+        assert!(!tampered.validate(&original, 2));
+        let products = grand_product(&original, &tampered.columns, &alpha, &gamma).unwrap();
+        assert_ne!(products[5], finite_field.one());
+
+        let mut unsorted = sorted.clone();
+        unsorted.permutation.swap(0, 2);
+        for column in unsorted.columns.iter_mut() {
+            column.swap(0, 2);
+        }
+        assert!(!unsorted.validate(&original, 2));
+    }
+
+    #[test]
+    fn test_sort_by_value() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let column: Vec<_> = finite_field.elements([3, 1, 2, 1]).collect();
+        let sorted = SortedColumns::sort_by_value(&column);
+        assert_eq!(
+            sorted.columns[0],
+            finite_field.elements([1, 1, 2, 3]).collect::<Vec<_>>()
+        );
+        assert!(sorted.validate(&[column], 1));
+    }
+}
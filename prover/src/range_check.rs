@@ -0,0 +1,126 @@
+use crate::error::ProverError;
+use algebra::finite_field::{FieldElement, FieldSize, FiniteField};
+use std::sync::Arc;
+
+/// Preprocessed table column `0, 1, ..., 2^bits - 1` looked up by range checked trace columns.
+#[derive(Debug, Clone)]
+pub struct RangeCheckTable {
+    bits: u32,
+    size: usize,
+    finite_field: Arc<FiniteField>,
+}
+
+impl RangeCheckTable {
+    /// Fails when `2^bits` doesn't fit in a `usize` or is not smaller than the field.
+    pub fn new(finite_field: Arc<FiniteField>, bits: u32) -> Result<Self, ProverError> {
+        let size = 1usize
+            .checked_shl(bits)
+            .filter(|size| (*size as u128) < finite_field.prime as u128)
+            .ok_or(ProverError::RangeTooLarge { bits })?;
+        Ok(Self {
+            bits,
+            size,
+            finite_field,
+        })
+    }
+
+    pub fn bits(&self) -> u32 {
+        self.bits
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn column(&self) -> Vec<FieldElement> {
+        self.finite_field
+            .elements(0..self.size() as FieldSize)
+            .collect()
+    }
+
+    /// How many times every table value is looked up by `values`, the multiplicity column
+    /// of a logUp style lookup, aligned with `column`. Fails on the first value outside the range.
+    pub fn multiplicities(
+        &self,
+        values: &[FieldElement],
+    ) -> Result<Vec<FieldElement>, ProverError> {
+        let mut counts = vec![0 as FieldSize; self.size()];
+        for (row, value) in values.iter().enumerate() {
+            let value = value.value();
+            if value >= self.size() as FieldSize {
+                return Err(ProverError::ValueOutOfRange {
+                    row,
+                    value,
+                    bits: self.bits,
+                });
+            }
+            counts[value as usize] += 1;
+        }
+        Ok(self.finite_field.elements(counts).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::ProverError;
+    use crate::range_check::RangeCheckTable;
+    use algebra::finite_field::FiniteField;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_range_check_table() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let table = RangeCheckTable::new(Arc::clone(&finite_field), 2).unwrap();
+
+        assert_eq!(
+            table.column(),
+            finite_field.elements([0, 1, 2, 3]).collect::<Vec<_>>()
+        );
+
+        let values: Vec<_> = finite_field.elements([3, 0, 3, 1]).collect();
+        assert_eq!(
+            table.multiplicities(&values),
+            Ok(finite_field.elements([1, 1, 0, 2]).collect())
+        );
+
+        let values: Vec<_> = finite_field.elements([3, 4]).collect();
+        assert_eq!(
+            table.multiplicities(&values),
+            Err(ProverError::ValueOutOfRange {
+                row: 1,
+                value: 4,
+                bits: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_range_too_large() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        assert_eq!(
+            RangeCheckTable::new(Arc::clone(&finite_field), 6)
+                .unwrap()
+                .size(),
+            64
+        );
+        for bits in [7, 63, 64, 127, 128, u32::MAX] {
+            assert_eq!(
+                RangeCheckTable::new(Arc::clone(&finite_field), bits).err(),
+                Some(ProverError::RangeTooLarge { bits })
+            );
+        }
+
+        // 2^107 - 1 holds any range a usize can count
+        let finite_field = Arc::new(FiniteField::new((1 << 107) - 1, 3));
+        assert_eq!(
+            RangeCheckTable::new(Arc::clone(&finite_field), 63)
+                .unwrap()
+                .size(),
+            1 << 63
+        );
+        assert_eq!(
+            RangeCheckTable::new(finite_field, usize::BITS).err(),
+            Some(ProverError::RangeTooLarge { bits: usize::BITS })
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use crate::error::ProofError;
+use algebra::finite_field::FieldElement;
+
+/// DEEP composition at a queried point `x` for the out of domain point `z`:
+/// `Σ c_i * (f_i(x) - f_i(z)) / (x - z)`.
+///
+/// The coefficients come from `DeepBatching::coefficients`, so both batching modes are checked the same way.
+pub fn deep_composition(
+    coefficients: &[FieldElement],
+    queried: &[FieldElement],
+    out_of_domain: &[FieldElement],
+    x: &FieldElement,
+    z: &FieldElement,
+) -> Result<FieldElement, ProofError> {
+    for values in [queried, out_of_domain] {
+        if values.len() != coefficients.len() {
+            return Err(ProofError::ColumnCountMismatch {
+                expected: coefficients.len(),
+                found: values.len(),
+            });
+        }
+    }
+    let denominator_inv = (x - z).try_inverse()?;
+
+    let mut result = x.finite_field().zero();
+    for ((coefficient, at_x), at_z) in coefficients.iter().zip(queried).zip(out_of_domain) {
+        result += coefficient * &(at_x - at_z);
+    }
+    Ok(&result * &denominator_inv)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::deep::deep_composition;
+    use crate::error::ProofError;
+    use algebra::error::AlgebraError;
+    use algebra::finite_field::FiniteField;
+    use algebra::poly;
+    use algebra::polynomial::Polynomial;
+    use crypto_primitives::proof_options::DeepBatching;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_deep_composition() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let columns = [
+            poly!(finite_field, [1, 2, 3]),
+            poly!(finite_field, [5, 0, 7, 1]),
+        ];
+        let z = finite_field.element(11);
+        let x = finite_field.element(40);
+        let queried: Vec<_> = columns.iter().map(|f| f.evaluate(x.clone())).collect();
+        let out_of_domain: Vec<_> = columns.iter().map(|f| f.evaluate(z.clone())).collect();
+
+        for batching in [DeepBatching::Independent, DeepBatching::Powers] {
+            let mut challenges = finite_field.elements([17, 23]);
+            let coefficients = batching.coefficients(2, || challenges.next().unwrap());
+
+            // prover side: Σ c_i * (f_i - f_i(z)) / (X - z) as a polynomial
+            let mut deep = Polynomial::new(vec![], Arc::clone(&finite_field));
+            for ((column, at_z), coefficient) in
+                columns.iter().zip(&out_of_domain).zip(&coefficients)
+            {
+                let numerator =
+                    column - &Polynomial::new(vec![at_z.clone()], Arc::clone(&finite_field));
+                let (quotient, _) = numerator
+                    .div_rem(&Polynomial::new(
+                        vec![(-z.clone()).abs(), finite_field.one()],
+                        Arc::clone(&finite_field),
+                    ))
+                    .unwrap();
+                deep = deep + quotient.scalar_mul(coefficient.clone());
+            }
+
+            assert_eq!(
+                deep_composition(&coefficients, &queried, &out_of_domain, &x, &z),
+                Ok(deep.evaluate(x.clone()))
+            );
+        }
+
+        let coefficients = vec![finite_field.one(); 2];
+        assert_eq!(
+            deep_composition(&coefficients, &queried[..1], &out_of_domain, &x, &z),
+            Err(ProofError::ColumnCountMismatch {
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            deep_composition(&coefficients, &queried, &out_of_domain, &z, &z),
+            Err(ProofError::Algebra(AlgebraError::DivisionByZero))
+        );
+    }
+}
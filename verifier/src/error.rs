@@ -0,0 +1,28 @@
+use algebra::error::AlgebraError;
+use crypto_primitives::error::{CommitmentError, ProofOptionsError, TranscriptError, WireError};
+use thiserror::Error;
+
+/// Reasons a proof is rejected, surfaced to the caller of the verifier.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ProofError {
+    #[error(transparent)]
+    Algebra(#[from] AlgebraError),
+    #[error(transparent)]
+    Commitment(#[from] CommitmentError),
+    #[error(transparent)]
+    Wire(#[from] WireError),
+    #[error(transparent)]
+    Options(#[from] ProofOptionsError),
+    #[error(transparent)]
+    Transcript(#[from] TranscriptError),
+    #[error("the authentication path does not match the committed root")]
+    InvalidAuthenticationPath,
+    #[error("FRI remainder has {found} coefficients, at most {max} are allowed")]
+    RemainderDegreeTooHigh { max: usize, found: usize },
+    #[error("FRI remainder does not match the last layer at position {0}")]
+    RemainderMismatch(usize),
+    #[error("the last FRI layer has {codeword} values for a domain of {domain} points")]
+    RemainderLengthMismatch { domain: usize, codeword: usize },
+    #[error("expected values for {expected} columns, found {found}")]
+    ColumnCountMismatch { expected: usize, found: usize },
+}
@@ -0,0 +1,96 @@
+use crate::error::ProofError;
+use algebra::finite_field::FieldElement;
+use algebra::polynomial::Polynomial;
+use crypto_primitives::proof_options::ProofOptions;
+
+/// Checks the coefficients sent in place of the last FRI layer: at most
+/// `fri_remainder_max_degree + 1` of them, and their polynomial must agree with the
+/// last layer `codeword` on every point of `domain`, which can't be empty and must have one value
+/// per point.
+pub fn verify_remainder(
+    options: &ProofOptions,
+    remainder: &[FieldElement],
+    domain: &[FieldElement],
+    codeword: &[FieldElement],
+) -> Result<(), ProofError> {
+    let max = options.fri_remainder_max_degree + 1;
+    if remainder.len() > max {
+        return Err(ProofError::RemainderDegreeTooHigh {
+            max,
+            found: remainder.len(),
+        });
+    }
+    if domain.is_empty() || codeword.len() != domain.len() {
+        return Err(ProofError::RemainderLengthMismatch {
+            domain: domain.len(),
+            codeword: codeword.len(),
+        });
+    }
+    let first = &domain[0];
+    let polynomial = Polynomial::new(remainder.to_vec(), first.finite_field().clone());
+    for (position, (x, y)) in domain.iter().zip(codeword).enumerate() {
+        if polynomial.evaluate(x.clone()) != *y {
+            return Err(ProofError::RemainderMismatch(position));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::ProofError;
+    use crate::fri::verify_remainder;
+    use algebra::finite_field::FiniteField;
+    use algebra::poly;
+    use crypto_primitives::proof_options::{HashKind, ProofOptions};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_verify_remainder() {
+        let finite_field = Arc::new(FiniteField::new(97, 5));
+        let options =
+            ProofOptions::new(4, 10, 0, 2, 1, HashKind::Rescue, (*finite_field).clone()).unwrap();
+        let domain: Vec<_> = finite_field.elements(1..9).collect();
+        let remainder = poly!(finite_field, [3, 2]);
+        let codeword: Vec<_> = domain
+            .iter()
+            .map(|x| remainder.evaluate(x.clone()))
+            .collect();
+        let coefficients = vec![finite_field.element(3), finite_field.element(2)];
+
+        assert_eq!(
+            verify_remainder(&options, &coefficients, &domain, &codeword),
+            Ok(())
+        );
+
+        let mut too_long = coefficients.clone();
+        too_long.push(finite_field.one());
+        assert_eq!(
+            verify_remainder(&options, &too_long, &domain, &codeword),
+            Err(ProofError::RemainderDegreeTooHigh { max: 2, found: 3 })
+        );
+
+        let mut tampered = codeword.clone();
+        tampered[5] = &tampered[5] + &finite_field.one();
+        assert_eq!(
+            verify_remainder(&options, &coefficients, &domain, &tampered),
+            Err(ProofError::RemainderMismatch(5))
+        );
+
+        // every point of the domain must be checked
+        for (domain, codeword) in [
+            (&domain[..], &codeword[..7]),
+            (&domain[..], &[][..]),
+            (&domain[..7], &codeword[..]),
+            (&[][..], &[][..]),
+        ] {
+            assert_eq!(
+                verify_remainder(&options, &coefficients, domain, codeword),
+                Err(ProofError::RemainderLengthMismatch {
+                    domain: domain.len(),
+                    codeword: codeword.len()
+                })
+            );
+        }
+    }
+}
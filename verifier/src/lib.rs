@@ -1,3 +1,7 @@
+pub mod deep;
+pub mod error;
+pub mod fri;
+
 pub fn add(left: usize, right: usize) -> usize {
     left + right
 }
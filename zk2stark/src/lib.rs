@@ -0,0 +1,40 @@
+//! Single entry point to the workspace crates.
+//!
+//! ```
+//! use zk2stark::prelude::*;
+//! use std::sync::Arc;
+//!
+//! let finite_field = Arc::new(FiniteField::new(97, 5));
+//! let p = poly!(finite_field, [1, 2, 3]);
+//! assert_eq!(p.evaluate(finite_field.one()), felt!(finite_field, 6));
+//!
+//! let evaluations = p.evaluate_on_domain(4);
+//! let mut tree = MerkleTree::new(Blake3Hasher, evaluations).unwrap();
+//! let _root = tree.commit();
+//! assert_eq!(Polynomial::from_bytes(&p.to_bytes(), &finite_field), Ok(p));
+//! ```
+pub use algebra;
+pub use crypto_primitives;
+
+pub mod prelude {
+    pub use algebra::error::{AlgebraError, MatrixError, PolynomialError};
+    pub use algebra::field::Field;
+    pub use algebra::finite_field::{FieldElement, FieldSize, FiniteField};
+    pub use algebra::matrix::Matrix;
+    pub use algebra::polynomial::Polynomial;
+    pub use algebra::{felt, matrix, points, poly};
+    pub use crypto_primitives::error::{
+        CommitmentError, HashParameterError, ProofOptionsError, TranscriptError, WireError,
+    };
+    pub use crypto_primitives::hash::{
+        Blake3Hasher, Hasher, PoseidonHash, RescueHash, RescueHashBuilder, Sha256Hasher, WideDigest,
+    };
+    pub use crypto_primitives::incremental_merkle_tree::IncrementalMerkleTree;
+    pub use crypto_primitives::merkle_mountain_range::{MerkleMountainRange, MountainRangeProof};
+    pub use crypto_primitives::merkle_tree::{
+        MerkleMultiProof, MerkleNode, MerklePadding, MerkleProof, MerkleTree,
+    };
+    pub use crypto_primitives::proof_options::{DeepBatching, HashKind, ProofOptions};
+    pub use crypto_primitives::sparse_merkle_tree::{SparseMerkleProof, SparseMerkleTree};
+    pub use crypto_primitives::transcript::Transcript;
+}